@@ -166,10 +166,6 @@ endif
 # Rustdoc (libcore/std)
 ######################################################################
 
-ifeq ($(CFG_PANDOC),)
-  $(info cfg: no pandoc found, omitting library doc build)
-else
-
 # The rustdoc executable
 RUSTDOC = $(HBIN2_H_$(CFG_BUILD_TRIPLE))/rustdoc$(X_$(CFG_BUILD_TRIPLE))
 
@@ -191,7 +187,6 @@ endef
 
 $(eval $(call libdoc,core,$(CORELIB_CRATE),$(CORELIB_INPUTS)))
 $(eval $(call libdoc,std,$(STDLIB_CRATE),$(STDLIB_INPUTS)))
-endif
 
 
 ifdef CFG_DISABLE_DOCS
@@ -134,6 +134,15 @@ rt/$(1)/arch/$$(HOST_$(1))/libmorestack.a: $$(MORESTACK_OBJ_$(1))
 	@$$(call E, link: $$@)
 	$$(Q)$(AR_$(1)) rcs $$@ $$<
 
+# The runtime as an archive, with libuv, for rustc --staticlib
+rt/$(1)/librustrt_static.a: $$(RUNTIME_OBJS_$(1)) $$(RUNTIME_LIBS_$(1))
+	@$$(call E, link: $$@)
+	$$(Q)rm -rf $$@ rt/$(1)/libuv-objs
+	$$(Q)mkdir -p rt/$(1)/libuv-objs
+	$$(Q)cd rt/$(1)/libuv-objs && \
+	  $(AR_$(1)) x $$(CFG_BUILD_DIR)/$$(LIBUV_LIB_$(1))
+	$$(Q)$(AR_$(1)) rcs $$@ $$(RUNTIME_OBJS_$(1)) rt/$(1)/libuv-objs/*.o
+
 rt/$(1)/$(CFG_RUNTIME_$(1)): $$(RUNTIME_OBJS_$(1)) $$(MKFILE_DEPS) \
                         $$(RUNTIME_DEF_$(1)) \
                         $$(RUNTIME_LIBS_$(1))
@@ -22,23 +22,53 @@ $$(TLIB$(1)_T_$(2)_H_$(3))/libmorestack.a: \
 	@$$(call E, cp: $$@)
 	$$(Q)cp $$< $$@
 
+$$(TLIB$(1)_T_$(2)_H_$(3))/librustrt_static.a: \
+		rt/$(2)/librustrt_static.a
+	@$$(call E, cp: $$@)
+	$$(Q)cp $$< $$@
+
 $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_RUNTIME_$(2)): \
 		rt/$(2)/$(CFG_RUNTIME_$(2))
 	@$$(call E, cp: $$@)
 	$$(Q)cp $$< $$@
 
+# core and std carry their bitcode, so that executables built with --lto
+# can link them in. The stage0 compiler doesn't know the flag.
+ifeq ($(1),0)
+LTO_FLAGS$(1)_T_$(2)_H_$(3) =
+else
+LTO_FLAGS$(1)_T_$(2)_H_$(3) = --lto
+endif
+
 $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_CORELIB_$(2)): \
 		$$(CORELIB_CRATE) $$(CORELIB_INPUTS) \
 		$$(TSREQ$(1)_T_$(2)_H_$(3))
 	@$$(call E, compile_and_link: $$@)
-	$$(STAGE$(1)_T_$(2)_H_$(3)) -o $$@ $$< && touch $$@
+	$$(STAGE$(1)_T_$(2)_H_$(3)) $$(LTO_FLAGS$(1)_T_$(2)_H_$(3)) \
+		-o $$@ $$< && touch $$@
 
 $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_STDLIB_$(2)): \
 		$$(STDLIB_CRATE) $$(STDLIB_INPUTS) \
 	        $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_CORELIB_$(2)) \
 		$$(TSREQ$(1)_T_$(2)_H_$(3))
 	@$$(call E, compile_and_link: $$@)
-	$$(STAGE$(1)_T_$(2)_H_$(3)) -o $$@ $$< && touch $$@
+	$$(STAGE$(1)_T_$(2)_H_$(3)) $$(LTO_FLAGS$(1)_T_$(2)_H_$(3)) \
+		-o $$@ $$< && touch $$@
+
+# The rlibs are the crates' objects, which -Z static links into
+# executables and --staticlib into archives
+$$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_CORELIB_RLIB_$(2)): \
+		$$(CORELIB_CRATE) $$(CORELIB_INPUTS) \
+		$$(TSREQ$(1)_T_$(2)_H_$(3))
+	@$$(call E, compile: $$@)
+	$$(STAGE$(1)_T_$(2)_H_$(3)) -Z static -o $$@ $$< && touch $$@
+
+$$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_STDLIB_RLIB_$(2)): \
+		$$(STDLIB_CRATE) $$(STDLIB_INPUTS) \
+	        $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_CORELIB_RLIB_$(2)) \
+		$$(TSREQ$(1)_T_$(2)_H_$(3))
+	@$$(call E, compile: $$@)
+	$$(STAGE$(1)_T_$(2)_H_$(3)) -Z static -o $$@ $$< && touch $$@
 
 $$(TLIB$(1)_T_$(2)_H_$(3))/$(CFG_LIBSYNTAX_$(3)): \
                 $$(LIBSYNTAX_CRATE) $$(LIBSYNTAX_INPUTS) \
@@ -201,6 +201,7 @@ check-stage$(1)-T-$(2)-H-$(3)-exec:     				\
 	check-stage$(1)-T-$(2)-H-$(3)-bench-exec			\
 	check-stage$(1)-T-$(2)-H-$(3)-debuginfo-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-doc-exec \
+	check-stage$(1)-T-$(2)-H-$(3)-staticlib-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-pretty-exec
 
 # Only test the compiler-dependent crates when the target is
@@ -540,6 +541,53 @@ $(foreach host,$(CFG_HOST_TRIPLES), \
     $(eval $(call DEF_RUN_DOC_TEST,$(stage),$(target),$(host),$(docname)))))))
 
 
+######################################################################
+# Linking a --staticlib crate into a C program
+######################################################################
+
+define DEF_STATICLIB_TEST
+
+check-stage$(1)-T-$(2)-H-$(3)-staticlib-exec: \
+		$$(call TEST_OK_FILE,$(1),$(2),$(3),staticlib)
+
+# The program can only be run where it is built
+ifeq ($(2),$(3))
+
+$(3)/test/staticlib/stage$(1)/librustlib.a: \
+		$$(S)src/test/staticlib/rustlib.rs \
+		$$(SREQ$(1)_T_$(2)_H_$(3))
+	@$$(call E, compile_and_link: $$@)
+	$$(Q)mkdir -p $$(@D)
+	$$(STAGE$(1)_T_$(2)_H_$(3)) --staticlib -o $$@ $$<
+
+$(3)/test/staticlib/stage$(1)/main$$(X_$(2)): \
+		$$(S)src/test/staticlib/main.c \
+		$(3)/test/staticlib/stage$(1)/librustlib.a
+	@$$(call E, link: $$@)
+	$$(Q)$$(CC_$(2)) $$(CFG_GCCISH_CFLAGS_$(2)) -o $$@ $$< \
+		$$(@D)/librustlib.a `cat $$(@D)/librustlib.libs`
+
+$$(call TEST_OK_FILE,$(1),$(2),$(3),staticlib): \
+		$(3)/test/staticlib/stage$(1)/main$$(X_$(2))
+	@$$(call E, run: $$<)
+	$$(Q)$$< && touch $$@
+
+else
+
+$$(call TEST_OK_FILE,$(1),$(2),$(3),staticlib):
+	@$$(call E, warning: staticlib test disabled: cross-compiling)
+	touch $$@
+
+endif
+
+endef
+
+$(foreach host,$(CFG_HOST_TRIPLES), \
+ $(foreach target,$(CFG_TARGET_TRIPLES), \
+  $(foreach stage,$(STAGES), \
+   $(eval $(call DEF_STATICLIB_TEST,$(stage),$(target),$(host))))))
+
+
 ######################################################################
 # Extracting tests for docs
 ######################################################################
@@ -576,6 +624,7 @@ TEST_GROUPS = \
 	debuginfo \
 	doc \
 	$(foreach docname,$(DOC_TEST_NAMES),$(docname)) \
+	staticlib \
 	pretty \
 	pretty-rpass \
 	pretty-rpass-full \
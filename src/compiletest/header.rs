@@ -26,6 +26,8 @@ pub struct TestProps {
     // If present, the name of a file that this test should match when
     // pretty-printed
     pp_exact: Option<Path>,
+    // The mode to pretty-print in, as given to --pretty
+    pretty_mode: ~str,
     // Modules from aux directory that should be compiled
     aux_builds: ~[~str],
     // Environment settings to use during execution
@@ -43,6 +45,7 @@ pub fn load_props(testfile: &Path) -> TestProps {
     let mut exec_env = ~[];
     let mut compile_flags = None;
     let mut pp_exact = None;
+    let mut pretty_mode = None;
     let mut debugger_cmds = ~[];
     let mut check_lines = ~[];
     for iter_header(testfile) |ln| {
@@ -59,6 +62,10 @@ pub fn load_props(testfile: &Path) -> TestProps {
             pp_exact = parse_pp_exact(ln, testfile);
         }
 
+        if pretty_mode.is_none() {
+            pretty_mode = parse_pretty_mode(ln);
+        }
+
         for parse_aux_build(ln).each |ab| {
             aux_builds.push(*ab);
         }
@@ -81,6 +88,7 @@ pub fn load_props(testfile: &Path) -> TestProps {
         error_patterns: error_patterns,
         compile_flags: compile_flags,
         pp_exact: pp_exact,
+        pretty_mode: pretty_mode.get_or_default(~"normal"),
         aux_builds: aux_builds,
         exec_env: exec_env,
         debugger_cmds: debugger_cmds,
@@ -164,6 +172,10 @@ fn parse_pp_exact(line: ~str, testfile: &Path) -> Option<Path> {
     }
 }
 
+fn parse_pretty_mode(line: ~str) -> Option<~str> {
+    parse_name_value_directive(line, ~"pretty-mode")
+}
+
 fn parse_name_directive(line: ~str, directive: ~str) -> bool {
     str::contains(line, directive)
 }
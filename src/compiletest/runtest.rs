@@ -134,7 +134,8 @@ fn run_pretty_test(config: config, props: TestProps, testfile: &Path) {
     let mut round = 0;
     while round < rounds {
         logv(config, fmt!("pretty-printing round %d", round));
-        let ProcRes = print_source(config, testfile, srcs[round]);
+        let ProcRes = print_source(config, testfile, srcs[round],
+                                   copy props.pretty_mode);
 
         if ProcRes.status != 0 {
             fatal_ProcRes(fmt!("pretty-printing failed in round %d", round),
@@ -164,6 +165,9 @@ fn run_pretty_test(config: config, props: TestProps, testfile: &Path) {
 
     compare_source(expected, actual);
 
+    // The other modes print annotations or something other than source
+    if props.pretty_mode != ~"normal" { return; }
+
     // Finally, let's make sure it actually appears to remain valid code
     let ProcRes = typecheck_source(config, props, testfile, actual);
 
@@ -173,14 +177,17 @@ fn run_pretty_test(config: config, props: TestProps, testfile: &Path) {
 
     return;
 
-    fn print_source(config: config, testfile: &Path, src: ~str) -> ProcRes {
-        compose_and_run(config, testfile, make_pp_args(config, testfile),
+    fn print_source(config: config, testfile: &Path, src: ~str,
+                    pretty_mode: ~str) -> ProcRes {
+        compose_and_run(config, testfile,
+                        make_pp_args(config, testfile, pretty_mode),
                         ~[], config.compile_lib_path, Some(src))
     }
 
-    fn make_pp_args(config: config, _testfile: &Path) -> ProcArgs {
+    fn make_pp_args(config: config, _testfile: &Path,
+                    pretty_mode: ~str) -> ProcArgs {
         let prog = config.rustc_path;
-        let args = ~[~"-", ~"--pretty", ~"normal"];
+        let args = ~[~"-", ~"--pretty", pretty_mode];
         return ProcArgs {prog: prog.to_str(), args: args};
     }
 
@@ -9,7 +9,9 @@ if __name__ == '__main__':
         summary = {}
         fd = open(fname)
         for line in fd:
-            status, test = line.strip().split(' ', 1)
+            # The status, which has spaces for benchmarks, the test name
+            # and how long the test took in nanoseconds
+            status, test, ns = line.strip().rsplit(' ', 2)
             if not summary.has_key(status):
                 summary[status] = []
             summary[status].append(test)
@@ -32,7 +32,6 @@ struct Scheduler { priv opaque: () }
 struct SchedulerLoop { priv opaque: () }
 struct Kernel { priv opaque: () }
 struct Env { priv opaque: () }
-struct AllocHeader { priv opaque: () }
 struct MemoryRegion { priv opaque: () }
 
 #[cfg(target_arch="x86")]
@@ -41,10 +41,7 @@ pub mod rustrt {
     }
 }
 
-const lz_none : c_int = 0x0;   // Huffman-coding only.
-const lz_fast : c_int = 0x1;   // LZ with only one probe
 const lz_norm : c_int = 0x80;  // LZ with 128 probes, "normal"
-const lz_best : c_int = 0xfff; // LZ with 4095 probes, "best"
 
 pub fn deflate_bytes(bytes: &[const u8]) -> ~[u8] {
     do vec::as_const_buf(bytes) |b, len| {
@@ -26,6 +26,7 @@ use core::char;
 use core::hash::Streaming;
 use core::hash;
 use core::io::{Writer, WriterUtil};
+use core::io;
 use core::libc::{c_int, c_uint, c_char};
 use core::os::consts::{macos, freebsd, linux, android, win32};
 use core::os;
@@ -163,6 +164,8 @@ pub mod jit {
 
 pub mod write {
     use back::link::jit;
+    use back::link::{binutil, run_binutil};
+    use back::lto;
     use back::link::{WriteOutputFile, output_type};
     use back::link::{output_type_assembly, output_type_bitcode};
     use back::link::{output_type_exe, output_type_llvm_assembly};
@@ -172,13 +175,23 @@ pub mod write {
     use driver::session;
     use lib::llvm::llvm;
     use lib::llvm::{False, True, ModuleRef, mk_pass_manager, mk_target_data};
+    use lib::llvm::{PassManagerRef, TargetDataRef, ValueRef};
     use lib;
+    use middle::trans::monomorphize;
 
     use core::prelude::*;
+    use core::cell::Cell;
+    use core::hashmap::linear::LinearSet;
     use core::libc::{c_char, c_int, c_uint};
+    use core::os;
     use core::path::Path;
+    use core::ptr;
     use core::str;
     use core::run;
+    use core::uint;
+    use core::vec;
+    use std::future;
+    use std::sort;
 
     pub fn is_object_or_assembly_or_exe(ot: output_type) -> bool {
         if ot == output_type_assembly || ot == output_type_object ||
@@ -188,10 +201,69 @@ pub mod write {
         return false;
     }
 
+    // Runs the function passes for `optimize` over `llmod` right away, and
+    // adds the module passes to `llpm`
+    unsafe fn add_optimization_passes(llmod: ModuleRef,
+                                      llpm: PassManagerRef,
+                                      lltd: TargetDataRef,
+                                      optimize: session::OptLevel) {
+        let fpm = mk_pass_manager();
+        llvm::LLVMAddTargetData(lltd, fpm.llpm);
+
+        let FPMB = llvm::LLVMPassManagerBuilderCreate();
+        llvm::LLVMPassManagerBuilderSetOptLevel(FPMB, 2u as c_uint);
+        llvm::LLVMPassManagerBuilderPopulateFunctionPassManager(FPMB,
+                                                                fpm.llpm);
+        llvm::LLVMPassManagerBuilderDispose(FPMB);
+
+        llvm::LLVMRunPassManager(fpm.llpm, llmod);
+        let mut threshold = 225;
+        if optimize == session::Aggressive { threshold = 275; }
+
+        let MPMB = llvm::LLVMPassManagerBuilderCreate();
+        llvm::LLVMPassManagerBuilderSetOptLevel(MPMB, optimize as c_uint);
+        llvm::LLVMPassManagerBuilderSetSizeLevel(MPMB, False);
+        llvm::LLVMPassManagerBuilderSetDisableUnitAtATime(MPMB, False);
+        llvm::LLVMPassManagerBuilderSetDisableUnrollLoops(MPMB, False);
+        llvm::LLVMPassManagerBuilderSetDisableSimplifyLibCalls(MPMB, False);
+
+        if threshold != 0u {
+            llvm::LLVMPassManagerBuilderUseInlinerWithThreshold
+                (MPMB, threshold as c_uint);
+        }
+        llvm::LLVMPassManagerBuilderPopulateModulePassManager(MPMB, llpm);
+
+        llvm::LLVMPassManagerBuilderDispose(MPMB);
+    }
+
+    fn codegen_opt_level(optimize: session::OptLevel) -> c_int {
+        let LLVMOptNone       = 0 as c_int; // -O0
+        let LLVMOptLess       = 1 as c_int; // -O1
+        let LLVMOptDefault    = 2 as c_int; // -O2, -Os
+        let LLVMOptAggressive = 3 as c_int; // -O3
+
+        match optimize {
+          session::No => LLVMOptNone,
+          session::Less => LLVMOptLess,
+          session::Default => LLVMOptDefault,
+          session::Aggressive => LLVMOptAggressive
+        }
+    }
+
     pub fn run_passes(sess: Session, llmod: ModuleRef,
             output_type: output_type, output: &Path) {
         unsafe {
             let opts = sess.opts;
+            if opts.lto && output_type == output_type_exe &&
+                   !*sess.building_library {
+                lto::run(sess, llmod);
+            }
+            if opts.codegen_units > 1u && !opts.jit &&
+                   (output_type == output_type_object ||
+                    output_type == output_type_exe) {
+                return run_passes_in_units(sess, llmod, output,
+                                           opts.codegen_units);
+            }
             if sess.time_llvm_passes() { llvm::LLVMRustEnableTimePasses(); }
             let mut pm = mk_pass_manager();
             let td = mk_target_data(sess.targ_cfg.target_strs.data_layout);
@@ -230,53 +302,12 @@ pub mod write {
             // tool?
 
             if opts.optimize != session::No {
-                let fpm = mk_pass_manager();
-                llvm::LLVMAddTargetData(td.lltd, fpm.llpm);
-
-                let FPMB = llvm::LLVMPassManagerBuilderCreate();
-                llvm::LLVMPassManagerBuilderSetOptLevel(FPMB, 2u as c_uint);
-                llvm::LLVMPassManagerBuilderPopulateFunctionPassManager(
-                    FPMB, fpm.llpm);
-                llvm::LLVMPassManagerBuilderDispose(FPMB);
-
-                llvm::LLVMRunPassManager(fpm.llpm, llmod);
-                let mut threshold = 225;
-                if opts.optimize == session::Aggressive { threshold = 275; }
-
-                let MPMB = llvm::LLVMPassManagerBuilderCreate();
-                llvm::LLVMPassManagerBuilderSetOptLevel(MPMB,
-                                                        opts.optimize as
-                                                            c_uint);
-                llvm::LLVMPassManagerBuilderSetSizeLevel(MPMB, False);
-                llvm::LLVMPassManagerBuilderSetDisableUnitAtATime(MPMB,
-                                                                  False);
-                llvm::LLVMPassManagerBuilderSetDisableUnrollLoops(MPMB,
-                                                                  False);
-                llvm::LLVMPassManagerBuilderSetDisableSimplifyLibCalls(MPMB,
-                                                                       False);
-
-                if threshold != 0u {
-                    llvm::LLVMPassManagerBuilderUseInlinerWithThreshold
-                        (MPMB, threshold as c_uint);
-                }
-                llvm::LLVMPassManagerBuilderPopulateModulePassManager(
-                    MPMB, pm.llpm);
-
-                llvm::LLVMPassManagerBuilderDispose(MPMB);
+                add_optimization_passes(llmod, pm.llpm, td.lltd,
+                                        opts.optimize);
             }
             if !sess.no_verify() { llvm::LLVMAddVerifierPass(pm.llpm); }
             if is_object_or_assembly_or_exe(output_type) || opts.jit {
-                let LLVMOptNone       = 0 as c_int; // -O0
-                let LLVMOptLess       = 1 as c_int; // -O1
-                let LLVMOptDefault    = 2 as c_int; // -O2, -Os
-                let LLVMOptAggressive = 3 as c_int; // -O3
-
-                let mut CodeGenOptLevel = match opts.optimize {
-                  session::No => LLVMOptNone,
-                  session::Less => LLVMOptLess,
-                  session::Default => LLVMOptDefault,
-                  session::Aggressive => LLVMOptAggressive
-                };
+                let CodeGenOptLevel = codegen_opt_level(opts.optimize);
 
                 if opts.jit {
                     // If we are using JIT, go ahead and create and
@@ -395,6 +426,293 @@ pub mod write {
         }
     }
 
+    // What the task emitting one codegen unit needs; the module itself
+    // can't be shared, so each task reads its own copy from `bitcode`
+    struct CodegenUnit {
+        bitcode: Path,
+        output: Path,
+        // The external functions whose bodies this unit emits
+        owned: ~[~str],
+        // Whether this unit emits the crate's external statics
+        owns_statics: bool,
+        triple: ~str,
+        data_layout: ~str,
+        optimize: session::OptLevel,
+        verify: bool
+    }
+
+    pub unsafe fn value_name(llval: ValueRef) -> ~str {
+        str::raw::from_c_str(llvm::LLVMGetValueName(llval))
+    }
+
+    pub unsafe fn is_external_definition(llval: ValueRef) -> bool {
+        llvm::LLVMIsDeclaration(llval) == False &&
+            llvm::LLVMGetLinkage(llval) ==
+                lib::llvm::ExternalLinkage as c_uint
+    }
+
+    unsafe fn last_llvm_error(msg: &str) -> ~str {
+        let cstr = llvm::LLVMRustGetLastError();
+        if cstr == ptr::null() {
+            str::from_slice(msg)
+        } else {
+            fmt!("%s: %s", msg, str::raw::from_c_str(cstr))
+        }
+    }
+
+    /**
+     * Makes the internal functions and statics of `llmod` hidden external
+     * symbols, so that each one is emitted once, by the unit that owns it,
+     * and any unit can call or refer to it. The joined object makes them
+     * local again.
+     */
+    unsafe fn promote_internal(llmod: ModuleRef) {
+        unsafe fn promote(llval: ValueRef, anon: &mut uint) {
+            let linkage = llvm::LLVMGetLinkage(llval);
+            if llvm::LLVMIsDeclaration(llval) == True ||
+                   (linkage != lib::llvm::InternalLinkage as c_uint &&
+                    linkage != lib::llvm::PrivateLinkage as c_uint) {
+                return;
+            }
+            let name = value_name(llval);
+            if str::starts_with(name, "llvm.") { return; }
+            // Every unit must give an unnamed value the same symbol
+            if name.is_empty() {
+                do str::as_c_str(fmt!("_rust_cgu_anon%u", *anon)) |buf| {
+                    llvm::LLVMSetValueName(llval, buf);
+                }
+                *anon += 1u;
+            }
+            llvm::LLVMSetLinkage(llval,
+                                 lib::llvm::ExternalLinkage as c_uint);
+            llvm::LLVMSetVisibility(llval,
+                                    lib::llvm::HiddenVisibility as c_uint);
+        }
+
+        let mut anon = 0u;
+        let mut llfn = llvm::LLVMGetFirstFunction(llmod);
+        while llfn.is_not_null() {
+            promote(llfn, &mut anon);
+            llfn = llvm::LLVMGetNextFunction(llfn);
+        }
+        let mut llglobal = llvm::LLVMGetFirstGlobal(llmod);
+        while llglobal.is_not_null() {
+            promote(llglobal, &mut anon);
+            llglobal = llvm::LLVMGetNextGlobal(llglobal);
+        }
+    }
+
+    /**
+     * Assigns each external function defined in `llmod` to one of `units`
+     * parts. The biggest functions are placed first, each into the part
+     * that has the fewest instructions so far, so the parts take about as
+     * long to optimize.
+     */
+    fn partition(llmod: ModuleRef, units: uint) -> ~[~[~str]] {
+        let mut fns = ~[];
+        unsafe {
+            let mut llfn = llvm::LLVMGetFirstFunction(llmod);
+            while llfn.is_not_null() {
+                if is_external_definition(llfn) {
+                    fns.push((monomorphize::count_insns(llfn),
+                              value_name(llfn)));
+                }
+                llfn = llvm::LLVMGetNextFunction(llfn);
+            }
+        }
+        let fns = sort::merge_sort(fns, |&(a, _), &(b, _)| a >= b);
+
+        let mut parts = vec::from_fn(units, |_| ~[]);
+        let mut sizes = vec::from_elem(units, 0u);
+        for fns.each |&(size, ref name)| {
+            let mut least = 0u;
+            for uint::range(1u, units) |i| {
+                if sizes[i] < sizes[least] { least = i; }
+            }
+            parts[least].push(copy *name);
+            sizes[least] += size;
+        }
+        parts
+    }
+
+    /**
+     * Optimizes and emits one codegen unit as an object file. Functions
+     * the unit doesn't own become available_externally: they can still be
+     * inlined here, but their code is emitted by the unit that owns them.
+     * Those the unit never refers to are dropped before optimizing, so
+     * each unit only optimizes its own functions and what they call.
+     */
+    fn emit_unit(unit: CodegenUnit) -> Result<(), ~str> {
+        unsafe {
+            let llmb = str::as_c_str(unit.bitcode.to_str(), |buf| {
+                llvm::LLVMRustCreateMemoryBufferWithContentsOfFile(buf)
+            });
+            if llmb.is_null() {
+                return Err(last_llvm_error("could not read bitcode"));
+            }
+            let llcx = llvm::LLVMContextCreate();
+            let llmod = llvm::LLVMRustParseBitcodeInContext(llcx, llmb);
+            llvm::LLVMDisposeMemoryBuffer(llmb);
+            if llmod.is_null() {
+                llvm::LLVMContextDispose(llcx);
+                return Err(last_llvm_error("could not parse bitcode"));
+            }
+
+            let mut owned = LinearSet::new();
+            for unit.owned.each |name| { owned.insert(copy *name); }
+            let available_externally =
+                lib::llvm::AvailableExternallyLinkage as c_uint;
+            let mut llfn = llvm::LLVMGetFirstFunction(llmod);
+            while llfn.is_not_null() {
+                if is_external_definition(llfn) &&
+                       !owned.contains(&value_name(llfn)) {
+                    llvm::LLVMSetLinkage(llfn, available_externally);
+                }
+                llfn = llvm::LLVMGetNextFunction(llfn);
+            }
+            if !unit.owns_statics {
+                let mut llglobal = llvm::LLVMGetFirstGlobal(llmod);
+                while llglobal.is_not_null() {
+                    if is_external_definition(llglobal) {
+                        llvm::LLVMSetLinkage(llglobal, available_externally);
+                    }
+                    llglobal = llvm::LLVMGetNextGlobal(llglobal);
+                }
+                // A library's metadata and bitcode are internal, and only
+                // llvm.used keeps them; `ld -r` would join every unit's
+                // copy into one section, so only the first unit has them
+                let llused = str::as_c_str("llvm.used", |buf| {
+                    llvm::LLVMGetNamedGlobal(llmod, buf)
+                });
+                if llused.is_not_null() { llvm::LLVMDeleteGlobal(llused); }
+            }
+
+            // Drop the bodies owned by other units that this one doesn't
+            // call, and whatever only they used, before optimizing
+            {
+                let dce = mk_pass_manager();
+                llvm::LLVMAddGlobalDCEPass(dce.llpm);
+                llvm::LLVMRunPassManager(dce.llpm, llmod);
+            }
+
+            let ok = {
+                let pm = mk_pass_manager();
+                let td = mk_target_data(unit.data_layout);
+                llvm::LLVMAddTargetData(td.lltd, pm.llpm);
+                if unit.verify { llvm::LLVMAddVerifierPass(pm.llpm); }
+                if unit.optimize != session::No {
+                    add_optimization_passes(llmod, pm.llpm, td.lltd,
+                                            unit.optimize);
+                }
+                // Drop the other units' bodies that were inlined
+                // everywhere they were called
+                llvm::LLVMAddGlobalDCEPass(pm.llpm);
+                do str::as_c_str(unit.triple) |buf_t| {
+                    do str::as_c_str(unit.output.to_str()) |buf_o| {
+                        llvm::LLVMRustWriteOutputFile(
+                            pm.llpm,
+                            llmod,
+                            buf_t,
+                            buf_o,
+                            lib::llvm::ObjectFile as c_uint,
+                            codegen_opt_level(unit.optimize),
+                            true)
+                    }
+                }
+            };
+            let result = if ok {
+                Ok(())
+            } else {
+                Err(last_llvm_error("could not write output"))
+            };
+            llvm::LLVMDisposeModule(llmod);
+            llvm::LLVMContextDispose(llcx);
+            result
+        }
+    }
+
+    /**
+     * The --codegen-units version of `run_passes`: the crate is still
+     * translated as one module, which is written out as bitcode and then
+     * optimized and emitted as `units` object files by as many tasks.
+     * The objects are joined into `output` with `ld -r`, so the rest of
+     * the build sees a single object file, as usual. The internal
+     * definitions are emitted once, as hidden symbols, which keeps the
+     * address of a function the same in every unit; they are made local
+     * in the joined object.
+     */
+    pub fn run_passes_in_units(sess: Session, llmod: ModuleRef,
+                               output: &Path, units: uint) {
+        let opts = sess.opts;
+        let bitcode = output.with_filetype("bc");
+        unsafe {
+            promote_internal(llmod);
+            let written = str::as_c_str(bitcode.to_str(), |buf| {
+                llvm::LLVMWriteBitcodeToFile(llmod, buf)
+            });
+            if written != 0 {
+                sess.fatal(fmt!("could not write bitcode to %s",
+                                bitcode.to_str()));
+            }
+        }
+
+        let objects = vec::from_fn(units, |i| {
+            output.with_filetype(fmt!("%u.o", i))
+        });
+        let mut emitting = ~[];
+        for partition(llmod, units).eachi |i, part| {
+            emitting.push(CodegenUnit {
+                bitcode: copy bitcode,
+                output: copy objects[i],
+                owned: copy *part,
+                owns_statics: i == 0u,
+                triple: copy sess.targ_cfg.target_strs.target_triple,
+                data_layout: copy sess.targ_cfg.target_strs.data_layout,
+                optimize: opts.optimize,
+                verify: !sess.no_verify()
+            });
+        }
+        unsafe { llvm::LLVMDisposeModule(llmod); }
+
+        // Without thread support in LLVM the units are emitted one at a
+        // time, which is no faster than a single unit but still correct
+        let threaded = unsafe { llvm::LLVMRustStartMultithreading() };
+        let mut results = ~[];
+        if threaded {
+            let futures = do vec::map_consume(emitting) |unit| {
+                let unit = Cell(unit);
+                do future::spawn || { emit_unit(unit.take()) }
+            };
+            for futures.each |f| { results.push(copy *f.get_ref()); }
+        } else {
+            for vec::consume(emitting) |_, unit| {
+                results.push(emit_unit(unit));
+            }
+        }
+        for results.eachi |i, result| {
+            match *result {
+                Ok(()) => (),
+                Err(ref e) => {
+                    sess.err(fmt!("codegen unit %u: %s", i, *e));
+                }
+            }
+        }
+        sess.abort_if_errors();
+
+        let mut ld_args = ~[~"-r", ~"-o", output.to_str()];
+        ld_args.push_all(objects.map(|o| o.to_str()));
+        run_binutil(sess, binutil(sess, "ld"), ld_args);
+        // The linker on Mac OS X already makes hidden symbols local
+        if sess.targ_cfg.os != session::os_macos {
+            run_binutil(sess, binutil(sess, "objcopy"),
+                        ~[~"--localize-hidden", output.to_str()]);
+        }
+        if !opts.save_temps {
+            os::remove_file(&bitcode);
+            for objects.each |o| { os::remove_file(o); }
+        }
+    }
+
     pub fn run_ndk(sess: Session, assembly: &Path, object: &Path) {
         let cc_prog: ~str = match &sess.opts.android_cross_path {
             &Some(copy path) => {
@@ -761,6 +1079,166 @@ pub fn output_dll_filename(os: session::os, lm: LinkMeta) -> ~str {
            str::from_slice(dll_suffix);
 }
 
+// The system libraries every crate needs on `os`, which some linkers won't
+// find on their own
+fn os_link_args(os: session::os) -> ~[~str] {
+    let mut args = ~[];
+
+    // On linux librt and libdl are an indirect dependencies via rustrt,
+    // and binutils 2.22+ won't add them automatically
+    if os == session::os_linux {
+        args.push_all(~[~"-lrt", ~"-ldl"]);
+
+        // LLVM implements the `frem` instruction as a call to `fmod`,
+        // which lives in libm. Similar to above, on some linuxes we
+        // have to be explicit about linking to it. See #2510
+        args.push(~"-lm");
+    }
+    else if os == session::os_android {
+        args.push_all(~[~"-ldl", ~"-llog",  ~"-lsupc++",
+                        ~"-lgnustl_shared"]);
+        args.push(~"-lm");
+    }
+
+    if os == session::os_freebsd {
+        args.push_all(~[~"-pthread", ~"-lrt",
+                        ~"-L/usr/local/lib", ~"-lexecinfo",
+                        ~"-L/usr/local/lib/gcc46",
+                        ~"-L/usr/local/lib/gcc44", ~"-lstdc++",
+                        ~"-Wl,-z,origin",
+                        ~"-Wl,-rpath,/usr/local/lib/gcc46",
+                        ~"-Wl,-rpath,/usr/local/lib/gcc44"]);
+    }
+    args
+}
+
+// The path to one of the binutils, such as `ld`, for the target
+pub fn binutil(sess: Session, name: &str) -> ~str {
+    if sess.targ_cfg.os == session::os_android {
+        match &sess.opts.android_cross_path {
+            &Some(copy path) => {
+                fmt!("%s/bin/arm-linux-androideabi-%s", path, name)
+            }
+            &None => {
+                sess.fatal(~"need Android NDK path for linking \
+                             (--android-cross-path)")
+            }
+        }
+    } else {
+        str::from_slice(name)
+    }
+}
+
+pub fn run_binutil(sess: Session, prog: &str, args: &[~str]) {
+    debug!("%s args: %s", prog, str::connect(args, ~" "));
+    let out = run::program_output(prog, args);
+    if 0 != out.status {
+        sess.err(fmt!("running `%s` failed with code %d", prog, out.status));
+        sess.note(fmt!("%s arguments: %s", prog, str::connect(args, ~" ")));
+        sess.note(out.err + out.out);
+        sess.abort_if_errors();
+    }
+}
+
+/**
+ * Builds the archive for a --staticlib crate, for linking into a program
+ * that isn't written in Rust.
+ *
+ * The crate's object, the Rust crates it uses and the parts of the runtime
+ * they need are joined into one object with `ld -r`, and archived. The
+ * crates must be available as rlibs (built with -Z static), as a shared
+ * library can't be put in an archive. The native libraries the archive
+ * needs are written next to it, to `lib<name>.libs`, as linker arguments.
+ *
+ * When the crate has a `main` function, the archive exports
+ * `int rust_staticlib_main(int argc, char **argv)`, which starts the
+ * runtime, runs `main` in a task and returns the exit status. Functions
+ * called from C outside of it have no task: they can use the stack and
+ * `~` boxes, vectors and strings, but must not use `@` boxes, fail, log
+ * or spawn tasks.
+ */
+pub fn link_staticlib(sess: Session,
+                      obj_filename: &Path,
+                      out_filename: &Path) {
+    let cstore = sess.cstore;
+    let joined = obj_filename.with_filetype("static.o");
+
+    let mut ld_args = ~[~"-r", ~"-o", joined.to_str(),
+                        obj_filename.to_str()];
+    for cstore::get_used_crate_files(cstore).each |cratepath| {
+        if cratepath.filetype() != Some(~".rlib") {
+            sess.err(fmt!("`%s` is not an rlib; crates linked into a \
+                           static library must be built with -Z static",
+                          cratepath.to_str()));
+            loop;
+        }
+        ld_args.push(cratepath.to_str());
+    }
+    sess.abort_if_errors();
+
+    // Only the members of the runtime's archives that are used are pulled
+    // in
+    ld_args.push(~"-L" + sess.filesearch.get_target_lib_path().to_str());
+    ld_args.push_all(~[~"-lrustrt_static", ~"-lmorestack"]);
+    run_binutil(sess, binutil(sess, "ld"), ld_args);
+
+    os::remove_file(out_filename);
+    run_binutil(sess, binutil(sess, "ar"),
+                ~[~"rcs", out_filename.to_str(), joined.to_str()]);
+
+    let mut libs = cstore::get_used_link_args(cstore);
+    do cstore::iter_crate_data(cstore) |crate_num, _| {
+        let link_args = csearch::get_link_args_for_crate(cstore, crate_num);
+        do vec::consume(link_args) |_, link_arg| {
+            if link_arg.starts_with("-") {
+              libs.push(link_arg);
+            }
+        }
+    }
+    for cstore::get_used_libraries(cstore).each |l| {
+        libs.push(~"-l" + *l);
+    }
+    libs.push_all(os_link_args(sess.targ_cfg.os));
+    // The runtime is C++; its shared library brought these along
+    match sess.targ_cfg.os {
+      session::os_linux => libs.push_all(~[~"-lstdc++", ~"-lpthread"]),
+      session::os_macos | session::os_win32 => libs.push(~"-lstdc++"),
+      session::os_android | session::os_freebsd => ()
+    }
+
+    let libs_filename = out_filename.with_filetype("libs");
+    match io::file_writer(&libs_filename, ~[io::Create, io::Truncate]) {
+      Ok(w) => w.write_line(str::connect(libs, ~" ")),
+      Err(e) => sess.fatal(fmt!("could not write %s: %s",
+                                libs_filename.to_str(), e))
+    }
+
+    if !sess.opts.save_temps {
+        os::remove_file(&joined);
+        if !os::remove_file(obj_filename) {
+            sess.warn(fmt!("failed to delete object file `%s`",
+                           obj_filename.to_str()));
+        }
+    }
+}
+
+// The file link_binary writes: `out_filename` for an executable, and the
+// library named for the crate's link meta next to it for a library
+pub fn binary_filename(sess: Session, out_filename: &Path,
+                       lm: LinkMeta) -> Path {
+    if *sess.building_library {
+        let long_libname = output_dll_filename(sess.targ_cfg.os, lm);
+        debug!("link_meta.name:  %s", lm.name);
+        debug!("long_libname: %s", long_libname);
+        debug!("out_filename: %s", out_filename.to_str());
+        debug!("dirname(out_filename): %s", out_filename.dir_path().to_str());
+
+        out_filename.dir_path().push(long_libname)
+    } else {
+        /*bad*/copy *out_filename
+    }
+}
+
 // If the user wants an exe generated we need to invoke
 // cc to link the object file with some libs
 pub fn link_binary(sess: Session,
@@ -777,17 +1255,7 @@ pub fn link_binary(sess: Session,
         }
     }
 
-    let output = if *sess.building_library {
-        let long_libname = output_dll_filename(sess.targ_cfg.os, lm);
-        debug!("link_meta.name:  %s", lm.name);
-        debug!("long_libname: %s", long_libname);
-        debug!("out_filename: %s", out_filename.to_str());
-        debug!("dirname(out_filename): %s", out_filename.dir_path().to_str());
-
-        out_filename.dir_path().push(long_libname)
-    } else {
-        /*bad*/copy *out_filename
-    };
+    let output = binary_filename(sess, out_filename, lm);
 
     debug!("output: %s", output.to_str());
 
@@ -830,16 +1298,20 @@ pub fn link_binary(sess: Session,
 
     // # Crate linking
 
+    // With --lto, the crates' code is already in the executable, and
+    // linking them as well would give it two copies of their statics
     let cstore = sess.cstore;
-    for cstore::get_used_crate_files(cstore).each |cratepath| {
-        if cratepath.filetype() == Some(~".rlib") {
-            cc_args.push(cratepath.to_str());
-            loop;
+    if !sess.opts.lto || *sess.building_library {
+        for cstore::get_used_crate_files(cstore).each |cratepath| {
+            if cratepath.filetype() == Some(~".rlib") {
+                cc_args.push(cratepath.to_str());
+                loop;
+            }
+            let dir = cratepath.dirname();
+            if dir != ~"" { cc_args.push(~"-L" + dir); }
+            let libarg = unlib(sess.targ_cfg, cratepath.filestem().get());
+            cc_args.push(~"-l" + libarg);
         }
-        let dir = cratepath.dirname();
-        if dir != ~"" { cc_args.push(~"-L" + dir); }
-        let libarg = unlib(sess.targ_cfg, cratepath.filestem().get());
-        cc_args.push(~"-l" + libarg);
     }
 
     let ula = cstore::get_used_link_args(cstore);
@@ -884,31 +1356,7 @@ pub fn link_binary(sess: Session,
         }
     }
 
-    // On linux librt and libdl are an indirect dependencies via rustrt,
-    // and binutils 2.22+ won't add them automatically
-    if sess.targ_cfg.os == session::os_linux {
-        cc_args.push_all(~[~"-lrt", ~"-ldl"]);
-
-        // LLVM implements the `frem` instruction as a call to `fmod`,
-        // which lives in libm. Similar to above, on some linuxes we
-        // have to be explicit about linking to it. See #2510
-        cc_args.push(~"-lm");
-    }
-    else if sess.targ_cfg.os == session::os_android {
-        cc_args.push_all(~[~"-ldl", ~"-llog",  ~"-lsupc++",
-                           ~"-lgnustl_shared"]);
-        cc_args.push(~"-lm");
-    }
-
-    if sess.targ_cfg.os == session::os_freebsd {
-        cc_args.push_all(~[~"-pthread", ~"-lrt",
-                                ~"-L/usr/local/lib", ~"-lexecinfo",
-                                ~"-L/usr/local/lib/gcc46",
-                                ~"-L/usr/local/lib/gcc44", ~"-lstdc++",
-                                ~"-Wl,-z,origin",
-                                ~"-Wl,-rpath,/usr/local/lib/gcc46",
-                                ~"-Wl,-rpath,/usr/local/lib/gcc44"]);
-    }
+    cc_args.push_all(os_link_args(sess.targ_cfg.os));
 
     // OS X 10.6 introduced 'compact unwind info', which is produced by the
     // linker from the dwarf unwind info. Unfortunately, it does not seem to
@@ -0,0 +1,120 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * Link-time optimization.
+ *
+ * A library built with `--lto` carries its LLVM bitcode in a section next
+ * to its metadata (see `trans::base::embed_bitcode`). An executable built
+ * with `--lto` links the bitcode of every crate it uses into its own
+ * module before that is optimized, so code is inlined across crates
+ * whether or not it was marked `#[inline]`, and whatever the program
+ * doesn't reach is dropped.
+ */
+
+use core::prelude::*;
+
+use back::link::llvm_err;
+use back::link::write::{is_external_definition, value_name};
+use driver::session::Session;
+use driver::session;
+use lib::llvm::llvm;
+use lib::llvm::{False, ModuleRef, True, ValueRef, mk_pass_manager};
+use lib::llvm::mk_target_data;
+use lib;
+use metadata::{cstore, loader};
+
+use core::libc::{c_char, c_uint, size_t};
+use core::str;
+use core::vec;
+
+/**
+ * Links the bitcode of every crate `llmod` uses into it, and adds the
+ * whole-program passes. Every crate must have been built with `--lto`.
+ */
+pub fn run(sess: Session, llmod: ModuleRef) {
+    let os = session::sess_os_to_meta_os(sess.targ_cfg.os);
+    for cstore::get_used_crate_files(sess.cstore).each |path| {
+        let bitcode = match loader::get_bitcode_section(os, path) {
+            Some(bitcode) => bitcode,
+            None => {
+                sess.err(fmt!("%s has no bitcode to link in; rebuild it \
+                               with --lto", path.to_str()));
+                loop;
+            }
+        };
+        debug!("linking in the bitcode of %s", path.to_str());
+        unsafe {
+            let llupstream = do vec::as_imm_buf(bitcode) |buf, len| {
+                llvm::LLVMRustParseBitcodeBytes(buf as *c_char,
+                                                len as size_t)
+            };
+            if llupstream.is_null() {
+                llvm_err(sess, fmt!("could not read the bitcode in %s",
+                                    path.to_str()));
+            }
+            internalize_clashes(llmod, llupstream);
+            if !llvm::LLVMRustLinkModules(llmod, llupstream) {
+                llvm_err(sess, fmt!("could not link in the bitcode of %s",
+                                    path.to_str()));
+            }
+            llvm::LLVMDisposeModule(llupstream);
+        }
+    }
+    sess.abort_if_errors();
+
+    unsafe {
+        let pm = mk_pass_manager();
+        let td = mk_target_data(sess.targ_cfg.target_strs.data_layout);
+        llvm::LLVMAddTargetData(td.lltd, pm.llpm);
+        // Nothing outside this module refers to anything in it but `main`
+        llvm::LLVMAddInternalizePass(pm.llpm, True as c_uint);
+        let PMB = llvm::LLVMPassManagerBuilderCreate();
+        llvm::LLVMPassManagerBuilderPopulateLTOPassManager(PMB, pm.llpm,
+                                                           False, True);
+        llvm::LLVMPassManagerBuilderDispose(PMB);
+        llvm::LLVMRunPassManager(pm.llpm, llmod);
+    }
+}
+
+/**
+ * Makes internal the definitions in `llupstream` that `llmod` also
+ * defines, which would clash when the modules are linked.
+ */
+unsafe fn internalize_clashes(llmod: ModuleRef, llupstream: ModuleRef) {
+    fn clashes(llval: ValueRef, llother: ValueRef) -> bool {
+        unsafe {
+            llother.is_not_null() && is_external_definition(llval) &&
+                llvm::LLVMIsDeclaration(llother) == False
+        }
+    }
+
+    let mut llglobal = llvm::LLVMGetFirstGlobal(llupstream);
+    while llglobal.is_not_null() {
+        let llother = str::as_c_str(value_name(llglobal), |buf| {
+            llvm::LLVMGetNamedGlobal(llmod, buf)
+        });
+        if clashes(llglobal, llother) {
+            lib::llvm::SetLinkage(llglobal, lib::llvm::InternalLinkage);
+        }
+        llglobal = llvm::LLVMGetNextGlobal(llglobal);
+    }
+
+    let mut llfn = llvm::LLVMGetFirstFunction(llupstream);
+    while llfn.is_not_null() {
+        let llother = str::as_c_str(value_name(llfn), |buf| {
+            llvm::LLVMGetNamedFunction(llmod, buf)
+        });
+        if clashes(llfn, llother) {
+            lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
+        }
+        llfn = llvm::LLVMGetNextFunction(llfn);
+    }
+}
@@ -12,6 +12,7 @@
 pub struct t {
     module_asm: ~str,
     meta_sect_name: ~str,
+    bc_sect_name: ~str,
     data_layout: ~str,
     target_triple: ~str,
     cc_args: ~[~str]
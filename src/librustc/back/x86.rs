@@ -12,7 +12,7 @@
 use back::target_strs;
 use driver::session::sess_os_to_meta_os;
 use driver::session;
-use metadata::loader::meta_section_name;
+use metadata::loader::{bitcode_section_name, meta_section_name};
 
 pub fn get_target_strs(target_os: session::os) -> target_strs::t {
     return target_strs::t {
@@ -20,6 +20,8 @@ pub fn get_target_strs(target_os: session::os) -> target_strs::t {
 
         meta_sect_name: meta_section_name(sess_os_to_meta_os(target_os)),
 
+        bc_sect_name: bitcode_section_name(sess_os_to_meta_os(target_os)),
+
         data_layout: match target_os {
           session::os_macos => {
             ~"e-p:32:32:32-i1:8:8-i8:8:8-i16:16:16" +
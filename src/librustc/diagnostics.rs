@@ -0,0 +1,1458 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+ * The registry of error codes.
+ *
+ * Errors reported with `span_err_with_code` carry one of the codes below,
+ * which is printed along with the message. `rustc --explain CODE` prints
+ * the long-form description. Codes are never reused: when an error goes
+ * away its entry stays, so old codes keep pointing at something useful.
+ *
+ * Every error reported by resolve, typeck and borrowck (including the
+ * liveness and move checks borrowck depends on) has a code. Errors from
+ * the other passes, and internal compiler errors, are reported without
+ * one. Lints never get a code; a lint diagnostic carries the name of its
+ * lint instead, the name it is allowed or denied by.
+ */
+
+use core::prelude::*;
+
+use core::uint;
+use core::vec;
+
+pub const unresolved_name: &'static str = "E0001";
+pub const unresolved_import: &'static str = "E0002";
+pub const duplicate_definition: &'static str = "E0003";
+pub const mismatched_types: &'static str = "E0004";
+pub const wrong_number_of_args: &'static str = "E0005";
+pub const no_method_found: &'static str = "E0006";
+pub const no_field_found: &'static str = "E0007";
+pub const use_of_moved_value: &'static str = "E0008";
+pub const illegal_borrow: &'static str = "E0009";
+pub const conflicting_loan: &'static str = "E0010";
+pub const outstanding_loan: &'static str = "E0011";
+pub const not_a_module: &'static str = "E0012";
+pub const dynamic_environment_capture: &'static str = "E0013";
+pub const type_param_out_of_scope: &'static str = "E0014";
+pub const non_constant_in_constant: &'static str = "E0015";
+pub const unknown_trait: &'static str = "E0016";
+pub const multiple_main_fns: &'static str = "E0017";
+pub const inconsistent_bindings: &'static str = "E0018";
+pub const shadowed_variant: &'static str = "E0019";
+pub const constant_in_irrefutable_pattern: &'static str = "E0020";
+pub const duplicate_binding: &'static str = "E0021";
+pub const unresolved_enum_variant: &'static str = "E0022";
+pub const not_a_struct: &'static str = "E0023";
+pub const undeclared_module: &'static str = "E0024";
+pub const undeclared_label: &'static str = "E0025";
+pub const illegal_binding_mode: &'static str = "E0026";
+pub const duplicate_field: &'static str = "E0027";
+pub const foreign_item_type_params: &'static str = "E0028";
+pub const unsafe_required: &'static str = "E0029";
+pub const wrong_loop_body_type: &'static str = "E0030";
+pub const not_a_function: &'static str = "E0031";
+pub const unsupported_operator: &'static str = "E0032";
+pub const method_value: &'static str = "E0033";
+pub const missing_fields: &'static str = "E0034";
+pub const non_closure_last_arg: &'static str = "E0035";
+pub const cannot_deref: &'static str = "E0036";
+pub const return_without_value: &'static str = "E0037";
+pub const invalid_cast: &'static str = "E0038";
+pub const cannot_index: &'static str = "E0039";
+pub const recursive_type: &'static str = "E0040";
+pub const invalid_discriminant: &'static str = "E0041";
+pub const duplicate_discriminant: &'static str = "E0042";
+pub const type_annotations_needed: &'static str = "E0043";
+pub const fixed_size_mismatch: &'static str = "E0044";
+pub const unused_type_param: &'static str = "E0045";
+pub const bad_intrinsic: &'static str = "E0046";
+pub const wrong_number_of_pattern_fields: &'static str = "E0047";
+pub const destructure_drop_struct: &'static str = "E0048";
+pub const bad_range_pattern: &'static str = "E0049";
+pub const duplicate_supertrait: &'static str = "E0050";
+pub const impl_method_mismatch: &'static str = "E0051";
+pub const not_a_trait_method: &'static str = "E0052";
+pub const bounds_not_allowed: &'static str = "E0053";
+pub const not_a_trait: &'static str = "E0054";
+pub const illegal_lifetime: &'static str = "E0055";
+pub const unexpected_lifetime_param: &'static str = "E0056";
+pub const wrong_number_of_type_params: &'static str = "E0057";
+pub const trait_object_cast: &'static str = "E0058";
+pub const bare_vec_or_str: &'static str = "E0059";
+pub const not_a_type: &'static str = "E0060";
+pub const non_constant_vec_len: &'static str = "E0061";
+pub const trait_not_implemented: &'static str = "E0062";
+pub const ambiguous_method: &'static str = "E0063";
+pub const invalid_inherent_impl: &'static str = "E0064";
+pub const conflicting_impls: &'static str = "E0065";
+pub const orphan_impl: &'static str = "E0066";
+pub const missing_trait_method: &'static str = "E0067";
+pub const drop_on_non_struct: &'static str = "E0068";
+pub const trait_object_method: &'static str = "E0069";
+pub const explicit_destructor_call: &'static str = "E0070";
+pub const bad_main_fn: &'static str = "E0071";
+pub const main_not_found: &'static str = "E0072";
+pub const assign_to_immutable: &'static str = "E0073";
+pub const impure_in_pure_context: &'static str = "E0074";
+pub const illegal_move: &'static str = "E0075";
+pub const lifetime_inference_failed: &'static str = "E0076";
+pub const reference_outlives_referent: &'static str = "E0077";
+pub const missing_return_value: &'static str = "E0078";
+
+const registry: &'static [(&'static str, &'static str)] = &[
+
+(unresolved_name, "
+A name was used that is not defined in the current scope.
+
+    fn main() {
+        let count = 1;
+        io::println(fmt!(\"%d\", cuont)); // error: unresolved name
+    }
+
+Check the spelling, and that the item is imported with `use` if it is
+defined in another module:
+
+    fn main() {
+        let count = 1;
+        io::println(fmt!(\"%d\", count));
+    }
+"),
+
+(unresolved_import, "
+A `use` declaration names a module or item that does not exist.
+
+    use core::hashmap::LinearMap; // error: unresolved import
+
+The path of an import is relative to the crate root, and must name the
+module the item is defined in:
+
+    use core::hashmap::linear::LinearMap;
+"),
+
+(duplicate_definition, "
+Two items with the same name were defined in the same namespace of a
+module. Types and modules share one namespace, and functions, statics
+and enum variants share another.
+
+    fn parse() { }
+    fn parse() { } // error: duplicate definition of value parse
+
+Rename one of the items, or move it into its own module:
+
+    fn parse() { }
+    fn parse_all() { }
+"),
+
+(mismatched_types, "
+An expression has a different type from the one its context requires.
+
+    fn double(x: int) -> int {
+        x * 2
+    }
+
+    fn main() {
+        double(~\"2\"); // error: mismatched types
+    }
+
+Convert the value to the expected type, or change the expected type:
+
+    fn main() {
+        double(2);
+    }
+"),
+
+(wrong_number_of_args, "
+A function was called with a different number of arguments than it
+declares. The closure passed by `do` or `for` counts as the last
+argument.
+
+    fn add(x: int, y: int) -> int { x + y }
+
+    fn main() {
+        add(1); // error: this function takes 2 parameters
+    }
+
+Pass exactly the parameters the function declares:
+
+    fn main() {
+        add(1, 2);
+    }
+"),
+
+(no_method_found, "
+A method was called that the receiver's type does not have. Methods of
+traits are only in scope when the trait is imported.
+
+    fn main() {
+        let r = io::stdin();
+        r.read_line(); // error: does not implement any method in scope
+    }
+
+Import the trait that provides the method:
+
+    use core::io::ReaderUtil;
+
+    fn main() {
+        let r = io::stdin();
+        r.read_line();
+    }
+"),
+
+(no_field_found, "
+A field was accessed that the type does not have.
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, y: 2 };
+        p.z; // error: attempted access of field `z`
+    }
+
+Use one of the fields the struct declares:
+
+    fn main() {
+        let p = Point { x: 1, y: 2 };
+        p.y;
+    }
+"),
+
+(use_of_moved_value, "
+A variable was used after its value was moved out of it. Assigning or
+passing a value whose type is not implicitly copyable, such as `~str`,
+moves it.
+
+    fn main() {
+        let s = ~\"hello\";
+        let t = s;
+        io::println(s); // error: use of moved value: `s`
+    }
+
+Copy the value explicitly if both variables are needed, or borrow it:
+
+    fn main() {
+        let s = ~\"hello\";
+        let t = copy s;
+        io::println(s);
+    }
+"),
+
+(illegal_borrow, "
+A pointer was taken to a value that may not live as long as the pointer,
+or that may change while the pointer is in use.
+
+    fn first(v: @mut ~[int]) -> &int {
+        &v[0] // error: illegal borrow
+    }
+
+Return a copy of the value instead of a pointer into it:
+
+    fn first(v: @mut ~[int]) -> int {
+        v[0]
+    }
+"),
+
+(conflicting_loan, "
+A value was borrowed while an earlier borrow of it was still in use, and
+the two borrows are not compatible: a mutable borrow cannot coexist with
+any other borrow of the same value.
+
+    fn main() {
+        let mut v = ~[1, 2, 3];
+        let a = &mut v;
+        let b = &v; // error: loan conflicts with prior loan
+    }
+
+End the first borrow, by limiting its scope, before taking the second:
+
+    fn main() {
+        let mut v = ~[1, 2, 3];
+        {
+            let a = &mut v;
+        }
+        let b = &v;
+    }
+"),
+
+(outstanding_loan, "
+A value was assigned to or moved while it was borrowed. The borrow
+guarantees the value does not change while the pointer is in use.
+
+    fn main() {
+        let mut x = 1;
+        let p = &x;
+        x = 2; // error: assigning to mutable local variable prohibited
+               // due to outstanding loan
+        io::println(fmt!(\"%d\", *p));
+    }
+
+Finish using the borrowed pointer before changing the value:
+
+    fn main() {
+        let mut x = 1;
+        {
+            let p = &x;
+            io::println(fmt!(\"%d\", *p));
+        }
+        x = 2;
+    }
+"),
+
+(not_a_module, "
+A path used a name as a module, but the name refers to something else,
+such as a type or a function.
+
+    mod shapes {
+        pub struct Circle { r: float }
+    }
+
+    use shapes::Circle::r; // error: not a module: Circle
+
+Only modules, and enums for their variants, can appear before `::` in an
+import:
+
+    use shapes::Circle;
+"),
+
+(dynamic_environment_capture, "
+A nested `fn` item used a local variable of the function enclosing it.
+Nested items cannot see the locals of their parent; only closures can.
+
+    fn outer() {
+        let x = 1;
+        fn inner() -> int { x } // error: attempted dynamic
+                                // environment-capture
+    }
+
+Use a closure, or pass the value as an argument:
+
+    fn outer() {
+        let x = 1;
+        let inner: &fn() -> int = || x;
+    }
+"),
+
+(type_param_out_of_scope, "
+A nested item used a type parameter of the function enclosing it. Items
+do not inherit the type parameters of their parent.
+
+    fn outer<T>() {
+        fn inner(x: T) { } // error: attempt to use a type argument out
+                           // of scope
+    }
+
+Give the nested item its own type parameter:
+
+    fn outer<T>() {
+        fn inner<U>(x: U) { }
+    }
+"),
+
+(non_constant_in_constant, "
+The value of a constant, such as an enum discriminant, refers to a local
+variable. Constants are evaluated at compile time and can only use other
+constants.
+
+    fn main() {
+        let base = 100;
+        enum Stuff {
+            Bar = base // error: attempt to use a non-constant value in
+                       // a constant
+        }
+    }
+
+Make the value a constant item:
+
+    const base: int = 100;
+
+    enum Stuff {
+        Bar = base
+    }
+"),
+
+(unknown_trait, "
+An `impl` or a trait's supertrait list names a trait that is not in
+scope.
+
+    impl Shape for Square { // error: attempt to implement an unknown
+        ...                 // trait
+    }
+
+Check the spelling, and import the trait with `use` if it is defined in
+another module:
+
+    use shapes::Shape;
+"),
+
+(multiple_main_fns, "
+A crate defines more than one entry point, either as several functions
+named `main` or by marking several functions with `#[main]`.
+
+    fn main() { }
+
+    #[main]
+    fn start() { } // error: multiple 'main' functions
+
+Keep exactly one entry point.
+"),
+
+(inconsistent_bindings, "
+The alternatives of an or-pattern (`a | b`) do not bind the same
+variables in the same way. Every alternative must bind each variable,
+with the same binding mode, so the arm can use it whichever one matched.
+
+    match x {
+        Some(y) | None => y, // error: variable `y` from pattern #1 is
+                             // not bound in pattern #2
+    }
+
+Bind the same variables in every alternative, or split the arm:
+
+    match x {
+        Some(y) => y,
+        None => 0,
+    }
+"),
+
+(shadowed_variant, "
+A `let` or a function argument declares a variable with the name of an
+enum variant or unit-like struct in scope. In an irrefutable pattern the
+name would be taken as the variant, not as a new variable.
+
+    enum Dir { North, South }
+
+    fn main() {
+        let North = 1; // error: declaration of `North` shadows an enum
+                       // variant or unit-like struct in scope
+    }
+
+Choose a variable name that is not also a variant:
+
+    fn main() {
+        let north = 1;
+    }
+"),
+
+(constant_in_irrefutable_pattern, "
+A constant was used in a pattern that must always match, such as the
+pattern of a `let`. A constant only matches one value, so the pattern
+could fail.
+
+    const zero: int = 0;
+
+    fn main() {
+        let zero = 5; // error: only refutable patterns allowed here
+    }
+
+Use a different name for the variable, or compare with `match`:
+
+    fn main() {
+        let n = 5;
+    }
+"),
+
+(duplicate_binding, "
+A pattern binds the same variable name twice.
+
+    match pair {
+        (x, x) => x, // error: Identifier x is bound more than once in
+                     // the same pattern
+    }
+
+Give each binding its own name and compare them in a guard if needed:
+
+    match pair {
+        (x, y) if x == y => x,
+        (x, _) => x,
+    }
+"),
+
+(unresolved_enum_variant, "
+A pattern names an enum variant, struct or constant that is not in
+scope, or names an item that cannot be matched against, such as a
+function.
+
+    match c {
+        Red => 0,
+        Grene(x) => 1, // error: unresolved enum variant
+    }
+
+Check the spelling, and import the variant if its enum is defined in
+another module:
+
+    use colors::{Red, Green};
+"),
+
+(not_a_struct, "
+A struct expression or struct pattern names something that is not a
+structure, or a struct-like enum variant.
+
+    type Point = (int, int);
+
+    fn main() {
+        let p = Point { x: 1, y: 2 }; // error: `Point` does not name a
+                                      // structure
+    }
+
+Use a struct declaration for the type:
+
+    struct Point { x: int, y: int }
+"),
+
+(undeclared_module, "
+A path names a module that does not exist.
+
+    fn main() {
+        let v = vecc::len(~[1, 2]); // error: use of undeclared module
+                                    // `vecc`
+    }
+
+Check the spelling of the module, and that it is imported:
+
+    fn main() {
+        let v = vec::len(~[1, 2]);
+    }
+"),
+
+(undeclared_label, "
+A `break` or `loop` names a loop label that is not in scope.
+
+    fn main() {
+        loop {
+            break outer; // error: use of undeclared label `outer`
+        }
+    }
+
+Label the enclosing loop with the name used:
+
+    fn main() {
+        loop outer: {
+            break outer;
+        }
+    }
+"),
+
+(illegal_binding_mode, "
+A pattern that matches an enum variant or a constant has a `copy` or
+`ref` binding mode. Those names do not bind anything, so a binding mode
+makes no sense.
+
+    match x {
+        ref None => 0, // error: cannot use `ref` binding mode with an
+                       // enum variant
+        Some(_) => 1,
+    }
+
+Remove the binding mode:
+
+    match x {
+        None => 0,
+        Some(_) => 1,
+    }
+"),
+
+(duplicate_field, "
+A struct declaration, struct expression or record type names the same
+field twice.
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, x: 2, y: 3 }; // error: field `x`
+                                            // specified more than once
+    }
+
+Give each field exactly once:
+
+    fn main() {
+        let p = Point { x: 1, y: 3 };
+    }
+"),
+
+(foreign_item_type_params, "
+A function in an `extern` block has type parameters. Foreign functions
+are called with the C ABI and cannot be generic.
+
+    extern {
+        fn free<T>(p: *T); // error: foreign items may not have type
+                           // parameters
+    }
+
+Declare the foreign function with a concrete type, and cast at the call
+site:
+
+    extern {
+        fn free(p: *libc::c_void);
+    }
+"),
+
+(unsafe_required, "
+An unsafe operation, such as dereferencing a raw pointer or calling an
+`unsafe` function, was used outside an `unsafe` function or block.
+
+    fn read(p: *int) -> int {
+        *p // error: dereference of unsafe pointer requires unsafe
+           // function or block
+    }
+
+Wrap the operation in an `unsafe` block once you have checked it is
+sound:
+
+    fn read(p: *int) -> int {
+        unsafe { *p }
+    }
+"),
+
+(wrong_loop_body_type, "
+The body of a `for` loop must be a closure returning `()`, and the
+iterator it is passed to must expect a closure returning `bool`. A `do`
+block, on the other hand, must return what the function expects.
+
+    fn apply(f: &fn(int) -> int) -> int { f(1) }
+
+    fn main() {
+        for apply |x| { x * 2 }; // error: A `for` loop iterator should
+    }                            // expect a closure that returns `bool`
+
+Use `do` to call a function that is not an iterator:
+
+    fn main() {
+        let y = do apply |x| { x * 2 };
+    }
+"),
+
+(not_a_function, "
+A value was called that is not a function or closure.
+
+    fn main() {
+        let x = 5;
+        x(); // error: expected function or foreign function but found
+             // `int`
+    }
+
+Only call values of function type.
+"),
+
+(unsupported_operator, "
+A binary or unary operator was applied to a type that does not support
+it. Operators on user-defined types require an implementation of the
+matching trait from `core::ops`.
+
+    struct Meters(float);
+
+    fn main() {
+        let d = Meters(1.0) + Meters(2.0); // error: binary operation +
+                                           // cannot be applied to type
+    }
+
+Implement the operator's trait for the type:
+
+    impl Add<Meters, Meters> for Meters {
+        fn add(&self, other: &Meters) -> Meters {
+            Meters(**self + **other)
+        }
+    }
+"),
+
+(method_value, "
+A method was used as a value without calling it. Methods cannot be
+taken as first-class values.
+
+    fn main() {
+        let s = ~\"hello\";
+        let f = s.len; // error: attempted to take value of method
+    }
+
+Wrap the call in a closure:
+
+    fn main() {
+        let s = ~\"hello\";
+        let f: &fn() -> uint = || s.len();
+    }
+"),
+
+(missing_fields, "
+A struct expression or a struct pattern does not mention every field of
+the structure.
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1 }; // error: missing field: y
+    }
+
+Give every field a value, or copy the rest from another value with
+`..`:
+
+    fn main() {
+        let p = Point { x: 1, y: 0 };
+    }
+"),
+
+(non_closure_last_arg, "
+The function called with `do` or `for` does not take a closure as its
+last argument.
+
+    fn twice(x: int) -> int { x * 2 }
+
+    fn main() {
+        do twice { 5 }; // error: last argument in `do` call has
+                        // non-closure type
+    }
+
+Call the function normally:
+
+    fn main() {
+        twice(5);
+    }
+"),
+
+(cannot_deref, "
+The unary `*` operator was applied to a value that is not a pointer, an
+enum with a single variant of one field, or a struct with one anonymous
+field.
+
+    fn main() {
+        let x = 5;
+        let y = *x; // error: type int cannot be dereferenced
+    }
+
+Only dereference pointers, and newtypes of one field:
+
+    fn main() {
+        let x = @5;
+        let y = *x;
+    }
+"),
+
+(return_without_value, "
+A bare `return;` was used in a function whose return type is not `()`.
+
+    fn parse(s: &str) -> int {
+        if s.is_empty() {
+            return; // error: `return;` in function returning non-nil
+        }
+        1
+    }
+
+Return a value of the declared type:
+
+    fn parse(s: &str) -> int {
+        if s.is_empty() {
+            return 0;
+        }
+        1
+    }
+"),
+
+(invalid_cast, "
+An `as` cast was used between types that cannot be cast. Only scalar
+types, such as integers, floats and raw pointers, can be cast, and
+nothing can be cast to or from `()`.
+
+    fn main() {
+        let s = ~\"5\";
+        let n = s as int; // error: non-scalar cast
+    }
+
+Convert the value with a function instead:
+
+    fn main() {
+        let n = int::from_str(\"5\").get();
+    }
+"),
+
+(cannot_index, "
+A value was indexed with `[]` whose type cannot be indexed. Vectors,
+strings and types that implement `core::ops::Index` can.
+
+    fn main() {
+        let x = 5;
+        let y = x[0]; // error: cannot index a value of type `int`
+    }
+
+Index a vector or string instead.
+"),
+
+(recursive_type, "
+A type contains itself without any indirection, so it would have
+infinite size.
+
+    enum List {
+        Cons(int, List), // error: illegal recursive enum type
+        Nil
+    }
+
+Put the recursive part behind a pointer:
+
+    enum List {
+        Cons(int, @List),
+        Nil
+    }
+"),
+
+(invalid_discriminant, "
+The discriminant of an enum variant is not a constant signed integer.
+
+    enum Level {
+        Low = 1.5, // error: expected signed integer constant
+        High = 2
+    }
+
+Use an integer literal or a constant integer expression:
+
+    enum Level {
+        Low = 1,
+        High = 2
+    }
+"),
+
+(duplicate_discriminant, "
+Two variants of an enum have the same discriminant value. A variant
+without an explicit value takes the value of the previous variant plus
+one.
+
+    enum Level {
+        Low = 1,
+        Mid,      // Mid is 2
+        High = 2  // error: discriminator value already exists
+    }
+
+Give each variant a distinct value:
+
+    enum Level {
+        Low = 1,
+        Mid,
+        High = 3
+    }
+"),
+
+(type_annotations_needed, "
+The type of a value could not be inferred from how it is used.
+
+    fn main() {
+        let v = ~[]; // error: cannot determine a type for this local
+                     // variable
+    }
+
+Annotate the type:
+
+    fn main() {
+        let v: ~[int] = ~[];
+    }
+"),
+
+(fixed_size_mismatch, "
+A fixed-size vector literal has a different number of elements from the
+length written after it.
+
+    fn main() {
+        let v = [1, 2]/3; // error: fixed-size sequence mismatch: 3 vs. 2
+    }
+
+Give the literal exactly as many elements as its length, or leave the
+length out:
+
+    fn main() {
+        let v = [1, 2, 3]/3;
+    }
+"),
+
+(unused_type_param, "
+A type declaration has a type parameter that its definition does not
+use.
+
+    type Id<T> = int; // error: type parameter `T` is unused
+
+Remove the parameter:
+
+    type Id = int;
+"),
+
+(bad_intrinsic, "
+A function in an `extern \"rust-intrinsic\"` block is not a known
+intrinsic, or its signature does not match the intrinsic's. Intrinsics
+are implemented by the compiler; only the standard library should
+declare them.
+
+    #[abi = \"rust-intrinsic\"]
+    extern {
+        fn size_off<T>() -> uint; // error: unrecognized intrinsic
+                                  // function: `size_off`
+    }
+
+Use the wrappers in `core::sys` instead:
+
+    fn main() {
+        let n = sys::size_of::<int>();
+    }
+"),
+
+(wrong_number_of_pattern_fields, "
+A pattern for an enum variant or tuple struct has a different number of
+fields from the variant's declaration.
+
+    enum Shape { Circle(float), Rect(float, float) }
+
+    fn area(s: Shape) -> float {
+        match s {
+            Circle(r) => 3.14 * r * r,
+            Rect(w) => w * w, // error: this pattern has 1 field, but
+                              // the corresponding variant has 2 fields
+        }
+    }
+
+Match every field, using `_` for the ones that are not needed:
+
+    fn area(s: Shape) -> float {
+        match s {
+            Circle(r) => 3.14 * r * r,
+            Rect(w, h) => w * h,
+        }
+    }
+"),
+
+(destructure_drop_struct, "
+A pattern takes apart a struct that implements `Drop`. Moving the fields
+out would leave the destructor without a value to run on.
+
+    struct File { fd: int }
+
+    impl Drop for File {
+        fn finalize(&self) { close(self.fd); }
+    }
+
+    fn main() {
+        let File { fd: fd } = open(); // error: deconstructing struct
+                                      // not allowed in pattern
+    }
+
+Access the fields through the value instead:
+
+    fn main() {
+        let f = open();
+        let fd = f.fd;
+    }
+"),
+
+(bad_range_pattern, "
+A range pattern (`a .. b`) was used with a non-numeric type, or its
+lower bound is greater than its upper bound.
+
+    match n {
+        10 .. 1 => 0, // error: lower range bound must be less than
+                      // upper
+        _ => 1,
+    }
+
+Put the smaller bound first:
+
+    match n {
+        1 .. 10 => 0,
+        _ => 1,
+    }
+"),
+
+(duplicate_supertrait, "
+A trait lists the same supertrait more than once.
+
+    trait Shape: Eq + Eq { } // error: Duplicate supertrait in trait
+                             // declaration
+
+List each supertrait once:
+
+    trait Shape: Eq { }
+"),
+
+(impl_method_mismatch, "
+A method in a trait implementation does not match the declaration of the
+method in the trait: its self type, staticness, number of parameters,
+type parameters or bounds, or parameter and return types differ.
+
+    trait Shape {
+        fn area(&self) -> float;
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> int { ... } // error: method `area` has an
+                                      // incompatible type
+    }
+
+Copy the signature from the trait:
+
+    impl Shape for Square {
+        fn area(&self) -> float { ... }
+    }
+"),
+
+(not_a_trait_method, "
+A trait implementation defines a method that the trait does not declare.
+
+    trait Shape {
+        fn area(&self) -> float;
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> float { ... }
+        fn side(&self) -> float { ... } // error: method `side` is not a
+                                        // member of trait `Shape`
+    }
+
+Move the extra method into an inherent `impl` of the type:
+
+    impl Square {
+        fn side(&self) -> float { ... }
+    }
+"),
+
+(bounds_not_allowed, "
+Trait bounds were given on the type parameters of a type alias, struct
+or enum. Bounds are only allowed on functions, methods and impls.
+
+    struct Sorted<T: Ord> { // error: trait bounds are not allowed in
+        items: ~[T]         // structure definitions
+    }
+
+Put the bounds on the functions that need them:
+
+    struct Sorted<T> {
+        items: ~[T]
+    }
+
+    fn insert<T: Ord>(s: &mut Sorted<T>, x: T) { ... }
+"),
+
+(not_a_trait, "
+An `impl ... for`, or a type parameter bound, names a type that is not a
+trait.
+
+    enum chan { }
+
+    impl chan for int { } // error: can only implement trait types
+
+Implement a trait, or use an inherent `impl` for the type's own
+methods:
+
+    impl int { ... }
+"),
+
+(illegal_lifetime, "
+A lifetime was used where it is not allowed. Type declarations may only
+use the `'self` lifetime, and must name it explicitly.
+
+    struct Parser<'self> {
+        input: &'a str // error: Illegal lifetime 'a: only 'self is
+                       // allowed as part of a type declaration
+    }
+
+Use `'self`:
+
+    struct Parser<'self> {
+        input: &'self str
+    }
+"),
+
+(unexpected_lifetime_param, "
+A lifetime was given to a type or item that does not take one.
+
+    struct Point { x: int, y: int }
+
+    fn origin(p: Point<'static>) { } // error: no region bound is
+                                     // allowed on `Point`
+
+Remove the lifetime:
+
+    fn origin(p: Point) { }
+"),
+
+(wrong_number_of_type_params, "
+A type, function or method was given a different number of type
+parameters than it declares.
+
+    struct Pair<A, B> { a: A, b: B }
+
+    fn first(p: Pair<int>) -> int { p.a } // error: wrong number of
+                                          // type arguments
+
+Give exactly the type parameters the item declares:
+
+    fn first(p: Pair<int, int>) -> int { p.a }
+"),
+
+(trait_object_cast, "
+A value was cast to a trait object with the wrong kind of pointer. The
+sigil of the trait object must match the sigil of the value cast to it:
+`@T` becomes `@Trait`, `~T` becomes `~Trait` and `&T` becomes `&Trait`.
+
+    fn main() {
+        let s = ~5 as @ToStr; // error: can only cast an @-pointer to an
+                              // @-object
+    }
+
+Use the same sigil on both sides:
+
+    fn main() {
+        let s = @5 as @ToStr;
+    }
+"),
+
+(bare_vec_or_str, "
+A vector or string type was written without a pointer sigil. Vectors and
+strings have dynamic size and must live behind `~`, `@` or `&`.
+
+    fn sum(v: [int]) -> int { ... } // error: bare `[]` is not a type
+
+Say how the vector is stored:
+
+    fn sum(v: &[int]) -> int { ... }
+"),
+
+(not_a_type, "
+A path used as a type names something that is not a type, such as a
+function or a local variable.
+
+    fn origin() -> int { 0 }
+
+    fn main() {
+        let x: origin = 0; // error: found type name used as a variable
+    }
+
+Use the name of a type:
+
+    fn main() {
+        let x: int = origin();
+    }
+"),
+
+(non_constant_vec_len, "
+The length of a fixed-size vector type is not a constant expression.
+
+    fn zeros(n: uint) {
+        let v: [int * n]; // error: expected constant expr for vector
+                          // length
+    }
+
+Use a constant for the length, or a vector whose size is not fixed:
+
+    fn zeros(n: uint) {
+        let v = vec::from_elem(n, 0);
+    }
+"),
+
+(trait_not_implemented, "
+A type was used where a trait bound requires an implementation of the
+trait, but the type does not implement it.
+
+    struct Point { x: int, y: int }
+
+    fn show<T: ToStr>(x: T) -> ~str { x.to_str() }
+
+    fn main() {
+        show(Point { x: 1, y: 2 }); // error: failed to find an
+                                    // implementation of trait ToStr
+    }
+
+Implement the trait for the type:
+
+    impl ToStr for Point {
+        fn to_str(&self) -> ~str { fmt!(\"(%d, %d)\", self.x, self.y) }
+    }
+"),
+
+(ambiguous_method, "
+More than one method in scope matches a call, typically because two
+traits in scope define a method of the same name for the type.
+
+    trait A { fn name(&self) -> ~str; }
+    trait B { fn name(&self) -> ~str; }
+
+    fn main() {
+        5.name(); // error: multiple applicable methods in scope
+    }
+
+Only import one of the traits in the scope of the call, or call the
+method through a function that is bounded by one of them.
+"),
+
+(invalid_inherent_impl, "
+An `impl` without a trait was given for a type that cannot have methods
+of its own there: either the type has no nominal base type, such as a
+vector or a tuple, or the type was defined in another crate.
+
+    impl ~[int] { // error: no base type found for inherent
+        ...       // implementation
+    }
+
+Define a trait and implement it, or wrap the type in a new type:
+
+    struct Numbers(~[int]);
+
+    impl Numbers { ... }
+"),
+
+(conflicting_impls, "
+Two implementations of the same trait apply to the same type.
+
+    trait Show { fn show(&self) -> ~str; }
+
+    impl Show for int { ... }
+    impl Show for int { ... } // error: conflicting implementations
+                              // for a trait
+
+Remove one of the implementations.
+"),
+
+(orphan_impl, "
+A trait defined in another crate was implemented for a type that is
+also defined in another crate. One of the two must be local.
+
+    impl ToStr for std::arena::Arena { // error: cannot provide an
+        ...                            // extension implementation for
+    }                                  // a trait not defined in this
+                                       // crate
+
+Wrap the type in a local type, or define a local trait:
+
+    struct Pool(std::arena::Arena);
+
+    impl ToStr for Pool { ... }
+"),
+
+(missing_trait_method, "
+A trait implementation does not define a method that the trait declares
+without a default body.
+
+    trait Shape {
+        fn area(&self) -> float;
+        fn perimeter(&self) -> float;
+    }
+
+    impl Shape for Square { // error: missing method `perimeter`
+        fn area(&self) -> float { ... }
+    }
+
+Define every required method of the trait.
+"),
+
+(drop_on_non_struct, "
+`Drop` was implemented for a type that is not a structure. Only structs
+can have destructors.
+
+    enum Handle { File(int), Socket(int) }
+
+    impl Drop for Handle { // error: the Drop trait may only be
+        ...                // implemented on structures
+    }
+
+Wrap the value in a struct and implement `Drop` for that:
+
+    struct Owned { handle: Handle }
+
+    impl Drop for Owned { ... }
+"),
+
+(trait_object_method, "
+A method was called through a trait object whose type cannot be
+expressed once the concrete type is erased: a method with type
+parameters, or one that mentions `Self` in its arguments or return type.
+
+    trait Shape {
+        fn same(&self, other: Self) -> bool;
+    }
+
+    fn check(s: @Shape, t: @Shape) -> bool {
+        s.same(t) // error: cannot call a method whose type contains a
+                  // self-type through a boxed trait
+    }
+
+Call the method on a generic type instead of a trait object:
+
+    fn check<S: Shape>(s: S, t: S) -> bool {
+        s.same(t)
+    }
+"),
+
+(explicit_destructor_call, "
+The `finalize` method of a `Drop` implementation was called directly.
+Destructors run automatically when the value goes out of scope.
+
+    fn main() {
+        let f = open();
+        f.finalize(); // error: explicit call to destructor
+    }
+
+Let the value go out of scope, for example by ending the block it was
+declared in.
+"),
+
+(bad_main_fn, "
+The `main` function has the wrong type. It must take no type parameters
+and no arguments, and return `()`.
+
+    fn main(args: ~[~str]) -> int { // error: Wrong type in main function
+        0
+    }
+
+Get the arguments from `os::args` and report a status with
+`os::set_exit_status`:
+
+    fn main() {
+        let args = os::args();
+        os::set_exit_status(0);
+    }
+"),
+
+(main_not_found, "
+An executable crate has no `main` function.
+
+    fn run() { } // error: main function not found
+
+Add a `main` function, or build the crate as a library with
+`--lib` or `#[crate_type = \"lib\"];`.
+"),
+
+(assign_to_immutable, "
+A value was assigned to that is not mutable: an immutable local that was
+already initialized, a static item, or a field reached through an
+immutable pointer.
+
+    fn main() {
+        let x = 1;
+        x = 2; // error: re-assignment of immutable variable
+    }
+
+Declare the variable `mut`:
+
+    fn main() {
+        let mut x = 1;
+        x = 2;
+    }
+"),
+
+(impure_in_pure_context, "
+A `pure` function does something impure: it calls an impure function,
+assigns to data it does not own, or borrows in a way that is only safe
+if the code is pure.
+
+    fn log_it(x: int) { io::println(x.to_str()); }
+
+    pure fn double(x: int) -> int {
+        log_it(x); // error: access to impure function prohibited in
+                   // pure context
+        x * 2
+    }
+
+Remove the impure operation, or drop `pure` from the function.
+"),
+
+(illegal_move, "
+A value was moved out of a place that cannot be left without a value,
+such as the contents of a borrowed pointer or a managed box, or a
+by-reference argument, or it was moved while borrowed.
+
+    fn take(v: &~[int]) -> ~[int] {
+        *v // error: moving out of dereference of immutable & pointer
+    }
+
+Copy the value instead, or take it by value:
+
+    fn take(v: &~[int]) -> ~[int] {
+        copy *v
+    }
+"),
+
+(lifetime_inference_failed, "
+The compiler could not find a lifetime for a borrowed pointer that
+satisfies every requirement on it, usually because the pointer must
+outlive the value it points into.
+
+    pub impl Counter {
+        fn get(&mut self) {
+            let p: &'static mut uint = &mut self.count; // error: cannot
+                     // infer an appropriate lifetime due to conflicting
+                     // requirements
+        }
+    }
+
+Give the pointer a lifetime no longer than the value it borrows:
+
+    pub impl Counter {
+        fn get(&mut self) {
+            let p: &mut uint = &mut self.count;
+        }
+    }
+"),
+
+(reference_outlives_referent, "
+A borrowed pointer, or a closure capturing a variable by reference, may
+be used after the value it refers to is gone.
+
+    fn main() {
+        let f: &fn() -> int;
+        {
+            let x = 5;
+            f = || x; // error: captured variable does not outlive the
+                      // enclosing closure
+        }
+    }
+
+Declare the captured variable at least as early as the closure:
+
+    fn main() {
+        let x = 5;
+        let f: &fn() -> int = || x;
+    }
+"),
+
+(missing_return_value, "
+A function with a return type has a path through its body that ends
+without returning a value. A function returning `!` must never return.
+
+    fn sign(x: int) -> int {
+        if x < 0 { return -1; }
+        if x > 0 { return 1; }
+    } // error: not all control paths return a value
+
+End every path with a value:
+
+    fn sign(x: int) -> int {
+        if x < 0 { -1 } else if x > 0 { 1 } else { 0 }
+    }
+"),
+
+];
+
+/// The long-form description of the error code `code`, if it is known
+pub fn explain(code: &str) -> Option<&'static str> {
+    for registry.each |&(c, desc)| {
+        if c == code {
+            return Some(desc);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_registry() {
+    // Every code is described once, and codes are handed out in order,
+    // so no code up to the last one is missing an entry
+    for registry.eachi |i, &(code, desc)| {
+        fail_unless!(code.len() == 5 && code.starts_with("E"));
+        fail_unless!(uint::from_str(code.slice(1, 5)) == Some(i + 1));
+        fail_unless!(!desc.is_empty());
+        for vec::slice(registry, 0, i).each |&(other, _)| {
+            fail_unless!(other != code);
+        }
+    }
+
+    fail_unless!(explain(mismatched_types).is_some());
+    fail_unless!(explain(missing_return_value).is_some());
+    fail_unless!(explain("E9999").is_none());
+}
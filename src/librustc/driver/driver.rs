@@ -23,15 +23,20 @@ use middle::{trans, freevars, kind, ty, typeck, lint, astencode};
 use middle;
 use util::ppaux;
 
+use core::float;
+use core::hashmap::linear::LinearMap;
 use core::int;
 use core::io::WriterUtil;
 use core::io;
+use core::managed;
 use core::os;
 use core::str;
+use core::uint;
 use core::vec;
 use std::getopts::groups::{optopt, optmulti, optflag, optflagopt, getopts};
 use std::getopts::{opt_present};
 use std::getopts;
+use std::json;
 use std;
 use syntax::ast;
 use syntax::attr;
@@ -39,6 +44,7 @@ use syntax::codemap;
 use syntax::diagnostic;
 use syntax::parse;
 use syntax::print::{pp, pprust};
+use syntax::visit;
 use syntax;
 
 pub enum pp_mode {
@@ -46,7 +52,9 @@ pub enum pp_mode {
     ppm_expanded,
     ppm_typed,
     ppm_identified,
-    ppm_expanded_identified
+    ppm_expanded_identified,
+    ppm_expanded_hygiene,
+    ppm_flowgraph(~str)
 }
 
 /**
@@ -161,16 +169,158 @@ pub fn parse_input(sess: Session, +cfg: ast::crate_cfg, input: input)
     }
 }
 
-pub fn time<T>(do_it: bool, what: ~str, thunk: &fn() -> T) -> T {
-    if !do_it { return thunk(); }
+/**
+ * Run a pass of the compiler. With -Z time-passes, its time, the peak
+ * memory use at the end of it, the number of node ids allocated so far
+ * and anything it counted with `count_in_pass` are added to the report.
+ */
+pub fn time<T>(sess: Session, what: ~str, thunk: &fn() -> T) -> T {
+    if !sess.time_passes() { return thunk(); }
     let start = std::time::precise_time_s();
     let rv = thunk();
     let end = std::time::precise_time_s();
-    io::stdout().write_str(fmt!("time: %3.3f s\t%s\n",
-                                end - start, what));
+
+    let mut counts = ~[(~"node ids", sess.parse_sess.next_id as uint - 1)];
+    counts.push_all(*sess.pass_counts);
+    *sess.pass_counts = ~[];
+    sess.pass_stats.push(session::PassStats {
+        name: what,
+        time: end - start,
+        peak_rss: peak_rss(),
+        counts: counts
+    });
     rv
 }
 
+// The peak resident set size of the process in kilobytes, where the
+// platform reports it
+fn peak_rss() -> Option<uint> {
+    match io::read_whole_file_str(&Path("/proc/self/status")) {
+        Ok(status) => {
+            for str::lines_each(status) |line| {
+                if str::starts_with(line, "VmHWM:") {
+                    let words = str::words(str::slice(line, 6, line.len()));
+                    if !words.is_empty() {
+                        return uint::from_str(words[0]);
+                    }
+                }
+            }
+            None
+        }
+        Err(_) => None
+    }
+}
+
+// Count the items of a crate rewritten by a pass, for -Z time-passes
+fn count_items(sess: Session, crate: @ast::crate) {
+    if sess.time_passes() {
+        let n = @mut 0u;
+        let v = visit::mk_simple_visitor(@visit::SimpleVisitor {
+            visit_item: |_i| *n += 1,
+            .. *visit::default_simple_visitor()
+        });
+        visit::visit_crate(*crate, (), v);
+        sess.count_in_last_pass(~"items", *n);
+    }
+}
+
+// The names of the counts in a -Z time-passes report, in the order they
+// first appear
+fn pass_count_names(stats: &[session::PassStats]) -> ~[~str] {
+    let mut names = ~[];
+    for stats.each |pass| {
+        for pass.counts.each |&(ref name, _)| {
+            if !names.contains(name) {
+                names.push(copy *name);
+            }
+        }
+    }
+    names
+}
+
+/// Format a -Z time-passes report as a table, one pass to a line
+pub fn pass_report_table(stats: &[session::PassStats]) -> ~str {
+    fn pad_left(s: &str, width: uint) -> ~str {
+        str::from_bytes(vec::from_elem(width - uint::min(width, s.len()),
+                                       ' ' as u8)) + s
+    }
+    fn pad_right(s: &str, width: uint) -> ~str {
+        s + str::from_bytes(vec::from_elem(width - uint::min(width, s.len()),
+                                           ' ' as u8))
+    }
+    fn cell(n: Option<uint>, width: uint) -> ~str {
+        match n {
+            Some(n) => pad_left(n.to_str(), width),
+            None => pad_left("-", width)
+        }
+    }
+
+    let names = pass_count_names(stats);
+    let mut name_width = 5; // "total"
+    for stats.each |pass| {
+        name_width = uint::max(name_width, pass.name.len());
+    }
+
+    let mut s = pad_right("pass", name_width) + "  time (s)  peak RSS (kB)";
+    for names.each |name| {
+        s += ~"  " + pad_left(*name, uint::max(name.len(), 8));
+    }
+    s += "\n";
+
+    let mut total = 0.0;
+    for stats.each |pass| {
+        total += pass.time;
+        s += pad_right(pass.name, name_width) + "  " +
+            pad_left(float::to_str_digits(pass.time, 3), 8) + "  " +
+            cell(pass.peak_rss, 13);
+        for names.each |name| {
+            let mut n = None;
+            for pass.counts.each |&(ref c, v)| {
+                if c == name { n = Some(v); }
+            }
+            s += ~"  " + cell(n, uint::max(name.len(), 8));
+        }
+        s += "\n";
+    }
+    s += pad_right("total", name_width) + "  " +
+        pad_left(float::to_str_digits(total, 3), 8) + "\n";
+    s
+}
+
+/// Format a -Z time-passes report as a JSON object with a list of passes
+pub fn pass_report_json(stats: &[session::PassStats]) -> json::Json {
+    let mut total = 0.0;
+    let passes = do stats.map |pass| {
+        total += pass.time;
+        let mut counts = ~LinearMap::new();
+        for pass.counts.each |&(ref name, n)| {
+            counts.insert(copy *name, json::Number(n as float));
+        }
+        let mut obj = ~LinearMap::new();
+        obj.insert(~"name", json::String(copy pass.name));
+        obj.insert(~"time", json::Number(pass.time));
+        obj.insert(~"peak_rss", match pass.peak_rss {
+            Some(kb) => json::Number(kb as float),
+            None => json::Null
+        });
+        obj.insert(~"counts", json::Object(counts));
+        json::Object(obj)
+    };
+    let mut obj = ~LinearMap::new();
+    obj.insert(~"passes", json::List(passes));
+    obj.insert(~"total_time", json::Number(total));
+    json::Object(obj)
+}
+
+// Print the -Z time-passes report for the passes run so far
+pub fn print_pass_report(sess: Session) {
+    if sess.time_passes_json() {
+        io::println(json::to_str(&pass_report_json(*sess.pass_stats)));
+    } else {
+        io::print(pass_report_table(*sess.pass_stats));
+    }
+}
+
 #[deriving_eq]
 pub enum compile_upto {
     cu_parse,
@@ -181,49 +331,57 @@ pub enum compile_upto {
 }
 
 // For continuing compilation after a parsed crate has been
-// modified
+// modified. The type context is returned if compilation stops at
+// `cu_typeck` or `cu_no_trans`, and the move maps if it stops at
+// `cu_no_trans`.
 pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
                     upto: compile_upto, outputs: Option<@OutputFilenames>,
                     curr: Option<@ast::crate>)
-    -> (@ast::crate, Option<ty::ctxt>) {
-    let time_passes = sess.time_passes();
+    -> (@ast::crate, Option<ty::ctxt>, Option<middle::moves::MoveMaps>) {
     let mut crate = curr.get();
 
     *sess.building_library = session::building_library(
         sess.opts.crate_type, crate, sess.opts.test);
 
-    crate = time(time_passes, ~"configuration", ||
+    crate = time(sess, ~"configuration", ||
         front::config::strip_unconfigured_items(crate));
+    count_items(sess, crate);
 
-    crate = time(time_passes, ~"maybe building test harness", ||
+    crate = time(sess, ~"maybe building test harness", ||
         front::test::modify_for_testing(sess, crate));
+    count_items(sess, crate);
 
-    crate = time(time_passes, ~"expansion", ||
+    crate = time(sess, ~"expansion", ||
         syntax::ext::expand::expand_crate(sess.parse_sess, copy cfg,
                                           crate));
+    count_items(sess, crate);
 
-    if upto == cu_expand { return (crate, None); }
+    if upto == cu_expand { return (crate, None, None); }
 
-    crate = time(time_passes, ~"intrinsic injection", ||
+    crate = time(sess, ~"intrinsic injection", ||
         front::intrinsic_inject::inject_intrinsic(sess, crate));
+    count_items(sess, crate);
 
-    crate = time(time_passes, ~"core injection", ||
+    crate = time(sess, ~"core injection", ||
         front::core_inject::maybe_inject_libcore_ref(sess, crate));
+    count_items(sess, crate);
 
-    time(time_passes, ~"building lint settings table", ||
+    time(sess, ~"building lint settings table", ||
         lint::build_settings_crate(sess, crate));
 
-    let ast_map = time(time_passes, ~"ast indexing", ||
+    let ast_map = time(sess, ~"ast indexing", ||
             syntax::ast_map::map_crate(sess.diagnostic(), *crate));
 
-    time(time_passes, ~"external crate/lib resolution", ||
+    time(sess, ~"external crate/lib resolution", ||
         creader::read_crates(sess.diagnostic(), *crate, sess.cstore,
                              sess.filesearch,
                              session::sess_os_to_meta_os(sess.targ_cfg.os),
-                             sess.opts.is_static,
+                             sess.opts.is_static ||
+                                 sess.opts.crate_type ==
+                                     session::staticlib_crate,
                              sess.parse_sess.interner));
 
-    let lang_items = time(time_passes, ~"language item collection", ||
+    let lang_items = time(sess, ~"language item collection", ||
          middle::lang_items::collect_language_items(crate, sess));
 
     let middle::resolve::CrateMap {
@@ -231,16 +389,16 @@ pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
         exp_map2: exp_map2,
         trait_map: trait_map
     } =
-        time(time_passes, ~"resolution", ||
+        time(sess, ~"resolution", ||
              middle::resolve::resolve_crate(sess, lang_items, crate));
 
-    let freevars = time(time_passes, ~"freevar finding", ||
+    let freevars = time(sess, ~"freevar finding", ||
         freevars::annotate_freevars(def_map, crate));
 
-    let region_map = time(time_passes, ~"region resolution", ||
+    let region_map = time(sess, ~"region resolution", ||
         middle::region::resolve_crate(sess, def_map, crate));
 
-    let rp_set = time(time_passes, ~"region parameterization inference", ||
+    let rp_set = time(sess, ~"region parameterization inference", ||
         middle::region::determine_rp_in_crate(sess, ast_map, def_map, crate));
 
 
@@ -252,55 +410,58 @@ pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
                                 region_map, rp_set, lang_items, crate);
 
         let (method_map, vtable_map) =
-            time(time_passes, ~"typechecking", ||
+            time(sess, ~"typechecking", ||
                  typeck::check_crate(ty_cx,
                                      trait_map,
                                      crate));
 
         // These next two const passes can probably be merged
-        time(time_passes, ~"const marking", ||
+        time(sess, ~"const marking", ||
              middle::const_eval::process_crate(crate, def_map, ty_cx));
 
-        time(time_passes, ~"const checking", ||
+        time(sess, ~"const checking", ||
              middle::check_const::check_crate(sess, crate, ast_map, def_map,
                                               method_map, ty_cx));
 
-        if upto == cu_typeck { return (crate, Some(ty_cx)); }
+        if upto == cu_typeck { return (crate, Some(ty_cx), None); }
 
-        time(time_passes, ~"privacy checking", ||
+        time(sess, ~"privacy checking", ||
              middle::privacy::check_crate(ty_cx, &method_map, crate));
 
-        time(time_passes, ~"loop checking", ||
+        time(sess, ~"loop checking", ||
              middle::check_loop::check_crate(ty_cx, crate));
 
-        let middle::moves::MoveMaps {moves_map, variable_moves_map,
-                                     capture_map} =
-            time(time_passes, ~"compute moves", ||
+        let move_maps =
+            time(sess, ~"compute moves", ||
                  middle::moves::compute_moves(ty_cx, method_map, crate));
+        let middle::moves::MoveMaps {moves_map, variable_moves_map,
+                                     capture_map} = move_maps;
 
-        time(time_passes, ~"match checking", ||
+        time(sess, ~"match checking", ||
              middle::check_match::check_crate(ty_cx, method_map,
                                               moves_map, crate));
 
         let last_use_map =
-            time(time_passes, ~"liveness checking", ||
+            time(sess, ~"liveness checking", ||
                  middle::liveness::check_crate(ty_cx, method_map,
                                                variable_moves_map,
                                                capture_map, crate));
 
         let (root_map, mutbl_map, write_guard_map) =
-            time(time_passes, ~"borrow checking", ||
+            time(sess, ~"borrow checking", ||
                  middle::borrowck::check_crate(ty_cx, method_map,
                                                moves_map, capture_map,
                                                crate));
 
-        time(time_passes, ~"kind checking", ||
+        time(sess, ~"kind checking", ||
              kind::check_crate(ty_cx, method_map, last_use_map, crate));
 
-        time(time_passes, ~"lint checking", ||
-             lint::check_crate(ty_cx, crate));
+        time(sess, ~"lint checking", ||
+             lint::check_crate(ty_cx, method_map, crate));
 
-        if upto == cu_no_trans { return (crate, Some(ty_cx)); }
+        if upto == cu_no_trans {
+            return (crate, Some(ty_cx), Some(move_maps));
+        }
 
         let maps = astencode::Maps {
             mutbl_map: mutbl_map,
@@ -313,7 +474,7 @@ pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
             capture_map: capture_map
         };
 
-        time(time_passes, ~"translation", ||
+        time(sess, ~"translation", ||
              trans::base::trans_crate(sess, crate, ty_cx,
                                       &outputs.obj_filename,
                                       exp_map2, maps))
@@ -327,13 +488,13 @@ pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
         let output_type = link::output_type_assembly;
         let obj_filename = outputs.obj_filename.with_filetype("s");
 
-        time(time_passes, ~"LLVM passes", ||
+        time(sess, ~"LLVM passes", ||
             link::write::run_passes(sess, llmod, output_type,
                             &obj_filename));
 
         link::write::run_ndk(sess, &obj_filename, &outputs.obj_filename);
     } else {
-        time(time_passes, ~"LLVM passes", ||
+        time(sess, ~"LLVM passes", ||
             link::write::run_passes(sess, llmod, sess.opts.output_type,
                                 &outputs.obj_filename));
     }
@@ -343,24 +504,95 @@ pub fn compile_rest(sess: Session, cfg: ast::crate_cfg,
         (sess.opts.is_static && *sess.building_library)   ||
         sess.opts.jit;
 
-    if stop_after_codegen { return (crate, None); }
+    // A library is named for its link meta, which is only known now
+    let target = if stop_after_codegen ||
+                    sess.opts.crate_type == session::staticlib_crate {
+        copy outputs.out_filename
+    } else {
+        link::binary_filename(sess, &outputs.out_filename, link_meta)
+    };
+
+    if !stop_after_codegen {
+        if sess.opts.crate_type == session::staticlib_crate {
+            time(sess, ~"linking", ||
+                 link::link_staticlib(sess,
+                                      &outputs.obj_filename,
+                                      &outputs.out_filename));
+        } else {
+            time(sess, ~"linking", ||
+                 link::link_binary(sess,
+                                   &outputs.obj_filename,
+                                   &outputs.out_filename, link_meta));
+        }
+    }
+
+    if sess.opts.dep_info {
+        time(sess, ~"writing dep-info", ||
+             write_dep_info(sess, outputs, &target));
+    }
+
+    return (crate, None, None);
+}
 
-    time(time_passes, ~"linking", ||
-         link::link_binary(sess,
-                           &outputs.obj_filename,
-                           &outputs.out_filename, link_meta));
+// Format a Makefile rule making `target` depend on each of `deps`, plus an
+// empty rule for every dependency so that make carries on when one of them
+// is deleted
+pub fn dep_info_str(target: &str, deps: &[~str]) -> ~str {
+    fn escape(s: &str) -> ~str {
+        str::replace(str::replace(s, "$", "$$"), " ", "\\ ")
+    }
 
-    return (crate, None);
+    let mut s = escape(target) + ":";
+    for deps.each |dep| {
+        s += " \\\n    " + escape(*dep);
+    }
+    s += "\n";
+    for deps.each |dep| {
+        s += "\n" + escape(*dep) + ":\n";
+    }
+    s
+}
+
+/**
+ * Write a `.d` file next to the output, listing every source file loaded
+ * into the codemap (through `mod`, `include!`, `include_str!` and
+ * `include_bin!`) and every extern crate library as prerequisites of
+ * `target`, the file that was written.
+ */
+pub fn write_dep_info(sess: Session, outputs: &OutputFilenames,
+                      target: &Path) {
+    let deps_filename = outputs.out_filename.with_filetype("d");
+    let mut deps = ~[];
+
+    for sess.codemap.files.each |fm| {
+        // Sources that aren't files have names between angle brackets
+        if !str::starts_with(fm.name, "<") && !deps.contains(&fm.name) {
+            deps.push(copy fm.name);
+        }
+    }
+    for cstore::get_used_crate_files(sess.cstore).each |lib| {
+        deps.push(lib.to_str());
+    }
+
+    match io::file_writer(&deps_filename, ~[io::Create, io::Truncate]) {
+        Ok(out) => {
+            out.write_str(dep_info_str(target.to_str(), deps));
+        }
+        Err(e) => {
+            sess.fatal(fmt!("error writing dep-info to %s: %s",
+                            deps_filename.to_str(), e));
+        }
+    }
 }
 
 pub fn compile_upto(sess: Session, +cfg: ast::crate_cfg,
                 input: input, upto: compile_upto,
                 outputs: Option<@OutputFilenames>)
-    -> (@ast::crate, Option<ty::ctxt>) {
-    let time_passes = sess.time_passes();
-    let mut crate = time(time_passes, ~"parsing",
-                         || parse_input(sess, copy cfg, input) );
-    if upto == cu_parse { return (crate, None); }
+    -> (@ast::crate, Option<ty::ctxt>, Option<middle::moves::MoveMaps>) {
+    let crate = time(sess, ~"parsing", ||
+        parse_input(sess, copy cfg, input));
+    count_items(sess, crate);
+    if upto == cu_parse { return (crate, None, None); }
 
     compile_rest(sess, cfg, upto, outputs, Some(crate))
 }
@@ -371,6 +603,22 @@ pub fn compile_input(sess: Session, +cfg: ast::crate_cfg, input: input,
                else if sess.opts.no_trans { cu_no_trans }
                else { cu_everything };
     let outputs = build_output_filenames(input, outdir, output, sess);
+
+    // The report is printed when compilation fails too, covering the
+    // passes that finished
+    struct PassReport {
+        sess: Session
+    }
+
+    impl Drop for PassReport {
+        fn finalize(&self) {
+            if self.sess.time_passes() {
+                print_pass_report(self.sess);
+            }
+        }
+    }
+
+    let _report = PassReport { sess: sess };
     compile_upto(sess, cfg, input, upto, Some(outputs));
 }
 
@@ -414,19 +662,101 @@ pub fn pretty_print_input(sess: Session, +cfg: ast::crate_cfg, input: input,
             pp::space(s.s);
             pprust::synth_comment(s, ~"pat " + int::to_str(pat.id));
           }
+          pprust::node_ident(*) => ()
+        }
+    }
+    // Nodes that macros expanded into are marked with the backtrace of the
+    // expansion, unless their parent came from the same expansion. The
+    // stack holds the expansion of each enclosing node and whether it is
+    // marked.
+    fn node_expn_info(node: pprust::ann_node) -> Option<@codemap::ExpnInfo> {
+        match node {
+          pprust::node_item(_, item) => item.span.expn_info,
+          pprust::node_block(_, blk) => blk.span.expn_info,
+          pprust::node_expr(_, expr) => expr.span.expn_info,
+          pprust::node_pat(_, pat) => pat.span.expn_info,
+          pprust::node_ident(*) => None
+        }
+    }
+    fn ann_hygiene_pre(stack: @mut ~[(Option<@codemap::ExpnInfo>, bool)],
+                       node: pprust::ann_node) {
+        match node {
+          pprust::node_ident(*) => return,
+          _ => ()
         }
+        let info = node_expn_info(node);
+        let parent = if stack.is_empty() {
+            None
+        } else {
+            stack.last().first()
+        };
+        let same = match (info, parent) {
+          (Some(a), Some(b)) => managed::ptr_eq(a, b),
+          (None, None) => true,
+          _ => false
+        };
+        stack.push((info, info.is_some() && !same));
+    }
+    fn ann_hygiene_post(cm: @codemap::CodeMap,
+                        stack: @mut ~[(Option<@codemap::ExpnInfo>, bool)],
+                        node: pprust::ann_node) {
+        match node {
+          pprust::node_ident(s, ident) => {
+            // Identifiers carry no syntax context yet, only the interned
+            // name, which tells apart names gensym'd by macros
+            pp::word(s.s, fmt!("#%u", ident.repr));
+          }
+          pprust::node_item(s, _) | pprust::node_block(s, _) |
+          pprust::node_expr(s, _) | pprust::node_pat(s, _) => {
+            let (info, marked) = stack.pop();
+            if marked {
+                pp::space(s.s);
+                pprust::synth_comment(s, expansion_to_str(cm, info.get()));
+            }
+          }
+        }
+    }
+    fn expansion_to_str(cm: @codemap::CodeMap,
+                        info: @codemap::ExpnInfo) -> ~str {
+        let mut sites = ~[];
+        let mut cur = Some(info);
+        while cur.is_some() {
+            let ei = cur.get();
+            let loc = cm.lookup_char_pos(ei.call_site.lo);
+            sites.push(fmt!("%s! at %s:%u", ei.callee.name,
+                            loc.file.name, loc.line));
+            cur = ei.call_site.expn_info;
+        }
+        ~"expanded from " + str::connect(sites, ~", from ")
     }
 
     // Because the pretty printer needs to make a pass over the source
     // to collect comments and literals, and we need to support reading
     // from stdin, we're going to just suck the source into a string
     // so both the parser and pretty-printer can use it.
+    match ppm {
+      ppm_flowgraph(ref name) => {
+        // Liveness prints the graph when it gets to the fn. Nothing is
+        // written, but compile_rest wants the output file names.
+        *sess.flowgraph_fn = Some(copy *name);
+        let outputs = build_output_filenames(input, &None, &None, sess);
+        compile_upto(sess, cfg, input, cu_no_trans, Some(outputs));
+        if !*sess.flowgraph_printed {
+            sess.fatal(fmt!("no function `%s` to print the flowgraph of",
+                            *name));
+        }
+        return;
+      }
+      _ => ()
+    }
+
     let upto = match ppm {
-      ppm_expanded | ppm_expanded_identified => cu_expand,
+      ppm_expanded | ppm_expanded_identified |
+      ppm_expanded_hygiene => cu_expand,
       ppm_typed => cu_typeck,
       _ => cu_parse
     };
-    let (crate, tcx) = compile_upto(sess, cfg, input, upto, None);
+    let (crate, tcx, _) = compile_upto(sess, cfg, input, upto, None);
 
     let ann = match ppm {
       ppm_typed => {
@@ -437,7 +767,13 @@ pub fn pretty_print_input(sess: Session, +cfg: ast::crate_cfg, input: input,
           pprust::pp_ann {pre: ann_paren_for_expr,
                           post: ann_identified_post}
       }
-      ppm_expanded | ppm_normal => {
+      ppm_expanded_hygiene => {
+          let stack = @mut ~[];
+          let cm = sess.codemap;
+          pprust::pp_ann {pre: |a| ann_hygiene_pre(stack, a),
+                          post: |a| ann_hygiene_post(cm, stack, a)}
+      }
+      ppm_expanded | ppm_normal | ppm_flowgraph(*) => {
           pprust::no_ann()
       }
     };
@@ -541,6 +877,8 @@ pub fn build_session_options(+binary: ~str,
                           -> @session::options {
     let crate_type = if opt_present(matches, ~"lib") {
         session::lib_crate
+    } else if opt_present(matches, ~"staticlib") {
+        session::staticlib_crate
     } else if opt_present(matches, ~"bin") {
         session::bin_crate
     } else {
@@ -609,6 +947,26 @@ pub fn build_session_options(+binary: ~str,
     let sysroot_opt = sysroot_opt.map(|m| Path(*m));
     let target_opt = getopts::opt_maybe_str(matches, ~"target");
     let save_temps = getopts::opt_present(matches, ~"save-temps");
+    // The emitter is chosen by rustc::monitor; only validate the format
+    match getopts::opt_maybe_str(matches, ~"error-format") {
+        Some(format) => {
+            if format != ~"text" && format != ~"json" {
+                early_error(demitter, ~"argument to `error-format` must be \
+                                        `text` or `json`");
+            }
+        }
+        None => ()
+    }
+    let dep_info = getopts::opt_present(matches, ~"dep-info");
+    let codegen_units = match getopts::opt_maybe_str(matches,
+                                                     ~"codegen-units") {
+        None => 1u,
+        Some(n) => match uint::from_str(n) {
+            Some(n) if n > 0u => n,
+            _ => early_error(demitter, ~"argument to `codegen-units` \
+                                         must be a positive integer")
+        }
+    };
     match output_type {
       // unless we're emitting huamn-readable assembly, omit comments.
       link::output_type_llvm_assembly | link::output_type_assembly => (),
@@ -663,6 +1021,9 @@ pub fn build_session_options(+binary: ~str,
         extra_debuginfo: extra_debuginfo,
         lint_opts: lint_opts,
         save_temps: save_temps,
+        dep_info: dep_info,
+        codegen_units: codegen_units,
+        lto: opt_present(matches, ~"lto"),
         jit: jit,
         output_type: output_type,
         addl_lib_search_paths: addl_lib_search_paths,
@@ -715,7 +1076,11 @@ pub fn build_session_(sopts: @session::options,
         filesearch: filesearch,
         building_library: @mut false,
         working_dir: os::getcwd(),
-        lint_settings: lint_settings
+        lint_settings: lint_settings,
+        pass_stats: @mut ~[],
+        pass_counts: @mut ~[],
+        flowgraph_fn: @mut None,
+        flowgraph_printed: @mut false
     }
 }
 
@@ -726,10 +1091,15 @@ pub fn parse_pretty(sess: Session, &&name: ~str) -> pp_mode {
       ~"typed" => ppm_typed,
       ~"expanded,identified" => ppm_expanded_identified,
       ~"identified" => ppm_identified,
+      ~"expanded,hygiene" => ppm_expanded_hygiene,
+      _ if str::starts_with(name, "flowgraph=") => {
+        ppm_flowgraph(str::slice(name, 10, name.len()))
+      }
       _ => {
         sess.fatal(~"argument to `pretty` must be one of `normal`, \
                      `expanded`, `typed`, `identified`, \
-                     or `expanded,identified`");
+                     `expanded,identified`, `expanded,hygiene`, \
+                     or `flowgraph=FUNCTION`");
       }
     }
 }
@@ -741,13 +1111,27 @@ pub fn optgroups() -> ~[getopts::groups::OptGroup] {
   optflag(~"c", ~"",    ~"Compile and assemble, but do not link"),
   optmulti(~"", ~"cfg", ~"Configure the compilation
                           environment", ~"SPEC"),
+  optopt(~"", ~"codegen-units",
+                        ~"Optimize and emit the crate as N object files
+                          in parallel, then join them", ~"N"),
+  optflag(~"",  ~"dep-info",
+                        ~"Write a Makefile-style dependency file (.d)
+                          next to the output"),
   optflag(~"",  ~"emit-llvm",
                         ~"Produce an LLVM bitcode file"),
+  optopt(~"", ~"error-format",
+                        ~"How to print diagnostics: text (default), or json
+                          (one JSON object per line)", ~"FORMAT"),
+  optopt(~"", ~"explain",
+                        ~"Print a longer description of an error code,
+                          such as E0004", ~"CODE"),
   optflag(~"h", ~"help",~"Display this message"),
   optmulti(~"L", ~"",   ~"Add a directory to the library search path",
                               ~"PATH"),
   optflag(~"",  ~"lib", ~"Compile a library crate"),
   optflag(~"",  ~"ls",  ~"List the symbols defined by a library crate"),
+  optflag(~"",  ~"lto", ~"Embed LLVM bitcode in a library; optimize an
+                          executable with the bitcode of its crates"),
   optflag(~"", ~"no-trans",
                         ~"Run all passes except translation; no output"),
   optflag(~"O", ~"",    ~"Equivalent to --opt-level=2"),
@@ -764,12 +1148,19 @@ pub fn optgroups() -> ~[getopts::groups::OptGroup] {
                           valid types are: normal (un-annotated source),
                           expanded (crates expanded),
                           typed (crates expanded, with type annotations),
-                          or identified (fully parenthesized,
-                          AST nodes and blocks with IDs)", ~"TYPE"),
+                          identified (fully parenthesized,
+                          AST nodes and blocks with IDs),
+                          expanded,hygiene (expanded, with macro
+                          backtraces and interned names),
+                          or flowgraph=FUNCTION (the control flow graph
+                          of a function, as Graphviz dot)", ~"TYPE"),
   optflag(~"S", ~"",    ~"Compile only; do not assemble or link"),
   optflag(~"", ~"save-temps",
                         ~"Write intermediate files (.bc, .opt.bc, .o)
                           in addition to normal output"),
+  optflag(~"", ~"staticlib",
+                        ~"Compile a static library holding the crate,
+                          the crates it uses and the runtime"),
   optopt(~"", ~"sysroot",
                         ~"Override the system root", ~"PATH"),
   optflag(~"", ~"test", ~"Build a test harness"),
@@ -840,7 +1231,10 @@ pub fn build_output_filenames(input: input,
           str_input(_) => ~"rust_out"
         };
 
-        if *sess.building_library {
+        if sopts.crate_type == session::staticlib_crate {
+            out_path = dirpath.push(fmt!("lib%s.a", stem));
+            obj_path = dirpath.push(stem).with_filetype(obj_suffix);
+        } else if *sess.building_library {
             out_path = dirpath.push(os::dll_filename(stem));
             obj_path = dirpath.push(stem).with_filetype(obj_suffix);
         } else {
@@ -877,7 +1271,7 @@ pub fn build_output_filenames(input: input,
 }
 
 pub fn early_error(emitter: diagnostic::Emitter, msg: ~str) -> ! {
-    emitter(None, msg, diagnostic::fatal);
+    emitter(None, msg, diagnostic::fatal, None, None);
     fail!();
 }
 
@@ -893,7 +1287,10 @@ pub mod test {
 
     use driver::driver::{build_configuration, build_session};
     use driver::driver::{build_session_options, optgroups, str_input};
+    use driver::driver::{dep_info_str, pass_report_table};
+    use driver::session::PassStats;
 
+    use core::str;
     use core::vec;
     use std::getopts::groups::getopts;
     use std::getopts;
@@ -935,6 +1332,40 @@ pub mod test {
         let test_items = attr::find_meta_items_by_name(cfg, ~"test");
         fail_unless!((vec::len(test_items) == 1u));
     }
+
+    #[test]
+    pub fn test_dep_info_str() {
+        let s = dep_info_str("foo", ~[~"foo.rc", ~"my dir/bar.rs"]);
+        fail_unless!(s == ~"foo: \\\n    foo.rc \\\n    my\\ dir/bar.rs\n\
+                             \nfoo.rc:\n\nmy\\ dir/bar.rs:\n");
+        let s = dep_info_str("lib$foo.so", ~[~"$bar.rs"]);
+        fail_unless!(s == ~"lib$$foo.so: \\\n    $$bar.rs\n\n$$bar.rs:\n");
+    }
+
+    #[test]
+    pub fn test_pass_report_table() {
+        let stats = ~[
+            PassStats { name: ~"parsing", time: 0.5, peak_rss: Some(1024),
+                        counts: ~[(~"node ids", 10), (~"items", 2)] },
+            PassStats { name: ~"translation", time: 0.25, peak_rss: None,
+                        counts: ~[(~"node ids", 12),
+                                  (~"monomorphized fns", 3)] }
+        ];
+        let mut lines = ~[];
+        for str::lines_each(pass_report_table(stats)) |line| {
+            lines.push(line.to_str());
+        }
+
+        // A count a pass didn't report shows as "-"
+        fail_unless!(lines.len() == 4);
+        fail_unless!(str::starts_with(lines[0], "pass "));
+        fail_unless!(str::ends_with(lines[0], "  monomorphized fns"));
+        fail_unless!(str::words(lines[1]) ==
+                     ~[~"parsing", ~"0.5", ~"1024", ~"10", ~"2", ~"-"]);
+        fail_unless!(str::words(lines[2]) ==
+                     ~[~"translation", ~"0.25", ~"-", ~"12", ~"-", ~"3"]);
+        fail_unless!(str::words(lines[3]) == ~[~"total", ~"0.75"]);
+    }
 }
 
 // Local Variables:
@@ -33,7 +33,8 @@ pub enum os { os_win32, os_macos, os_linux, os_android, os_freebsd, }
 #[deriving_eq]
 pub enum arch { arch_x86, arch_x86_64, arch_arm, arch_mips, }
 
-pub enum crate_type { bin_crate, lib_crate, unknown_crate, }
+#[deriving_eq]
+pub enum crate_type { bin_crate, lib_crate, staticlib_crate, unknown_crate, }
 
 pub struct config {
     os: os,
@@ -67,15 +68,21 @@ pub const jit: uint = 1 << 19;
 pub const debug_info: uint = 1 << 20;
 pub const extra_debug_info: uint = 1 << 21;
 pub const static: uint = 1 << 22;
+pub const time_passes_json: uint = 1 << 23;
+pub const print_mono_items: uint = 1 << 24;
 
 pub fn debugging_opts_map() -> ~[(~str, ~str, uint)] {
     ~[(~"verbose", ~"in general, enable more debug printouts", verbose),
      (~"time-passes", ~"measure time of each rustc pass", time_passes),
+     (~"time-passes-json", ~"measure each rustc pass, reporting as JSON",
+      time_passes_json),
      (~"count-llvm-insns", ~"count where LLVM \
                            instrs originate", count_llvm_insns),
      (~"time-llvm-passes", ~"measure time of each LLVM pass",
       time_llvm_passes),
      (~"trans-stats", ~"gather trans statistics", trans_stats),
+     (~"print-mono-items", ~"list the instances of each generic function",
+      print_mono_items),
      (~"no-asm-comments", ~"omit comments when using -S", no_asm_comments),
      (~"no-verify", ~"skip LLVM verification", no_verify),
      (~"trace", ~"emit trace logs", trace),
@@ -123,6 +130,13 @@ pub struct options {
     extra_debuginfo: bool,
     lint_opts: ~[(lint::lint, lint::level)],
     save_temps: bool,
+    // Write a Makefile-style .d file listing the crate's inputs
+    dep_info: bool,
+    // How many object files the crate is optimized and emitted as
+    codegen_units: uint,
+    // Embed bitcode in libraries, and optimize executables together with
+    // the bitcode of the crates they use
+    lto: bool,
     jit: bool,
     output_type: back::link::output_type,
     addl_lib_search_paths: ~[Path],
@@ -146,6 +160,18 @@ pub struct crate_metadata {
     data: ~[u8]
 }
 
+// What -Z time-passes measured about one pass of the compiler
+pub struct PassStats {
+    name: ~str,
+    // Wall clock time, in seconds
+    time: float,
+    // The most memory the process has had resident by the end of the pass,
+    // in kilobytes, on platforms that report it
+    peak_rss: Option<uint>,
+    // Things the pass counted, such as items or monomorphized functions
+    counts: ~[(~str, uint)]
+}
+
 pub struct Session_ {
     targ_cfg: @config,
     opts: @options,
@@ -158,7 +184,16 @@ pub struct Session_ {
     filesearch: @filesearch::FileSearch,
     building_library: @mut bool,
     working_dir: Path,
-    lint_settings: lint::LintSettings
+    lint_settings: lint::LintSettings,
+    // The passes run so far, when timing passes
+    pass_stats: @mut ~[PassStats],
+    // Counts reported by the pass that is running
+    pass_counts: @mut ~[(~str, uint)],
+    // The name or node id of the fn to print the flowgraph of, for
+    // --pretty=flowgraph
+    flowgraph_fn: @mut Option<~str>,
+    // Whether a flowgraph was printed for flowgraph_fn
+    flowgraph_printed: @mut bool
 }
 
 pub type Session = @Session_;
@@ -167,15 +202,25 @@ pub impl Session_ {
     fn span_fatal(@self, sp: span, msg: ~str) -> ! {
         self.span_diagnostic.span_fatal(sp, msg)
     }
+    fn span_fatal_with_code(@self, sp: span, msg: ~str, code: &str) -> ! {
+        self.span_diagnostic.span_fatal_with_code(sp, msg, code)
+    }
     fn fatal(@self, msg: ~str) -> ! {
         self.span_diagnostic.handler().fatal(msg)
     }
     fn span_err(@self, sp: span, msg: ~str) {
         self.span_diagnostic.span_err(sp, msg)
     }
+    // `code` is one of the error codes in `diagnostics`
+    fn span_err_with_code(@self, sp: span, msg: ~str, code: &str) {
+        self.span_diagnostic.span_err_with_code(sp, msg, code)
+    }
     fn err(@self, msg: ~str) {
         self.span_diagnostic.handler().err(msg)
     }
+    fn err_with_code(@self, msg: ~str, code: &str) {
+        self.span_diagnostic.handler().err_with_code(msg, code)
+    }
     fn has_errors(@self) -> bool {
         self.span_diagnostic.handler().has_errors()
     }
@@ -206,12 +251,18 @@ pub impl Session_ {
     fn unimpl(@self, msg: ~str) -> ! {
         self.span_diagnostic.handler().unimpl(msg)
     }
-    fn span_lint_level(@self, level: lint::level, sp: span, +msg: ~str) {
+    fn span_lint_level(@self, lint: lint::lint, level: lint::level,
+                       sp: span, +msg: ~str) {
+        let name = lint::lint_to_str(lint);
         match level {
           lint::allow => { },
-          lint::warn => self.span_warn(sp, msg),
+          lint::warn => {
+            self.span_diagnostic.span_lint(sp, msg, diagnostic::warning,
+                                           name);
+          }
           lint::deny | lint::forbid => {
-            self.span_err(sp, msg);
+            self.span_diagnostic.span_lint(sp, msg, diagnostic::error,
+                                           name);
           }
         }
     }
@@ -222,7 +273,7 @@ pub impl Session_ {
                  +msg: ~str) {
         let level = lint::get_lint_settings_level(
             self.lint_settings, lint_mode, expr_id, item_id);
-        self.span_lint_level(level, span, msg);
+        self.span_lint_level(lint_mode, level, span, msg);
     }
     fn next_node_id(@self) -> ast::node_id {
         return syntax::parse::next_node_id(self.parse_sess);
@@ -239,7 +290,26 @@ pub impl Session_ {
         self.span_bug(sp, fmt!("Impossible case reached: %s", msg));
     }
     fn verbose(@self) -> bool { self.debugging_opt(verbose) }
-    fn time_passes(@self) -> bool { self.debugging_opt(time_passes) }
+    fn time_passes(@self) -> bool {
+        self.debugging_opt(time_passes) || self.time_passes_json()
+    }
+    fn time_passes_json(@self) -> bool {
+        self.debugging_opt(time_passes_json)
+    }
+    // Add a count to the -Z time-passes report of the running pass
+    fn count_in_pass(@self, what: ~str, n: uint) {
+        if self.time_passes() {
+            self.pass_counts.push((what, n));
+        }
+    }
+    // Add a count to the -Z time-passes report of the pass that finished
+    // last, for counting that shouldn't be timed as part of it
+    fn count_in_last_pass(@self, what: ~str, n: uint) {
+        if self.time_passes() && !self.pass_stats.is_empty() {
+            let last = self.pass_stats.len() - 1;
+            self.pass_stats[last].counts.push((what, n));
+        }
+    }
     fn count_llvm_insns(@self) -> bool {
         self.debugging_opt(count_llvm_insns)
     }
@@ -250,6 +320,9 @@ pub impl Session_ {
         self.debugging_opt(time_llvm_passes)
     }
     fn trans_stats(@self) -> bool { self.debugging_opt(trans_stats) }
+    fn print_mono_items(@self) -> bool {
+        self.debugging_opt(print_mono_items)
+    }
     fn meta_stats(@self) -> bool { self.debugging_opt(meta_stats) }
     fn no_asm_comments(@self) -> bool { self.debugging_opt(no_asm_comments) }
     fn no_verify(@self) -> bool { self.debugging_opt(no_verify) }
@@ -288,6 +361,9 @@ pub fn basic_options() -> @options {
         extra_debuginfo: false,
         lint_opts: ~[],
         save_temps: false,
+        dep_info: false,
+        codegen_units: 1u,
+        lto: false,
         jit: false,
         output_type: link::output_type_exe,
         addl_lib_search_paths: ~[],
@@ -316,7 +392,7 @@ pub fn building_library(req_crate_type: crate_type,
                         testing: bool) -> bool {
     match req_crate_type {
       bin_crate => false,
-      lib_crate => true,
+      lib_crate | staticlib_crate => true,
       unknown_crate => {
         if testing {
             false
@@ -347,7 +423,7 @@ pub fn sess_os_to_meta_os(os: os) -> metadata::loader::os {
 #[cfg(test)]
 pub mod test {
     use driver::session::{bin_crate, building_library, lib_crate};
-    use driver::session::{unknown_crate};
+    use driver::session::{staticlib_crate, unknown_crate};
 
     use syntax::ast;
     use syntax::codemap;
@@ -416,6 +492,12 @@ pub mod test {
         let crate = make_crate(false, false);
         fail_unless!(building_library(lib_crate, crate, true));
     }
+
+    #[test]
+    pub fn staticlib_option_overrides_bin_crate_type() {
+        let crate = make_crate(true, false);
+        fail_unless!(building_library(staticlib_crate, crate, false));
+    }
 }
 
 // Local Variables:
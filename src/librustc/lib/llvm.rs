@@ -230,6 +230,7 @@ pub mod llvm {
     use super::{ValueRef};
 
     use core::libc::{c_char, c_int, c_longlong, c_uint, c_ulonglong};
+    use core::libc::size_t;
 
     #[link_args = "-Lrustllvm -lrustllvm"]
     #[link_name = "rustllvm"]
@@ -1308,6 +1309,8 @@ pub mod llvm {
         pub unsafe fn LLVMAddIndVarSimplifyPass(PM: PassManagerRef);
         pub unsafe fn LLVMAddAggressiveDCEPass(PM: PassManagerRef);
         pub unsafe fn LLVMAddGlobalDCEPass(PM: PassManagerRef);
+        pub unsafe fn LLVMAddInternalizePass(PM: PassManagerRef,
+                                             AllButMain: c_uint);
         pub unsafe fn LLVMAddCorrelatedValuePropagationPass(PM:
                                                             PassManagerRef);
         pub unsafe fn LLVMAddPruneEHPass(PM: PassManagerRef);
@@ -1338,6 +1341,10 @@ pub mod llvm {
         pub unsafe fn LLVMPassManagerBuilderPopulateFunctionPassManager
             (PMB: PassManagerBuilderRef, PM: PassManagerRef);
 
+        pub unsafe fn LLVMPassManagerBuilderPopulateLTOPassManager
+            (PMB: PassManagerBuilderRef, PM: PassManagerRef,
+             Internalize: Bool, RunInliner: Bool);
+
         /** Destroys a memory buffer. */
         pub unsafe fn LLVMDisposeMemoryBuffer(MemBuf: MemoryBufferRef);
 
@@ -1410,6 +1417,25 @@ pub mod llvm {
         pub unsafe fn LLVMRustParseBitcode(MemBuf: MemoryBufferRef)
                                         -> ModuleRef;
 
+        /** Parses the bitcode in the given memory buffer into the given
+            context. */
+        pub unsafe fn LLVMRustParseBitcodeInContext(C: ContextRef,
+                                                    MemBuf: MemoryBufferRef)
+                                                 -> ModuleRef;
+
+        /** Parses the bitcode in the given bytes. */
+        pub unsafe fn LLVMRustParseBitcodeBytes(Data: *c_char, Len: size_t)
+                                             -> ModuleRef;
+
+        /** Links the contents of Src into Dst, leaving Src empty. Returns
+            false on error. */
+        pub unsafe fn LLVMRustLinkModules(Dst: ModuleRef, Src: ModuleRef)
+                                       -> bool;
+
+        /** Prepares LLVM for emitting modules from several threads at
+            once. Returns false if LLVM was built without thread support. */
+        pub unsafe fn LLVMRustStartMultithreading() -> bool;
+
         /** Parses LLVM asm in the given file */
         pub unsafe fn LLVMRustParseAssemblyFile(Filename: *c_char)
                                              -> ModuleRef;
@@ -67,14 +67,20 @@ pub fn each_path(cstore: @mut cstore::CStore,
 }
 
 pub fn get_item_path(tcx: ty::ctxt, def: ast::def_id) -> ast_map::path {
-    let cstore = tcx.cstore;
+    get_path(tcx.cstore, def)
+}
+
+/// The path of an item in another crate, starting with the crate's name.
+/// Unlike `get_item_path` this doesn't need a type context.
+pub fn get_path(cstore: @mut cstore::CStore, def: ast::def_id)
+             -> ast_map::path {
     let cdata = cstore::get_crate_data(cstore, def.crate);
     let path = decoder::get_item_path(cstore.intr, cdata, def.node);
 
     // FIXME #1920: This path is not always correct if the crate is not linked
     // into the root namespace.
-    vec::append(~[ast_map::path_mod(tcx.sess.ident_of(
-        /*bad*/copy *cdata.name))], path)
+    vec::append(~[ast_map::path_mod(cstore.intr.intern(
+        @/*bad*/copy *cdata.name))], path)
 }
 
 pub enum found_ast {
@@ -1216,8 +1216,6 @@ fn encode_crate_deps(ecx: @EncodeContext,
                      cstore: @mut cstore::CStore) {
     fn get_ordered_deps(ecx: @EncodeContext, cstore: @mut cstore::CStore)
                      -> ~[decoder::crate_dep] {
-        type numdep = decoder::crate_dep;
-
         // Pull the cnums and name,vers,hash out of cstore
         let mut deps = ~[];
         do cstore::iter_crate_data(cstore) |key, val| {
@@ -241,6 +241,38 @@ fn get_metadata_section(os: os,
     }
 }
 
+/**
+ * The LLVM bitcode that a library built with `--lto` carries, inflated,
+ * or None if the library has none
+ */
+pub fn get_bitcode_section(os: os, filename: &Path) -> Option<~[u8]> {
+    unsafe {
+        let mb = str::as_c_str(filename.to_str(), |buf| {
+            llvm::LLVMRustCreateMemoryBufferWithContentsOfFile(buf)
+        });
+        if mb as int == 0 { return None; }
+        let of = match mk_object_file(mb) {
+            Some(of) => of,
+            None => return None
+        };
+        let si = mk_section_iter(of.llof);
+        while llvm::LLVMIsSectionIteratorAtEnd(of.llof, si.llsi) == False {
+            let name_buf = llvm::LLVMGetSectionName(si.llsi);
+            if str::raw::from_c_str(name_buf) == bitcode_section_name(os) {
+                let cbuf = llvm::LLVMGetSectionContents(si.llsi);
+                let csz = llvm::LLVMGetSectionSize(si.llsi) as uint;
+                let cvbuf: *u8 = cast::reinterpret_cast(&cbuf);
+                debug!("inflating %u bytes of compressed bitcode", csz);
+                return do vec::raw::buf_as_slice(cvbuf, csz) |bytes| {
+                    Some(flate::inflate_bytes(bytes))
+                };
+            }
+            llvm::LLVMMoveToNextSection(si.llsi);
+        }
+        None
+    }
+}
+
 pub fn meta_section_name(os: os) -> ~str {
     match os {
       os_macos => ~"__DATA,__note.rustc",
@@ -251,6 +283,16 @@ pub fn meta_section_name(os: os) -> ~str {
     }
 }
 
+pub fn bitcode_section_name(os: os) -> ~str {
+    match os {
+      os_macos => ~"__DATA,__note.rustc.bc",
+      os_win32 => ~".note.rustc.bc",
+      os_linux => ~".note.rustc.bc",
+      os_android => ~".note.rustc.bc",
+      os_freebsd => ~".note.rustc.bc"
+    }
+}
+
 // A diagnostic function for dumping crate metadata to an output stream
 pub fn list_file_metadata(intr: @ident_interner,
                           os: os,
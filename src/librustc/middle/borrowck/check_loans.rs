@@ -19,6 +19,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::moves;
 use middle::borrowck::{Loan, bckerr, BorrowckCtxt, inherent_mutability};
 use middle::borrowck::{ReqMaps, root_map_key, save_and_restore_managed};
@@ -316,12 +317,13 @@ pub impl CheckLoanCtxt {
             (TotalTake, PartialFreeze) | (PartialFreeze, TotalTake) |
             (TotalTake, PartialTake) | (PartialTake, TotalTake) |
             (TotalTake, TotalTake) => {
-                self.bccx.span_err(
+                self.bccx.span_err_with_code(
                     new_loan.cmt.span,
                     fmt!("loan of %s as %s \
                           conflicts with prior loan",
                          self.bccx.cmt_to_str(new_loan.cmt),
-                         self.bccx.loan_kind_to_str(new_loan.kind)));
+                         self.bccx.loan_kind_to_str(new_loan.kind)),
+                    diagnostics::conflicting_loan);
                 self.bccx.span_note(
                     old_loan.cmt.span,
                     fmt!("prior loan as %s granted here",
@@ -355,9 +357,10 @@ pub impl CheckLoanCtxt {
             match cmt.mutbl {
                 McDeclared | McInherited => { /*ok*/ }
                 McReadOnly | McImmutable => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         ex.span,
-                        at.ing_form(self.bccx.cmt_to_str(cmt)));
+                        at.ing_form(self.bccx.cmt_to_str(cmt)),
+                        diagnostics::assign_to_immutable);
                     return;
                 }
             }
@@ -399,6 +402,7 @@ pub impl CheckLoanCtxt {
         }
 
         self.bccx.add_to_mutbl_map(cmt);
+        self.bccx.note_used_mut(cmt);
 
         // Check for and insert write guards as necessary.
         self.add_write_guards_if_necessary(cmt);
@@ -437,10 +441,11 @@ pub impl CheckLoanCtxt {
                 Immobile => { /* ok */ }
                 TotalFreeze | PartialFreeze |
                 TotalTake | PartialTake => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         ex.span,
                         fmt!("%s prohibited due to outstanding loan",
-                             at.ing_form(self.bccx.cmt_to_str(cmt))));
+                             at.ing_form(self.bccx.cmt_to_str(cmt))),
+                        diagnostics::outstanding_loan);
                     self.bccx.span_note(
                         loan.cmt.span,
                         fmt!("loan of %s granted here",
@@ -469,17 +474,19 @@ pub impl CheckLoanCtxt {
     fn report_purity_error(@mut self, pc: purity_cause, sp: span, msg: ~str) {
         match pc {
           pc_pure_fn => {
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 sp,
-                fmt!("%s prohibited in pure context", msg));
+                fmt!("%s prohibited in pure context", msg),
+                diagnostics::impure_in_pure_context);
           }
           pc_cmt(ref e) => {
             let reported = self.reported;
             if reported.insert((*e).cmt.id, ()) {
-                self.tcx().sess.span_err(
+                self.tcx().sess.span_err_with_code(
                     (*e).cmt.span,
                     fmt!("illegal borrow unless pure: %s",
-                         self.bccx.bckerr_to_str((*e))));
+                         self.bccx.bckerr_to_str((*e))),
+                    diagnostics::impure_in_pure_context);
                 self.bccx.note_and_explain_bckerr((*e));
                 self.tcx().sess.span_note(
                     sp,
@@ -501,17 +508,19 @@ pub impl CheckLoanCtxt {
                 match self.analyze_move_out_from_cmt(cmt) {
                     MoveOk => {}
                     MoveFromIllegalCmt(_) => {
-                        self.bccx.span_err(
+                        self.bccx.span_err_with_code(
                             cmt.span,
                             fmt!("moving out of %s",
-                                 self.bccx.cmt_to_str(cmt)));
+                                 self.bccx.cmt_to_str(cmt)),
+                            diagnostics::illegal_move);
                     }
                     MoveWhileBorrowed(_, loan_cmt) => {
-                        self.bccx.span_err(
+                        self.bccx.span_err_with_code(
                             cmt.span,
                             fmt!("moving out of %s prohibited \
                                   due to outstanding loan",
-                                 self.bccx.cmt_to_str(cmt)));
+                                 self.bccx.cmt_to_str(cmt)),
+                            diagnostics::outstanding_loan);
                         self.bccx.span_note(
                             loan_cmt.span,
                             fmt!("loan of %s granted here",
@@ -656,17 +665,19 @@ fn check_loans_in_fn(fk: &visit::fn_kind,
                     match move_err {
                         MoveOk => {}
                         MoveFromIllegalCmt(move_cmt) => {
-                            self.bccx.span_err(
+                            self.bccx.span_err_with_code(
                                 cap_var.span,
                                 fmt!("illegal by-move capture of %s",
-                                     self.bccx.cmt_to_str(move_cmt)));
+                                     self.bccx.cmt_to_str(move_cmt)),
+                                diagnostics::illegal_move);
                         }
                         MoveWhileBorrowed(move_cmt, loan_cmt) => {
-                            self.bccx.span_err(
+                            self.bccx.span_err_with_code(
                                 cap_var.span,
                                 fmt!("by-move capture of %s prohibited \
                                       due to outstanding loan",
-                                     self.bccx.cmt_to_str(move_cmt)));
+                                     self.bccx.cmt_to_str(move_cmt)),
+                                diagnostics::outstanding_loan);
                             self.bccx.span_note(
                                 loan_cmt.span,
                                 fmt!("loan of %s granted here",
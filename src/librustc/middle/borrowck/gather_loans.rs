@@ -363,6 +363,10 @@ pub impl GatherLoanCtxt {
             m_const => Immobile
         };
 
+        if req_mutbl == m_mutbl {
+            self.bccx.note_used_mut(cmt);
+        }
+
         self.bccx.stats.guaranteed_paths += 1;
 
         debug!("guarantee_valid(cmt=%s, req_mutbl=%?, \
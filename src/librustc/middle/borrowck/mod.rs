@@ -226,6 +226,7 @@ Borrowck results in two maps.
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::mem_categorization::*;
 use middle::region;
 use middle::ty;
@@ -523,15 +524,16 @@ pub impl BorrowckCtxt {
     }
 
     fn report(&self, err: bckerr) {
-        self.span_err(
+        self.span_err_with_code(
             err.cmt.span,
             fmt!("illegal borrow: %s",
-                 self.bckerr_to_str(err)));
+                 self.bckerr_to_str(err)),
+            diagnostics::illegal_borrow);
         self.note_and_explain_bckerr(err);
     }
 
-    fn span_err(&self, s: span, +m: ~str) {
-        self.tcx.sess.span_err(s, m);
+    fn span_err_with_code(&self, s: span, +m: ~str, code: &str) {
+        self.tcx.sess.span_err_with_code(s, m, code);
     }
 
     fn span_note(&self, s: span, +m: ~str) {
@@ -550,6 +552,24 @@ pub impl BorrowckCtxt {
         }
     }
 
+    // Note that the `mut` local variable or argument `cmt` is stored in, if
+    // any, was needed, for the unused_mut lint
+    fn note_used_mut(&self, cmt: cmt) {
+        match cmt.cat {
+          cat_local(id) | cat_arg(id) => {
+            self.tcx.used_mut_nodes.insert(id, ());
+          }
+          cat_stack_upvar(cmt) => {
+            self.note_used_mut(cmt);
+          }
+          cat_comp(base, _) | cat_deref(base, _, uniq_ptr)
+                if cmt.mutbl == McInherited => {
+            self.note_used_mut(base);
+          }
+          _ => ()
+        }
+    }
+
     fn bckerr_to_str(&self, err: bckerr) -> ~str {
         match err.code {
             err_mutbl(lk) => {
@@ -23,8 +23,12 @@ use core::uint;
 use core::vec;
 use std::sort;
 use syntax::ast::*;
+use syntax::ast_map::{path_mod, path_name};
 use syntax::ast_util::{variant_def_ids, unguarded_pat, walk_pat};
+use syntax::ast_util::ident_to_path;
 use syntax::codemap::{span, dummy_sp, spanned};
+use syntax::parse::token::special_idents;
+use syntax::print::pprust::pat_to_str;
 use syntax::visit;
 
 pub struct MatchCheckCtxt {
@@ -116,6 +120,7 @@ pub fn check_arms(cx: @MatchCheckCtxt, arms: &[arm]) {
             match is_useful(cx, &seen, v) {
               not_useful => {
                 cx.tcx.sess.span_err(pat.span, ~"unreachable pattern");
+                note_covering_pats(cx, seen, v);
               }
               _ => ()
             }
@@ -124,6 +129,33 @@ pub fn check_arms(cx: @MatchCheckCtxt, arms: &[arm]) {
     }
 }
 
+// Points at the earlier patterns that make the unreachable `v` unreachable:
+// a single pattern that covers it if there is one, otherwise the shortest
+// run of arms from the top that covers it
+fn note_covering_pats(cx: @MatchCheckCtxt, seen: &[~[@pat]], v: &[@pat]) {
+    for seen.each |r| {
+        match is_useful(cx, &~[copy *r], v) {
+          not_useful => {
+            cx.tcx.sess.span_note(r[0].span,
+                                  ~"already matched by this pattern");
+            return;
+          }
+          _ => ()
+        }
+    }
+    for uint::range(1, seen.len() + 1) |n| {
+        match is_useful(cx, &vec::from_slice(vec::slice(seen, 0, n)), v) {
+          not_useful => {
+            cx.tcx.sess.span_note(seen[n - 1][0].span,
+                                  ~"already matched by this pattern \
+                                    together with the ones before it");
+            return;
+          }
+          _ => ()
+        }
+    }
+}
+
 pub fn raw_pat(p: @pat) -> @pat {
     match p.node {
       pat_ident(_, _, Some(s)) => { raw_pat(s) }
@@ -133,55 +165,24 @@ pub fn raw_pat(p: @pat) -> @pat {
 
 pub fn check_exhaustive(cx: @MatchCheckCtxt, sp: span, pats: ~[@pat]) {
     fail_unless!((!pats.is_empty()));
-    let ext = match is_useful(cx, &pats.map(|p| ~[*p]), ~[wild()]) {
+    match is_useful(cx, &pats.map(|p| ~[*p]), ~[wild()]) {
         not_useful => {
             // This is good, wildcard pattern isn't reachable
-            return;
         }
-        useful_ => None,
-        useful(ty, ref ctor) => {
-            match ty::get(ty).sty {
-                ty::ty_bool => {
-                    match (*ctor) {
-                        val(const_bool(true)) => Some(@~"true"),
-                        val(const_bool(false)) => Some(@~"false"),
-                        _ => None
-                    }
-                }
-                ty::ty_enum(id, _) => {
-                    let vid = match *ctor {
-                        variant(id) => id,
-                        _ => fail!(~"check_exhaustive: non-variant ctor"),
-                    };
-                    let variants = ty::enum_variants(cx.tcx, id);
-
-                    match variants.find(|v| v.id == vid) {
-                        Some(v) => Some(cx.tcx.sess.str_of(v.name)),
-                        None => {
-                            fail!(~"check_exhaustive: bad variant in ctor")
-                        }
-                    }
-                }
-                ty::ty_unboxed_vec(*) | ty::ty_evec(*) => {
-                    match *ctor {
-                        vec(n) => Some(@fmt!("vectors of length %u", n)),
-                        _ => None
-                    }
-                }
-                _ => None
-            }
+        useful(ref witness) => {
+            cx.tcx.sess.span_err(sp, fmt!("non-exhaustive patterns: `%s` \
+                                           not covered",
+                                          pat_to_str(witness[0],
+                                                     cx.tcx.sess.intr())));
         }
-    };
-    let msg = ~"non-exhaustive patterns" + match ext {
-        Some(ref s) => ~": " + **s + ~" not covered",
-        None => ~""
-    };
-    cx.tcx.sess.span_err(sp, msg);
+    }
 }
 
 pub type matrix = ~[~[@pat]];
 
-pub enum useful { useful(ty::t, ctor), useful_, not_useful }
+// When `v` is useful, the witness is a value that `v` matches and no row of
+// the matrix does, as one pattern per column of `v`.
+pub enum useful { useful(~[@pat]), not_useful }
 
 #[deriving_eq]
 pub enum ctor {
@@ -206,7 +207,9 @@ pub enum ctor {
 // Note: is_useful doesn't work on empty types, as the paper notes.
 // So it assumes that v is non-empty.
 pub fn is_useful(cx: @MatchCheckCtxt, m: &matrix, v: &[@pat]) -> useful {
-    if m.len() == 0u { return useful_; }
+    if m.len() == 0u {
+        return useful(v.map(|p| if is_wild(cx, *p) { wild() } else { *p }));
+    }
     if m[0].len() == 0u { return not_useful; }
     let real_pat = match m.find(|r| r[0].id != 0) {
       Some(r) => r[0], None => v[0]
@@ -268,8 +271,18 @@ pub fn is_useful(cx: @MatchCheckCtxt, m: &matrix, v: &[@pat]) -> useful {
             match is_useful(cx,
                             &m.filter_mapped(|r| default(cx, *r)),
                             v.tail()) {
-              useful_ => useful(left_ty, (/*bad*/copy *ctor)),
-              ref u => (/*bad*/copy *u)
+              useful(ref rest) => {
+                // Any value of the missing constructor will do
+                let first = if *ctor == single {
+                    wild()
+                } else {
+                    let arity = ctor_arity(cx, /*bad*/copy *ctor, left_ty);
+                    ctor_witness(cx, ctor, vec::from_elem(arity, wild()),
+                                 left_ty)
+                };
+                useful(vec::append(~[first], *rest))
+              }
+              not_useful => not_useful
             }
           }
         }
@@ -292,11 +305,80 @@ pub fn is_useful_specialized(cx: @MatchCheckCtxt,
     let could_be_useful = is_useful(
         cx, &ms, specialize(cx, v, ctor, arity, lty).get());
     match could_be_useful {
-      useful_ => useful(lty, ctor),
-      ref u => (/*bad*/copy *u)
+      useful(ref w) => {
+        // The first `arity` columns are the constructor's arguments
+        let first = ctor_witness(cx, &ctor, vec::slice(*w, 0, arity), lty);
+        useful(vec::append(~[first], vec::slice(*w, arity, w.len())))
+      }
+      not_useful => not_useful
     }
 }
 
+// The pattern for the constructor `ctor` of type `ty` applied to `args`,
+// used to show the user a value that is not covered
+pub fn ctor_witness(cx: @MatchCheckCtxt,
+                    ctor: &ctor,
+                    args: &[@pat],
+                    ty: ty::t)
+                 -> @pat {
+    let node = match ty::get(ty).sty {
+      ty::ty_tup(_) => pat_tup(vec::from_slice(args)),
+      ty::ty_box(_) => pat_box(args[0]),
+      ty::ty_uniq(_) => pat_uniq(args[0]),
+      ty::ty_rptr(*) => pat_region(args[0]),
+      ty::ty_enum(eid, _) => {
+        let vid = match *ctor {
+            variant(id) => id,
+            _ => fail!(~"ctor_witness: non-variant ctor")
+        };
+        match vec::find(*ty::enum_variants(cx.tcx, eid), |v| v.id == vid) {
+            Some(v) => pat_enum(ident_to_path(dummy_sp(), v.name),
+                                Some(vec::from_slice(args))),
+            None => fail!(~"ctor_witness: bad variant in ctor")
+        }
+      }
+      ty::ty_struct(cid, _) => {
+        let name = match *ty::item_path(cx.tcx, cid).last() {
+            path_mod(id) | path_name(id) => id
+        };
+        let path = ident_to_path(dummy_sp(), name);
+        let fields = ty::lookup_struct_fields(cx.tcx, cid);
+        if fields.any(|f| f.ident == special_idents::unnamed_field) {
+            pat_enum(path, Some(vec::from_slice(args)))
+        } else {
+            // Only show the fields that matter
+            let mut fpats = ~[];
+            for vec::each2(fields, args) |f, &p| {
+                if !is_wild(cx, p) {
+                    fpats.push(field_pat {ident: f.ident, pat: p});
+                }
+            }
+            let etc = fpats.len() < fields.len();
+            pat_struct(path, fpats, etc)
+        }
+      }
+      ty::ty_bool => {
+        match *ctor {
+            val(const_bool(b)) => {
+                pat_lit(@expr {
+                    id: 0,
+                    callee_id: 0,
+                    node: expr_lit(@spanned {node: lit_bool(b),
+                                             span: dummy_sp()}),
+                    span: dummy_sp()
+                })
+            }
+            _ => pat_wild
+        }
+      }
+      ty::ty_unboxed_vec(*) | ty::ty_evec(*) => {
+        pat_vec(vec::from_slice(args), None, ~[])
+      }
+      _ => pat_wild
+    };
+    @pat {id: 0, node: node, span: dummy_sp()}
+}
+
 pub fn pat_ctor_id(cx: @MatchCheckCtxt, p: @pat) -> Option<ctor> {
     let pat = raw_pat(p);
     match pat.node {
@@ -12,7 +12,9 @@ use core::prelude::*;
 
 use driver::session::Session;
 use driver::session;
+use middle::pat_util;
 use middle::ty;
+use middle::typeck;
 use util::ppaux::{ty_to_str};
 
 use core::char;
@@ -33,6 +35,7 @@ use core::vec;
 use std::oldmap::{Map, HashMap};
 use std::oldmap;
 use std::smallintmap::SmallIntMap;
+use syntax::ast_map;
 use syntax::attr;
 use syntax::codemap::span;
 use syntax::codemap;
@@ -85,6 +88,10 @@ pub enum lint {
 
     legacy_modes,
 
+    dead_code,
+    unused_mut,
+    unused_unsafe,
+
     // FIXME(#3266)--make liveness warnings lintable
     // unused_variable,
     // dead_assignment
@@ -259,6 +266,27 @@ pub fn get_lint_dict() -> LintDict {
             default: deny
         }),
 
+        (@~"dead_code",
+         @LintSpec {
+            lint: dead_code,
+            desc: "private items that are never used",
+            default: warn
+        }),
+
+        (@~"unused_mut",
+         @LintSpec {
+            lint: unused_mut,
+            desc: "detect mut variables which don't need to be mutable",
+            default: allow
+        }),
+
+        (@~"unused_unsafe",
+         @LintSpec {
+            lint: unused_unsafe,
+            desc: "unnecessary use of an unsafe block",
+            default: allow
+        }),
+
         /* FIXME(#3266)--make liveness warnings lintable
         (@~"unused_variable",
          @LintSpec {
@@ -297,6 +325,16 @@ pub fn mk_lint_settings() -> LintSettings {
     }
 }
 
+// The name a lint is known by in attributes and on the command line
+pub fn lint_to_str(lint: lint) -> ~str {
+    for get_lint_dict().each |&k, &v| {
+        if v.lint == lint {
+            return copy *k;
+        }
+    }
+    fail!(~"lint missing from the lint dictionary")
+}
+
 pub fn get_lint_level(modes: LintModes, lint: lint) -> level {
     match modes.find(&(lint as uint)) {
       Some(&c) => c,
@@ -341,8 +379,8 @@ pub impl Context {
         }
     }
 
-    fn span_lint(&self, level: level, span: span, +msg: ~str) {
-        self.sess.span_lint_level(level, span, msg);
+    fn span_lint(&self, lint: lint, level: level, span: span, +msg: ~str) {
+        self.sess.span_lint_level(lint, level, span, msg);
     }
 
     /**
@@ -395,6 +433,7 @@ pub impl Context {
             match self.dict.find(&lintname) {
               None => {
                 self.span_lint(
+                    unrecognized_lint,
                     new_ctxt.get_level(unrecognized_lint),
                     meta.span,
                     fmt!("unknown `%s` attribute: `%s`",
@@ -405,6 +444,7 @@ pub impl Context {
                 if new_ctxt.get_level(lint.lint) == forbid &&
                     level != forbid {
                     self.span_lint(
+                        lint.lint,
                         forbid,
                         meta.span,
                         fmt!("%s(%s) overruled by outer forbid(%s)",
@@ -491,6 +531,8 @@ fn check_item(i: @ast::item, cx: ty::ctxt) {
     check_item_default_methods(cx, i);
     check_item_deprecated_mutable_fields(cx, i);
     check_item_deprecated_drop(cx, i);
+    check_item_unused_mut(cx, i);
+    check_item_unused_unsafe(cx, i);
 }
 
 // Take a visitor, and modify it so that it will not proceed past subitems.
@@ -876,6 +918,69 @@ fn check_item_path_statement(cx: ty::ctxt, it: @ast::item) {
     visit::visit_item(it, (), visit);
 }
 
+// Borrowck records the `mut` variables that are assigned to or mutably
+// borrowed; any other `mut` is unnecessary
+fn check_item_unused_mut(cx: ty::ctxt, it: @ast::item) {
+    fn check_pat(cx: ty::ctxt, it: @ast::item, p: @ast::pat) {
+        let mut used = false;
+        let mut bindings = 0;
+        do pat_util::pat_bindings(cx.def_map, p) |_, id, _, _| {
+            used = used || cx.used_mut_nodes.contains_key(&id);
+            bindings += 1;
+        }
+        if !used {
+            let msg = if bindings == 1 {
+                ~"variable does not need to be mutable"
+            } else {
+                ~"variables do not need to be mutable"
+            };
+            cx.sess.span_lint(unused_mut, p.id, it.id, p.span, msg);
+        }
+    }
+
+    let visit = item_stopping_visitor(
+        visit::mk_simple_visitor(@visit::SimpleVisitor {
+            visit_local: |l: @ast::local| {
+                if l.node.is_mutbl {
+                    check_pat(cx, it, l.node.pat);
+                }
+            },
+            visit_fn: |_fk, decl, _body, _span, _id| {
+                for decl.inputs.each |arg| {
+                    if arg.is_mutbl {
+                        check_pat(cx, it, arg.pat);
+                    }
+                }
+            },
+            .. *visit::default_simple_visitor()
+        }));
+    visit::visit_item(it, (), visit);
+}
+
+// Typeck records the `unsafe` blocks that an unsafe operation relies on. A
+// block inside an unsafe fn or another unsafe block is never recorded.
+fn check_item_unused_unsafe(cx: ty::ctxt, it: @ast::item) {
+    let visit = item_stopping_visitor(
+        visit::mk_simple_visitor(@visit::SimpleVisitor {
+            visit_expr: |e: @ast::expr| {
+                match e.node {
+                    ast::expr_block(ref blk)
+                            if blk.node.rules == ast::unsafe_blk => {
+                        let id = blk.node.id;
+                        if !cx.used_unsafe.contains_key(&id) {
+                            cx.sess.span_lint(unused_unsafe, id, it.id,
+                                              blk.span,
+                                              ~"unnecessary `unsafe` block");
+                        }
+                    }
+                    _ => ()
+                }
+            },
+            .. *visit::default_simple_visitor()
+        }));
+    visit::visit_item(it, (), visit);
+}
+
 fn check_item_non_camel_case_types(cx: ty::ctxt, it: @ast::item) {
     fn is_camel_case(cx: ty::ctxt, ident: ast::ident) -> bool {
         let ident = cx.sess.str_of(ident);
@@ -1039,7 +1144,240 @@ fn check_item_deprecated_modes(tcx: ty::ctxt, it: @ast::item) {
     }
 }
 
-pub fn check_crate(tcx: ty::ctxt, crate: @ast::crate) {
+/**
+ * Warn about private items that nothing uses. Starting from `main`, the
+ * public items, the methods of trait implementations, the types traits
+ * are implemented for, `extern` functions and items marked `#[test]`,
+ * `#[bench]`, `#[lang]`, `#[start]` or `#[no_mangle]`, follow every path
+ * resolved in the def map and every statically resolved method call.
+ * In a library, every method of an impl on a public type is a root too,
+ * since other crates can call it whatever its visibility. Private
+ * functions, constants, types, enum variants and methods that are not
+ * reached are reported.
+ */
+fn check_crate_dead_code(tcx: ty::ctxt, method_map: typeck::method_map,
+                         crate: @ast::crate) {
+    struct Candidate {
+        id: ast::node_id,
+        // The item whose lint settings apply
+        item_id: ast::node_id,
+        span: span,
+        kind: &'static str,
+        name: ast::ident
+    }
+
+    fn is_root_item(tcx: ty::ctxt, id: ast::node_id,
+                    attrs: &[ast::attribute]) -> bool {
+        let metas = attr::attr_metas(attrs);
+        let is_main = match *tcx.sess.main_fn {
+            Some((main_id, _)) => main_id == id,
+            None => false
+        };
+        is_main ||
+            attr::contains_name(metas, ~"test") ||
+            attr::contains_name(metas, ~"bench") ||
+            attr::contains_name(metas, ~"lang") ||
+            attr::contains_name(metas, ~"start") ||
+            attr::contains_name(metas, ~"no_mangle")
+    }
+
+    // Whether other crates can name `ty`, the self type of an impl
+    fn is_exported_type(tcx: ty::ctxt, ty: @ast::Ty) -> bool {
+        let did = match ty.node {
+            ast::ty_path(_, id) => match tcx.def_map.find(&id) {
+                Some(ast::def_ty(did)) | Some(ast::def_struct(did)) => did,
+                _ => return true
+            },
+            _ => return true
+        };
+        if did.crate != ast::local_crate { return true; }
+        match tcx.items.find(&did.node) {
+            Some(ast_map::node_item(i, _)) => i.vis == ast::public,
+            _ => true
+        }
+    }
+
+    let worklist = @mut ~[];
+    let candidates = @mut ~[];
+
+    // Sort the items into roots and candidates
+    let collect = visit::mk_simple_visitor(@visit::SimpleVisitor {
+        visit_item: |i| {
+            // The injected intrinsic module isn't the user's code
+            let file = tcx.sess.codemap.lookup_char_pos(i.span.lo).file;
+            if file.name != ~"<intrinsic>" {
+                let public = i.vis == ast::public;
+                let is_extern = match i.node {
+                    ast::item_fn(_, ast::extern_fn, _, _) => true,
+                    _ => false
+                };
+                let root = public || is_extern ||
+                    is_root_item(tcx, i.id, i.attrs);
+                let kind = match i.node {
+                    ast::item_fn(*) => Some("function"),
+                    ast::item_const(*) => Some("constant"),
+                    ast::item_ty(*) => Some("type"),
+                    ast::item_enum(*) => Some("enum"),
+                    ast::item_struct(*) => Some("struct"),
+                    _ => None
+                };
+                for kind.each |&kind| {
+                    if root {
+                        worklist.push(i.id);
+                    } else {
+                        candidates.push(Candidate {
+                            id: i.id, item_id: i.id, span: i.span,
+                            kind: kind, name: i.ident
+                        });
+                    }
+                }
+
+                match i.node {
+                    ast::item_enum(ref enum_def, _) => {
+                        for enum_def.variants.each |v| {
+                            let privacy =
+                                ast_util::variant_visibility_to_privacy(
+                                    v.node.vis, public);
+                            if privacy == ast_util::Private {
+                                candidates.push(Candidate {
+                                    id: v.node.id, item_id: i.id,
+                                    span: v.span, kind: "variant",
+                                    name: v.node.name
+                                });
+                            }
+                        }
+                    }
+                    ast::item_impl(_, trait_ref, self_ty, ref methods) => {
+                        // A type is used by the traits implemented for it,
+                        // and trait methods are called through the trait
+                        if trait_ref.is_some() {
+                            match self_ty.node {
+                                ast::ty_path(_, id) => {
+                                    mark_id(tcx, worklist, id)
+                                }
+                                _ => ()
+                            }
+                        }
+                        // Method privacy isn't checked across crates
+                        let exported = *tcx.sess.building_library &&
+                            is_exported_type(tcx, self_ty);
+                        for methods.each |m| {
+                            let public = trait_ref.is_some() || exported ||
+                                m.vis == ast::public ||
+                                (m.vis == ast::inherited && public);
+                            if public || is_root_item(tcx, m.id, m.attrs) {
+                                worklist.push(m.id);
+                            } else {
+                                candidates.push(Candidate {
+                                    id: m.id, item_id: i.id, span: m.span,
+                                    kind: "method", name: m.ident
+                                });
+                            }
+                        }
+                    }
+                    ast::item_trait(_, _, ref methods) => {
+                        for methods.each |m| {
+                            match *m {
+                                ast::provided(m) => worklist.push(m.id),
+                                ast::required(_) => ()
+                            }
+                        }
+                    }
+                    _ => ()
+                }
+            }
+        },
+        .. *visit::default_simple_visitor()
+    });
+    visit::visit_crate(*crate, (), collect);
+
+    // Everything a live node refers to is live, but the items nested in it
+    // are not: they are roots or candidates themselves
+    fn mark_id(tcx: ty::ctxt, worklist: @mut ~[ast::node_id],
+               id: ast::node_id) {
+        match tcx.def_map.find(&id) {
+            Some(ast::def_fn(did, _)) |
+            Some(ast::def_static_method(did, _, _)) |
+            Some(ast::def_const(did)) | Some(ast::def_ty(did)) |
+            Some(ast::def_struct(did)) | Some(ast::def_variant(_, did)) => {
+                if did.crate == ast::local_crate {
+                    worklist.push(did.node);
+                }
+            }
+            _ => ()
+        }
+    }
+    let simple = visit::mk_simple_visitor(@visit::SimpleVisitor {
+        visit_expr: |e| {
+            mark_id(tcx, worklist, e.id);
+            match method_map.find(&e.id) {
+                Some(typeck::method_map_entry {
+                    origin: typeck::method_static(did), _
+                }) => {
+                    if did.crate == ast::local_crate {
+                        worklist.push(did.node);
+                    }
+                }
+                _ => ()
+            }
+        },
+        visit_pat: |p| mark_id(tcx, worklist, p.id),
+        visit_ty: |t| {
+            match t.node {
+                ast::ty_path(_, id) => mark_id(tcx, worklist, id),
+                _ => ()
+            }
+        },
+        .. *visit::default_simple_visitor()
+    });
+    let v = visit::mk_vt(@visit::Visitor {
+        visit_item: |_i, _e, _v| { },
+        .. **simple
+    });
+
+    let live: HashMap<ast::node_id, ()> = HashMap();
+    while !worklist.is_empty() {
+        let id = worklist.pop();
+        if live.contains_key(&id) { loop; }
+        live.insert(id, ());
+
+        match tcx.items.find(&id) {
+            Some(ast_map::node_item(i, _)) => {
+                match i.node {
+                    ast::item_fn(*) | ast::item_const(*) | ast::item_ty(*) |
+                    ast::item_enum(*) | ast::item_struct(*) => {
+                        visit::visit_item(i, (), v);
+                    }
+                    _ => ()
+                }
+            }
+            Some(ast_map::node_method(m, _, _)) |
+            Some(ast_map::node_trait_method(@ast::provided(m), _, _)) => {
+                visit::visit_method_helper(m, (), v);
+            }
+            Some(ast_map::node_variant(_, enum_item, _)) => {
+                worklist.push(enum_item.id);
+            }
+            Some(ast_map::node_struct_ctor(_, struct_item, _)) => {
+                worklist.push(struct_item.id);
+            }
+            _ => ()
+        }
+    }
+
+    for candidates.each |c| {
+        let name = tcx.sess.str_of(c.name);
+        // Names starting with an underscore are unused on purpose
+        if !live.contains_key(&c.id) && !str::starts_with(*name, "_") {
+            tcx.sess.span_lint(dead_code, c.id, c.item_id, c.span,
+                               fmt!("%s is never used: `%s`", c.kind,
+                                    *name));
+        }
+    }
+}
+
+pub fn check_crate(tcx: ty::ctxt, method_map: typeck::method_map,
+                   crate: @ast::crate) {
     let v = visit::mk_simple_visitor(@visit::SimpleVisitor {
         visit_item: |it|
             check_item(it, tcx),
@@ -1049,6 +1387,8 @@ pub fn check_crate(tcx: ty::ctxt, crate: @ast::crate) {
     });
     visit::visit_crate(*crate, (), v);
 
+    check_crate_dead_code(tcx, method_map, crate);
+
     tcx.sess.abort_if_errors();
 }
 
@@ -101,10 +101,17 @@
  * - `no_ret_var`: a synthetic variable that is only 'read' from, the
  *   fallthrough node.  This allows us to detect functions where we fail
  *   to return explicitly.
+ *
+ * # Printing the graph
+ *
+ * With `--pretty=flowgraph=NAME`, the edges between live nodes that the
+ * propagation follows are recorded for the fns named `NAME` (or with node
+ * id `NAME`), and printed in Graphviz dot format.
  */
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::pat_util;
 use middle::ty;
 use middle::typeck;
@@ -112,9 +119,11 @@ use middle::moves;
 use util::ppaux::ty_to_str;
 
 use core::cmp;
+use core::int;
 use core::io::WriterUtil;
 use core::io;
 use core::ptr;
+use core::str;
 use core::to_str;
 use core::uint;
 use core::vec;
@@ -503,8 +512,13 @@ fn visit_fn(fk: &visit::fn_kind,
     };
 
     // compute liveness
-    let lsets = @Liveness(fn_maps, specials);
+    let flowgraph = is_flowgraph_target(self.tcx, fk, id);
+    let lsets = @Liveness(fn_maps, specials, flowgraph);
     let entry_ln = (*lsets).compute(decl, body);
+    if flowgraph {
+        io::print(lsets.flowgraph_to_dot(fk, id, entry_ln));
+        *self.tcx.sess.flowgraph_printed = true;
+    }
 
     // check for various error conditions
     let check_vt = visit::mk_vt(@visit::Visitor {
@@ -519,6 +533,27 @@ fn visit_fn(fk: &visit::fn_kind,
     lsets.warn_about_unused_args(decl, entry_ln);
 }
 
+// Whether --pretty=flowgraph asked for the graph of this fn
+fn is_flowgraph_target(tcx: ty::ctxt, fk: &visit::fn_kind,
+                       id: node_id) -> bool {
+    match *tcx.sess.flowgraph_fn {
+      None => false,
+      Some(ref target) => {
+        match int::from_str(*target) {
+          Some(target_id) => target_id == id,
+          None => {
+            match *fk {
+              fk_item_fn(name, _, _) | fk_method(name, _, _) => {
+                *tcx.sess.str_of(name) == *target
+              }
+              fk_anon(*) | fk_fn_block(*) | fk_dtor(*) => false
+            }
+          }
+        }
+      }
+    }
+}
+
 fn visit_local(local: @local, &&self: @mut IrMaps, vt: vt<@mut IrMaps>) {
     let def_map = self.tcx.def_map;
     do pat_util::pat_bindings(def_map, local.node.pat) |_bm, p_id, sp, path| {
@@ -672,10 +707,15 @@ struct Liveness {
     // ("break" label should map to loop node ID,
     // it probably doesn't now)
     break_ln: LiveNodeMap,
-    cont_ln: LiveNodeMap
+    cont_ln: LiveNodeMap,
+    // The edges from each node to its successors, only recorded when
+    // printing the flowgraph
+    record_edges: bool,
+    edges: @mut ~[(LiveNode, LiveNode)]
 }
 
-fn Liveness(ir: @mut IrMaps, specials: Specials) -> Liveness {
+fn Liveness(ir: @mut IrMaps, specials: Specials,
+            record_edges: bool) -> Liveness {
     Liveness {
         ir: ir,
         tcx: ir.tcx,
@@ -685,7 +725,9 @@ fn Liveness(ir: @mut IrMaps, specials: Specials) -> Liveness {
                                    invalid_users()),
         loop_scope: @mut ~[],
         break_ln: HashMap(),
-        cont_ln: HashMap()
+        cont_ln: HashMap(),
+        record_edges: record_edges,
+        edges: @mut ~[]
     }
 }
 
@@ -886,8 +928,48 @@ pub impl Liveness {
         }
     }
 
+    fn add_edge(&self, ln: LiveNode, succ_ln: LiveNode) {
+        if self.record_edges && !self.edges.contains(&(ln, succ_ln)) {
+            self.edges.push((ln, succ_ln));
+        }
+    }
+
+    // The live nodes and the edges between them as a Graphviz digraph
+    fn flowgraph_to_dot(&self, fk: &visit::fn_kind, id: node_id,
+                        entry_ln: LiveNode) -> ~str {
+        fn escape(s: &str) -> ~str {
+            str::replace(str::replace(s, "\\", "\\\\"), "\"", "\\\"")
+        }
+
+        let name = self.tcx.sess.str_of(visit::name_of_fn(fk));
+        do io::with_str_writer |wr| {
+            wr.write_line(fmt!("digraph liveness_%d {", id));
+            wr.write_line(fmt!("    label=\"%s (node %d)\";",
+                               escape(*name), id));
+            for uint::range(0u, self.ir.num_live_nodes) |ln_idx| {
+                let ln = LiveNode(ln_idx);
+                let kind = if ln == entry_ln {
+                    ~"Entry node"
+                } else if ln == self.s.exit_ln {
+                    ~"Exit node"
+                } else if ln == self.s.fallthrough_ln {
+                    ~"Fallthrough node"
+                } else {
+                    live_node_kind_to_str(self.ir.lnk(ln), self.tcx)
+                };
+                wr.write_line(fmt!("    N%u [label=\"%s: %s\"];", ln_idx,
+                                   ln.to_str(), escape(kind)));
+            }
+            for self.edges.each |&(ln, succ_ln)| {
+                wr.write_line(fmt!("    N%u -> N%u;", *ln, *succ_ln));
+            }
+            wr.write_line(~"}");
+        }
+    }
+
     fn init_empty(&self, ln: LiveNode, succ_ln: LiveNode) {
         self.successors[*ln] = succ_ln;
+        self.add_edge(ln, succ_ln);
 
         // It is not necessary to initialize the
         // values to empty because this is the value
@@ -902,6 +984,7 @@ pub impl Liveness {
     fn init_from_succ(&self, ln: LiveNode, succ_ln: LiveNode) {
         // more efficient version of init_empty() / merge_from_succ()
         self.successors[*ln] = succ_ln;
+        self.add_edge(ln, succ_ln);
         self.indices2(ln, succ_ln, |idx, succ_idx| {
             self.users[idx] = self.users[succ_idx]
         });
@@ -912,6 +995,7 @@ pub impl Liveness {
     fn merge_from_succ(&self, ln: LiveNode, succ_ln: LiveNode,
                        first_merge: bool) -> bool {
         if ln == succ_ln { return false; }
+        self.add_edge(ln, succ_ln);
 
         let mut changed = false;
         do self.indices2(ln, succ_ln) |idx, succ_idx| {
@@ -1677,11 +1761,13 @@ pub impl Liveness {
                 // for nil return types, it is ok to not return a value expl.
             } else if ty::type_is_bot(t_ret) {
                 // for bot return types, not ok.  Function should fail.
-                self.tcx.sess.span_err(
-                    sp, ~"some control paths may return");
+                self.tcx.sess.span_err_with_code(
+                    sp, ~"some control paths may return",
+                    diagnostics::missing_return_value);
             } else {
-                self.tcx.sess.span_err(
-                    sp, ~"not all control paths return a value");
+                self.tcx.sess.span_err_with_code(
+                    sp, ~"not all control paths return a value",
+                    diagnostics::missing_return_value);
             }
         }
     }
@@ -1763,9 +1849,10 @@ pub impl Liveness {
                               orig_span: span) {
         match self.assigned_on_exit(ln, var) {
           Some(ExprNode(span)) => {
-            self.tcx.sess.span_err(
+            self.tcx.sess.span_err_with_code(
                 span,
-                ~"re-assignment of immutable variable");
+                ~"re-assignment of immutable variable",
+                diagnostics::assign_to_immutable);
 
             self.tcx.sess.span_note(
                 orig_span,
@@ -1792,10 +1879,11 @@ pub impl Liveness {
             let vk = self.ir.var_kinds[*var];
             match vk {
               Arg(_, name, _) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     move_expr.span,
                     fmt!("illegal move from argument `%s`, which is not \
-                          copy or move mode", *self.tcx.sess.str_of(name)));
+                          copy or move mode", *self.tcx.sess.str_of(name)),
+                    diagnostics::illegal_move);
                 return;
               }
               Local(*) | ImplicitRet => {
@@ -1873,14 +1961,16 @@ pub impl Liveness {
         let name = self.ir.variable_name(var);
         match lnk {
           FreeVarNode(span) => {
-            self.tcx.sess.span_err(
+            self.tcx.sess.span_err_with_code(
                 span,
-                fmt!("capture of %s: `%s`", msg, *name));
+                fmt!("capture of %s: `%s`", msg, *name),
+                diagnostics::use_of_moved_value);
           }
           ExprNode(span) => {
-            self.tcx.sess.span_err(
+            self.tcx.sess.span_err_with_code(
                 span,
-                fmt!("use of %s: `%s`", msg, *name));
+                fmt!("use of %s: `%s`", msg, *name),
+                diagnostics::use_of_moved_value);
           }
           ExitNode | VarDefNode(_) => {
             self.tcx.sess.span_bug(
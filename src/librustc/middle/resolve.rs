@@ -10,6 +10,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use driver::session;
 use driver::session::Session;
 use metadata::csearch::{each_path, get_method_names_if_trait};
@@ -18,7 +19,7 @@ use metadata::csearch::{get_type_name_if_impl};
 use metadata::cstore::find_extern_mod_stmt_cnum;
 use metadata::decoder::{def_like, dl_def, dl_field, dl_impl};
 use middle::lang_items::LanguageItems;
-use middle::lint::{deny, allow, forbid, level, unused_imports, warn};
+use middle::lint::{allow, level, unused_imports};
 use middle::lint::{get_lint_level, get_lint_settings_level};
 use middle::pat_util::{pat_bindings};
 
@@ -1013,10 +1014,11 @@ pub impl Resolver {
                     // had the duplicate.
                     let ns = namespace_for_duplicate_checking_mode(
                         duplicate_checking_mode);
-                    self.session.span_err(sp,
+                    self.session.span_err_with_code(sp,
                         fmt!("duplicate definition of %s %s",
                              namespace_to_str(ns),
-                             *self.session.str_of(name)));
+                             *self.session.str_of(name)),
+                        diagnostics::duplicate_definition);
                     for child.span_for_namespace(ns).each |sp| {
                         self.session.span_note(*sp,
                              fmt!("first definition of %s %s here:",
@@ -1938,7 +1940,9 @@ pub impl Resolver {
             }
 
             if self.unresolved_imports == prev_unresolved_imports {
-                self.session.err(~"failed to resolve imports");
+                self.session.err_with_code(
+                    ~"failed to resolve imports",
+                    diagnostics::unresolved_import);
                 self.report_unresolved_imports(module_root);
                 break;
             }
@@ -1992,7 +1996,9 @@ pub impl Resolver {
                                    *self.import_path_to_str(
                                        import_directive.module_path,
                                        *import_directive.subclass));
-                    self.session.span_err(import_directive.span, msg);
+                    self.session.span_err_with_code(
+                        import_directive.span, msg,
+                        diagnostics::unresolved_import);
                 }
                 Indeterminate => {
                     // Bail out. We'll come around next time.
@@ -2600,7 +2606,10 @@ pub impl Resolver {
                                               TypeNS,
                                               name_search_type) {
                 Failed => {
-                    self.session.span_err(span, ~"unresolved name");
+                    self.session.span_err_with_code(
+                        span,
+                        ~"unresolved name",
+                        diagnostics::unresolved_name);
                     return Failed;
                 }
                 Indeterminate => {
@@ -2617,12 +2626,11 @@ pub impl Resolver {
                             match type_def.module_def {
                                 None => {
                                     // Not a module.
-                                    self.session.span_err(span,
-                                                          fmt!("not a \
-                                                                module: %s",
-                                                               *self.session.
-                                                                   str_of(
-                                                                    name)));
+                                    self.session.span_err_with_code(
+                                        span,
+                                        fmt!("not a module: %s",
+                                             *self.session.str_of(name)),
+                                        diagnostics::not_a_module);
                                     return Failed;
                                 }
                                 Some(copy module_def) => {
@@ -2632,10 +2640,11 @@ pub impl Resolver {
                         }
                         None => {
                             // There are no type bindings at all.
-                            self.session.span_err(span,
-                                                  fmt!("not a module: %s",
-                                                       *self.session.str_of(
-                                                            name)));
+                            self.session.span_err_with_code(
+                                span,
+                                fmt!("not a module: %s",
+                                     *self.session.str_of(name)),
+                                diagnostics::not_a_module);
                             return Failed;
                         }
                     }
@@ -2685,7 +2694,8 @@ pub impl Resolver {
         let mut start_index;
         match module_prefix_result {
             Failed => {
-                self.session.span_err(span, ~"unresolved name");
+                self.session.span_err_with_code(
+                    span, ~"unresolved name", diagnostics::unresolved_name);
                 return Failed;
             }
             Indeterminate => {
@@ -2713,8 +2723,10 @@ pub impl Resolver {
                             module_path[0]);
                         match result {
                             Failed => {
-                                self.session.span_err(span,
-                                                      ~"unresolved name");
+                                self.session.span_err_with_code(
+                                    span,
+                                    ~"unresolved name",
+                                    diagnostics::unresolved_name);
                                 return Failed;
                             }
                             Indeterminate => {
@@ -3072,7 +3084,9 @@ pub impl Resolver {
         let imports: &mut ~[@ImportDirective] = &mut *module_.imports;
         let import_count = imports.len();
         if index != import_count {
-            self.session.span_err(imports[index].span, ~"unresolved import");
+            self.session.span_err_with_code(imports[index].span,
+                                            ~"unresolved import",
+                                            diagnostics::unresolved_import);
         }
 
         // Descend into children and anonymous children.
@@ -3347,16 +3361,18 @@ pub impl Resolver {
                         // named function item. This is not allowed, so we
                         // report an error.
 
-                        self.session.span_err(
+                        self.session.span_err_with_code(
                             span,
-                            ~"attempted dynamic environment-capture");
+                            ~"attempted dynamic environment-capture",
+                            diagnostics::dynamic_environment_capture);
                     } else {
                         // This was an attempt to use a type parameter outside
                         // its scope.
 
-                        self.session.span_err(span,
-                                              ~"attempt to use a type \
-                                               argument out of scope");
+                        self.session.span_err_with_code(
+                            span,
+                            ~"attempt to use a type argument out of scope",
+                            diagnostics::type_param_out_of_scope);
                     }
 
                     return None;
@@ -3369,25 +3385,29 @@ pub impl Resolver {
                         // named function item. This is not allowed, so we
                         // report an error.
 
-                        self.session.span_err(
+                        self.session.span_err_with_code(
                             span,
-                            ~"attempted dynamic environment-capture");
+                            ~"attempted dynamic environment-capture",
+                            diagnostics::dynamic_environment_capture);
                     } else {
                         // This was an attempt to use a type parameter outside
                         // its scope.
 
-                        self.session.span_err(span,
-                                              ~"attempt to use a type \
-                                               argument out of scope");
+                        self.session.span_err_with_code(
+                            span,
+                            ~"attempt to use a type argument out of scope",
+                            diagnostics::type_param_out_of_scope);
                     }
 
                     return None;
                 }
                 ConstantItemRibKind => {
                     // Still doesn't deal with upvars
-                    self.session.span_err(span,
-                                          ~"attempt to use a non-constant \
-                                            value in a constant");
+                    self.session.span_err_with_code(
+                        span,
+                        ~"attempt to use a non-constant value in a \
+                          constant",
+                        diagnostics::non_constant_in_constant);
 
                 }
             }
@@ -3524,9 +3544,10 @@ pub impl Resolver {
                         match self.resolve_path(trt.path, TypeNS, true,
                                                 visitor) {
                             None =>
-                                self.session.span_err(trt.path.span,
-                                                      ~"attempt to derive a \
-                                                       nonexistent trait"),
+                                self.session.span_err_with_code(
+                                    trt.path.span,
+                                    ~"attempt to derive a nonexistent trait",
+                                    diagnostics::unknown_trait),
                             Some(def) => {
                                 // Write a mapping from the trait ID to the
                                 // definition of the trait into the definition
@@ -3619,9 +3640,12 @@ pub impl Resolver {
 
             item_fn(ref fn_decl, _, ref generics, ref block) => {
                 // If this is the main function, we must record it in the
-                // session.
+                // session. A static library runs it from
+                // rust_staticlib_main.
                 // FIXME #4404 android JNI hacks
                 if !*self.session.building_library ||
+                    self.session.opts.crate_type ==
+                        session::staticlib_crate ||
                     self.session.targ_cfg.os == session::os_android {
 
                     if self.attr_main_fn.is_none() &&
@@ -3634,9 +3658,10 @@ pub impl Resolver {
                         if self.attr_main_fn.is_none() {
                             self.attr_main_fn = Some((item.id, item.span));
                         } else {
-                            self.session.span_err(
+                            self.session.span_err_with_code(
                                     item.span,
-                                    ~"multiple 'main' functions");
+                                    ~"multiple 'main' functions",
+                                    diagnostics::multiple_main_fns);
                         }
                     }
                 }
@@ -3903,9 +3928,10 @@ pub impl Resolver {
                     match self.resolve_path(
                         trait_reference.path, TypeNS, true, visitor) {
                         None => {
-                            self.session.span_err(span,
-                                                  ~"attempt to implement an \
-                                                    unknown trait");
+                            self.session.span_err_with_code(
+                                span,
+                                ~"attempt to implement an unknown trait",
+                                diagnostics::unknown_trait);
                         }
                         Some(def) => {
                             self.record_def(trait_reference.ref_id, def);
@@ -4013,19 +4039,21 @@ pub impl Resolver {
             for map_0.each |&key, &binding_0| {
                 match map_i.find(&key) {
                   None => {
-                    self.session.span_err(
+                    self.session.span_err_with_code(
                         p.span,
                         fmt!("variable `%s` from pattern #1 is \
                                   not bound in pattern #%u",
-                             *self.session.str_of(key), i + 1));
+                             *self.session.str_of(key), i + 1),
+                        diagnostics::inconsistent_bindings);
                   }
                   Some(binding_i) => {
                     if binding_0.binding_mode != binding_i.binding_mode {
-                        self.session.span_err(
+                        self.session.span_err_with_code(
                             binding_i.span,
                             fmt!("variable `%s` is bound with different \
                                       mode in pattern #%u than in pattern #1",
-                                 *self.session.str_of(key), i + 1));
+                                 *self.session.str_of(key), i + 1),
+                            diagnostics::inconsistent_bindings);
                     }
                   }
                 }
@@ -4033,11 +4061,12 @@ pub impl Resolver {
 
             for map_i.each |&key, &binding| {
                 if !map_0.contains_key(&key) {
-                    self.session.span_err(
+                    self.session.span_err_with_code(
                         binding.span,
                         fmt!("variable `%s` from pattern #%u is \
                                   not bound in pattern #1",
-                             *self.session.str_of(key), i + 1));
+                             *self.session.str_of(key), i + 1),
+                        diagnostics::inconsistent_bindings);
                 }
             }
         }
@@ -4146,9 +4175,10 @@ pub impl Resolver {
                         self.record_def(path_id, def);
                     }
                     None => {
-                        self.session.span_err
+                        self.session.span_err_with_code
                             (ty.span, fmt!("use of undeclared type name `%s`",
-                                           self.idents_to_str(path.idents)));
+                                           self.idents_to_str(path.idents)),
+                             diagnostics::unresolved_name);
                     }
                 }
             }
@@ -4199,13 +4229,13 @@ pub impl Resolver {
                             self.record_def(pattern.id, def);
                         }
                         FoundStructOrEnumVariant(_) => {
-                            self.session.span_err(pattern.span,
-                                                  fmt!("declaration of `%s` \
-                                                        shadows an enum \
-                                                        variant or unit-like \
-                                                        struct in scope",
-                                                        *self.session
-                                                            .str_of(ident)));
+                            self.session.span_err_with_code(
+                                pattern.span,
+                                fmt!("declaration of `%s` shadows an enum \
+                                      variant or unit-like struct in \
+                                      scope",
+                                      *self.session.str_of(ident)),
+                                diagnostics::shadowed_variant);
                         }
                         FoundConst(def) if mode == RefutableMode => {
                             debug!("(resolving pattern) resolving `%s` to \
@@ -4219,9 +4249,10 @@ pub impl Resolver {
                             self.record_def(pattern.id, def);
                         }
                         FoundConst(_) => {
-                            self.session.span_err(pattern.span,
-                                                  ~"only refutable patterns \
-                                                    allowed here");
+                            self.session.span_err_with_code(
+                                pattern.span,
+                                ~"only refutable patterns allowed here",
+                                diagnostics::constant_in_irrefutable_pattern);
                         }
                         BareIdentifierPatternUnresolved => {
                             debug!("(resolving pattern) binding `%s`",
@@ -4274,11 +4305,15 @@ pub impl Resolver {
                                       // Then this is a duplicate variable
                                       // in the same disjunct, which is an
                                       // error
-                                     self.session.span_err(pattern.span,
-                                       fmt!("Identifier %s is bound more \
-                                             than once in the same pattern",
-                                            path_to_str(path, self.session
-                                                        .intr())));
+                                     self.session.span_err_with_code(
+                                         pattern.span,
+                                         fmt!("Identifier %s is bound \
+                                               more than once in the \
+                                               same pattern",
+                                              path_to_str(
+                                                  path,
+                                                  self.session.intr())),
+                                         diagnostics::duplicate_binding);
                                   }
                                   // Not bound in the same pattern: do nothing
                                 }
@@ -4314,15 +4349,18 @@ pub impl Resolver {
                             self.record_def(pattern.id, def);
                         }
                         Some(_) => {
-                            self.session.span_err(
+                            self.session.span_err_with_code(
                                 path.span,
                                 fmt!("not an enum variant or constant: %s",
                                      *self.session.str_of(
-                                         *path.idents.last())));
+                                         *path.idents.last())),
+                                diagnostics::unresolved_enum_variant);
                         }
                         None => {
-                            self.session.span_err(path.span,
-                                                  ~"unresolved enum variant");
+                            self.session.span_err_with_code(
+                                path.span,
+                                ~"unresolved enum variant",
+                                diagnostics::unresolved_enum_variant);
                         }
                     }
 
@@ -4340,16 +4378,18 @@ pub impl Resolver {
                             self.record_def(pattern.id, def);
                         }
                         Some(_) => {
-                            self.session.span_err(
+                            self.session.span_err_with_code(
                                 path.span,
                                 fmt!("not an enum variant or struct: %s",
                                      *self.session.str_of(
-                                         *path.idents.last())));
+                                         *path.idents.last())),
+                                diagnostics::unresolved_enum_variant);
                         }
                         None => {
-                            self.session.span_err(path.span,
-                                                  ~"unresolved enum variant \
-                                                    or struct");
+                            self.session.span_err_with_code(
+                                path.span,
+                                ~"unresolved enum variant or struct",
+                                diagnostics::unresolved_enum_variant);
                         }
                     }
 
@@ -4389,10 +4429,11 @@ pub impl Resolver {
                         result => {
                             debug!("(resolving pattern) didn't find struct \
                                     def: %?", result);
-                            self.session.span_err(
+                            self.session.span_err_with_code(
                                 path.span,
                                 fmt!("`%s` does not name a structure",
-                                     self.idents_to_str(path.idents)));
+                                     self.idents_to_str(path.idents)),
+                                diagnostics::not_a_struct);
                         }
                     }
                 }
@@ -4585,10 +4626,11 @@ pub impl Resolver {
                                                   UseLexicalScope,
                                                   path.span) {
             Failed => {
-                self.session.span_err(path.span,
-                                      fmt!("use of undeclared module `%s`",
-                                           self.idents_to_str(
-                                               module_path_idents)));
+                self.session.span_err_with_code(
+                    path.span,
+                    fmt!("use of undeclared module `%s`",
+                         self.idents_to_str(module_path_idents)),
+                    diagnostics::undeclared_module);
                 return None;
             }
 
@@ -4634,10 +4676,11 @@ pub impl Resolver {
                                                  path.span,
                                                  SearchItemsAndAllImports) {
             Failed => {
-                self.session.span_err(path.span,
-                                      fmt!("use of undeclared module `::%s`",
-                                            self.idents_to_str(
-                                              module_path_idents)));
+                self.session.span_err_with_code(
+                    path.span,
+                    fmt!("use of undeclared module `::%s`",
+                         self.idents_to_str(module_path_idents)),
+                    diagnostics::undeclared_module);
                 return None;
             }
 
@@ -4665,6 +4708,60 @@ pub impl Resolver {
         }
     }
 
+    /**
+     * Resolves `path` as if it were written in the module that
+     * `module_path` names, looking for its last name in `namespace`, and
+     * without reporting errors. This is for rustdoc, which links the paths
+     * written in doc comments. Must be called after resolution.
+     */
+    fn resolve_doc_path(@mut self,
+                        module_path: &[ident],
+                        path: &[ident],
+                        namespace: Namespace)
+                     -> Option<def> {
+        let mut module_ = self.graph_root.get_module();
+        for module_path.each |name| {
+            match self.resolve_name_in_module(module_, *name, TypeNS,
+                                              SearchItemsAndAllImports) {
+                Success(target) => {
+                    match target.bindings.get_module_if_available() {
+                        Some(module_def) => module_ = module_def,
+                        None => return None
+                    }
+                }
+                Failed | Indeterminate => return None
+            }
+        }
+
+        // The first name is looked for in the scope of the module, and the
+        // rest in the modules before them
+        let last = path.len() - 1;
+        for path.eachi |i, name| {
+            let name_namespace = if i == last { namespace } else { TypeNS };
+            let result = if i == 0 {
+                self.resolve_item_in_lexical_scope(module_, *name,
+                                                   name_namespace,
+                                                   SearchThroughModules)
+            } else {
+                self.resolve_name_in_module(module_, *name, name_namespace,
+                                            SearchItemsAndPublicImports)
+            };
+            match result {
+                Success(target) if i == last => {
+                    return target.bindings.def_for_namespace(namespace);
+                }
+                Success(target) => {
+                    match target.bindings.get_module_if_available() {
+                        Some(module_def) => module_ = module_def,
+                        None => return None
+                    }
+                }
+                Failed | Indeterminate => return None
+            }
+        }
+        None
+    }
+
     fn resolve_identifier_in_local_ribs(@mut self,
                                         ident: ident,
                                         namespace: Namespace,
@@ -4828,25 +4925,30 @@ pub impl Resolver {
                         let wrong_name = self.idents_to_str(
                             path.idents);
                         if self.name_exists_in_scope_struct(wrong_name) {
-                            self.session.span_err(expr.span,
+                            self.session.span_err_with_code(expr.span,
                                         fmt!("unresolved name: `%s`. \
                                             Did you mean: `self.%s`?",
                                         wrong_name,
-                                        wrong_name));
+                                        wrong_name),
+                                        diagnostics::unresolved_name);
                         }
                         else {
                             match self.find_best_match_for_name(wrong_name) {
 
                                 Some(m) => {
-                                    self.session.span_err(expr.span,
+                                    self.session.span_err_with_code(
+                                            expr.span,
                                             fmt!("unresolved name: `%s`. \
                                                 Did you mean: `%s`?",
-                                                wrong_name, m));
+                                                wrong_name, m),
+                                            diagnostics::unresolved_name);
                                 }
                                 None => {
-                                    self.session.span_err(expr.span,
+                                    self.session.span_err_with_code(
+                                            expr.span,
                                             fmt!("unresolved name: `%s`.",
-                                                wrong_name));
+                                                wrong_name),
+                                            diagnostics::unresolved_name);
                                 }
                             }
                         }
@@ -4878,10 +4980,11 @@ pub impl Resolver {
                         self.record_def(expr.id, definition);
                     }
                     _ => {
-                        self.session.span_err(
+                        self.session.span_err_with_code(
                             path.span,
                             fmt!("`%s` does not name a structure",
-                                 self.idents_to_str(path.idents)));
+                                 self.idents_to_str(path.idents)),
+                            diagnostics::not_a_struct);
                     }
                 }
 
@@ -4903,11 +5006,11 @@ pub impl Resolver {
                 match self.search_ribs(&mut self.label_ribs, label, expr.span,
                                        DontAllowCapturingSelf) {
                     None =>
-                        self.session.span_err(expr.span,
-                                              fmt!("use of undeclared label \
-                                                   `%s`",
-                                                   *self.session.str_of(
-                                                       label))),
+                        self.session.span_err_with_code(
+                            expr.span,
+                            fmt!("use of undeclared label `%s`",
+                                 *self.session.str_of(label)),
+                            diagnostics::undeclared_label),
                     Some(dl_def(def @ def_label(_))) =>
                         self.record_def(expr.id, def),
                     Some(_) =>
@@ -5138,16 +5241,18 @@ pub impl Resolver {
         match pat_binding_mode {
             bind_infer => {}
             bind_by_copy => {
-                self.session.span_err(
+                self.session.span_err_with_code(
                     pat.span,
                     fmt!("cannot use `copy` binding mode with %s",
-                         descr));
+                         descr),
+                    diagnostics::illegal_binding_mode);
             }
             bind_by_ref(*) => {
-                self.session.span_err(
+                self.session.span_err_with_code(
                     pat.span,
                     fmt!("cannot use `ref` binding mode with %s",
-                         descr));
+                         descr),
+                    diagnostics::illegal_binding_mode);
             }
         }
     }
@@ -5164,9 +5269,10 @@ pub impl Resolver {
                 let mut i = 1u;
                 while i < this.main_fns.len() {
                     let (_, dup_main_span) = option::unwrap(this.main_fns[i]);
-                    this.session.span_err(
+                    this.session.span_err_with_code(
                         dup_main_span,
-                        ~"multiple 'main' functions");
+                        ~"multiple 'main' functions",
+                        diagnostics::multiple_main_fns);
                     i += 1;
                 }
                 *this.session.main_fn = this.main_fns[0];
@@ -5253,17 +5359,10 @@ pub impl Resolver {
                     import_resolution.span != dummy_sp() &&
                     import_resolution.privacy != Public {
                 import_resolution.state.warned = true;
-                match self.unused_import_lint_level(module_) {
-                    warn => {
-                        self.session.span_warn(copy import_resolution.span,
-                                               ~"unused import");
-                    }
-                    deny | forbid => {
-                      self.session.span_err(copy import_resolution.span,
-                                            ~"unused import");
-                    }
-                    allow => ()
-                }
+                let level = self.unused_import_lint_level(module_);
+                self.session.span_lint_level(unused_imports, level,
+                                             copy import_resolution.span,
+                                             ~"unused import");
             }
         }
     }
@@ -66,11 +66,13 @@ use util::common::indenter;
 use util::ppaux::{ty_to_str, ty_to_short_str};
 use util::ppaux;
 
+use core::flate;
 use core::hash;
 use core::hashmap::linear::LinearMap;
 use core::int;
 use core::io;
 use core::libc::{c_uint, c_ulonglong};
+use core::os;
 use core::uint;
 use std::oldmap::HashMap;
 use std::{oldmap, time, list};
@@ -2190,6 +2192,7 @@ pub fn register_fn_fuller(ccx: @CrateContext,
     // FIXME #4404 android JNI hacks
     let is_main = is_main_fn(&ccx.sess, node_id) &&
                      (!*ccx.sess.building_library ||
+                      ccx.sess.opts.crate_type == session::staticlib_crate ||
                       (*ccx.sess.building_library &&
                        ccx.sess.targ_cfg.os == session::os_android));
     if is_main { create_main_wrapper(ccx, sp, llfn); }
@@ -2240,8 +2243,14 @@ pub fn create_main_wrapper(ccx: @CrateContext,
         fn main_name() -> ~str { return ~"main"; }
         let llfty = T_fn(~[ccx.int_type, T_ptr(T_i8())], ccx.int_type);
 
+        // A static library is linked into a program with a main of its
+        // own, which starts the runtime by calling rust_staticlib_main
+        let staticlib = ccx.sess.opts.crate_type == session::staticlib_crate;
+
         // FIXME #4404 android JNI hacks
-        let llfn = if *ccx.sess.building_library {
+        let llfn = if staticlib {
+            decl_cdecl_fn(ccx.llmod, ~"rust_staticlib_main", llfty)
+        } else if *ccx.sess.building_library {
             decl_cdecl_fn(ccx.llmod, ~"amain", llfty)
         } else {
             decl_cdecl_fn(ccx.llmod, main_name(), llfty)
@@ -2275,7 +2284,7 @@ pub fn create_main_wrapper(ccx: @CrateContext,
             let opaque_crate_map = llvm::LLVMBuildPointerCast(
                 bld, crate_map, T_ptr(T_i8()), noname());
 
-            if *ccx.sess.building_library {
+            if *ccx.sess.building_library && !staticlib {
                 ~[
                     retptr,
                     C_null(T_opaque_box_ptr(ccx)),
@@ -2947,8 +2956,51 @@ pub fn crate_ctxt_to_encode_parms(cx: @CrateContext)
     }
 }
 
-pub fn write_metadata(cx: @CrateContext, crate: &ast::crate) {
+// Adds the module, as it stands, to a section of its own, for executables
+// built with --lto to link in; see back::lto
+pub fn embed_bitcode(cx: @CrateContext, output: &Path) -> ValueRef {
+    let path = output.with_filetype("lto.bc");
+    let bytes = unsafe {
+        let written = str::as_c_str(path.to_str(), |buf| {
+            llvm::LLVMWriteBitcodeToFile(cx.llmod, buf)
+        });
+        if written != 0 {
+            cx.sess.fatal(fmt!("could not write bitcode to %s",
+                               path.to_str()));
+        }
+        match io::read_whole_file(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => cx.sess.fatal(e)
+        }
+    };
+    os::remove_file(&path);
+
+    let llconst = C_struct(~[C_bytes(flate::deflate_bytes(bytes))]);
+    let llglobal = str::as_c_str(~"rust_bitcode", |buf| {
+        unsafe {
+            llvm::LLVMAddGlobal(cx.llmod, val_ty(llconst), buf)
+        }
+    });
+    unsafe {
+        llvm::LLVMSetInitializer(llglobal, llconst);
+        str::as_c_str(cx.sess.targ_cfg.target_strs.bc_sect_name, |buf| {
+            llvm::LLVMSetSection(llglobal, buf)
+        });
+        lib::llvm::SetLinkage(llglobal, lib::llvm::InternalLinkage);
+    }
+    llglobal
+}
+
+pub fn write_metadata(cx: @CrateContext, crate: &ast::crate,
+                      output: &Path) {
     if !*cx.sess.building_library { return; }
+    // The bitcode is taken before the metadata is added, so executables
+    // that link it in don't carry the metadata along
+    let llbitcode = if cx.sess.opts.lto {
+        Some(embed_bitcode(cx, output))
+    } else {
+        None
+    };
     let encode_parms = crate_ctxt_to_encode_parms(cx);
     let llmeta = C_bytes(encoder::encode_metadata(encode_parms, crate));
     let llconst = C_struct(~[llmeta]);
@@ -2966,18 +3018,26 @@ pub fn write_metadata(cx: @CrateContext, crate: &ast::crate) {
 
         let t_ptr_i8 = T_ptr(T_i8());
         llglobal = llvm::LLVMConstBitCast(llglobal, t_ptr_i8);
+        let mut used = ~[llglobal];
+        for llbitcode.each |&llbitcode| {
+            used.push(llvm::LLVMConstBitCast(llbitcode, t_ptr_i8));
+        }
         let llvm_used = str::as_c_str(~"llvm.used", |buf| {
-            llvm::LLVMAddGlobal(cx.llmod, T_array(t_ptr_i8, 1u), buf)
+            llvm::LLVMAddGlobal(cx.llmod, T_array(t_ptr_i8, used.len()),
+                                buf)
         });
         lib::llvm::SetLinkage(llvm_used, lib::llvm::AppendingLinkage);
-        llvm::LLVMSetInitializer(llvm_used, C_array(t_ptr_i8, ~[llglobal]));
+        llvm::LLVMSetInitializer(llvm_used, C_array(t_ptr_i8, used));
     }
 }
 
-// Writes the current ABI version into the crate.
+// Writes the current ABI version into the crate. Every crate defines it,
+// so it is weak: the runtime can still find it, and crates joined into one
+// object, as --staticlib does, don't clash over it.
 pub fn write_abi_version(ccx: @CrateContext) {
-    mk_global(ccx, ~"rust_abi_version", C_uint(ccx, abi::abi_version),
-                     false);
+    let llglobal = mk_global(ccx, ~"rust_abi_version",
+                             C_uint(ccx, abi::abi_version), false);
+    lib::llvm::SetLinkage(llglobal, lib::llvm::WeakODRLinkage);
 }
 
 pub fn trans_crate(sess: session::Session,
@@ -3089,7 +3149,8 @@ pub fn trans_crate(sess: session::Session,
                 n_closures: 0u,
                 llvm_insn_ctxt: @mut ~[],
                 llvm_insns: HashMap(),
-                fn_times: @mut ~[]
+                fn_times: @mut ~[],
+                mono_items: @mut ~[]
               },
               upcalls: upcall::declare_upcalls(targ_cfg, llmod),
               tydesc_type: tydesc_type,
@@ -3121,7 +3182,8 @@ pub fn trans_crate(sess: session::Session,
         write_abi_version(ccx);
 
         // Translate the metadata.
-        write_metadata(ccx, crate);
+        write_metadata(ccx, crate, output);
+        ccx.sess.count_in_pass(~"monomorphized fns", ccx.stats.n_monos);
         if ccx.sess.trans_stats() {
             io::println(~"--- trans stats ---");
             io::println(fmt!("n_static_tydescs: %u",
@@ -3142,6 +3204,10 @@ pub fn trans_crate(sess: session::Session,
                 io::println(fmt!("%-7u %s", v, k));
             }
         }
+
+        if ccx.sess.print_mono_items() {
+            io::print(monomorphize::mono_items_report(*ccx.stats.mono_items));
+        }
         return (llmod, link_meta);
     }
 }
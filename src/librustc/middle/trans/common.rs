@@ -135,7 +135,16 @@ pub struct Stats {
     n_closures: uint,
     llvm_insn_ctxt: @mut ~[~str],
     llvm_insns: HashMap<~str, uint>,
-    fn_times: @mut ~[(~str, int)] // (ident, time)
+    fn_times: @mut ~[(~str, int)], // (ident, time)
+    mono_items: @mut ~[MonoItem]
+}
+
+// An instance of a generic function, for -Z print-mono-items
+pub struct MonoItem {
+    path: ~str,
+    // The type arguments it was instantiated with
+    params: ~str,
+    llvm_insns: uint
 }
 
 pub struct BuilderRef_res {
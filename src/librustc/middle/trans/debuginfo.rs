@@ -41,7 +41,6 @@ const SubroutineTag: int = 21;
 const BasicTypeDescriptorTag: int = 36;
 const AutoVariableTag: int = 256;
 const ArgVariableTag: int = 257;
-const ReturnVariableTag: int = 258;
 const LexicalBlockTag: int = 11;
 const PointerTypeTag: int = 15;
 const StructureTypeTag: int = 19;
@@ -54,7 +53,6 @@ const DW_ATE_float: int = 0x04;
 const DW_ATE_signed: int = 0x05;
 const DW_ATE_signed_char: int = 0x06;
 const DW_ATE_unsigned: int = 0x07;
-const DW_ATE_unsigned_char: int = 0x08;
 
 fn llstr(s: &str) -> ValueRef {
     do str::as_c_str(s) |sbuf| {
@@ -12,7 +12,7 @@ use core::prelude::*;
 
 use back::link::mangle_exported_name;
 use driver::session;
-use lib::llvm::ValueRef;
+use lib::llvm::{ValueRef, llvm};
 use middle::trans::base::{get_insn_ctxt};
 use middle::trans::base::{set_inline_hint_if_appr, set_inline_hint};
 use middle::trans::base::{trans_enum_variant, trans_struct_dtor};
@@ -32,8 +32,11 @@ use middle::ty::{FnSig};
 use middle::typeck;
 use util::ppaux::ty_to_str;
 
+use core::hashmap::linear::LinearMap;
 use core::option;
+use core::str;
 use core::vec;
+use std::sort;
 use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_map::{path, path_mod, path_name};
@@ -263,10 +266,83 @@ pub fn monomorphic_fn(ccx: @CrateContext,
     };
     ccx.monomorphizing.insert(fn_id, depth);
 
+    if ccx.sess.print_mono_items() {
+        let params = psubsts.get().tys.map(|t| ty_to_str(ccx.tcx, *t));
+        ccx.stats.mono_items.push(MonoItem {
+            path: ty::item_path_str(ccx.tcx, fn_id),
+            params: ~"<" + str::connect(params, ", ") + ~">",
+            llvm_insns: count_insns(lldecl)
+        });
+    }
+
     debug!("leaving monomorphic fn %s", ty::item_path_str(ccx.tcx, fn_id));
     (lldecl, must_cast)
 }
 
+// The number of LLVM instructions in the body of `llfn`
+pub fn count_insns(llfn: ValueRef) -> uint {
+    unsafe {
+        let mut n = 0;
+        let mut bb = llvm::LLVMGetFirstBasicBlock(llfn);
+        while bb.is_not_null() {
+            let mut insn = llvm::LLVMGetFirstInstruction(bb);
+            while insn.is_not_null() {
+                n += 1;
+                insn = llvm::LLVMGetNextInstruction(insn);
+            }
+            bb = llvm::LLVMGetNextBasicBlock(bb);
+        }
+        n
+    }
+}
+
+/**
+ * Format the -Z print-mono-items report: each generic function that was
+ * instantiated, with the type arguments and size of each instance. The
+ * functions contributing the most LLVM instructions come first.
+ */
+pub fn mono_items_report(items: &[MonoItem]) -> ~str {
+    struct Generic {
+        path: ~str,
+        insns: uint,
+        instances: ~[(~str, uint)]
+    }
+
+    let mut by_path: LinearMap<~str, uint> = LinearMap::new();
+    let mut generics: ~[Generic] = ~[];
+    for items.each |item| {
+        match by_path.find(&item.path) {
+            Some(&i) => {
+                generics[i].insns += item.llvm_insns;
+                generics[i].instances.push((copy item.params,
+                                            item.llvm_insns));
+                loop;
+            }
+            None => ()
+        }
+        by_path.insert(copy item.path, generics.len());
+        generics.push(Generic {
+            path: copy item.path,
+            insns: item.llvm_insns,
+            instances: ~[(copy item.params, item.llvm_insns)]
+        });
+    }
+
+    let generics = sort::merge_sort(generics, |a, b| a.insns >= b.insns);
+    let mut s = ~"--- monomorphized items, by LLVM instructions ---\n";
+    for generics.each |g| {
+        s += fmt!("%-7u %s (%u instance%s)\n", g.insns, g.path,
+                  g.instances.len(),
+                  if g.instances.len() == 1 { "" } else { "s" });
+        let instances = sort::merge_sort(g.instances,
+                                         |a, b| a.second() >= b.second());
+        for instances.each |&(ref params, insns)| {
+            s += fmt!("    %-7u %s\n", insns, *params);
+        }
+    }
+    s
+}
+
 pub fn normalize_for_monomorphization(tcx: ty::ctxt,
                                       ty: ty::t) -> Option<ty::t> {
     // FIXME[mono] could do this recursively. is that worthwhile? (#2529)
@@ -400,3 +476,34 @@ pub fn make_mono_id(ccx: @CrateContext, item: ast::def_id, substs: &[ty::t],
     };
     @mono_id_ {def: item, params: param_ids, impl_did_opt: impl_did_opt}
 }
+
+#[cfg(test)]
+pub mod test {
+    use middle::trans::common::MonoItem;
+    use middle::trans::monomorphize::mono_items_report;
+
+    fn mono_item(path: &str, params: &str, llvm_insns: uint) -> MonoItem {
+        MonoItem {
+            path: path.to_owned(),
+            params: params.to_owned(),
+            llvm_insns: llvm_insns
+        }
+    }
+
+    #[test]
+    pub fn mono_items_report_sorts_by_total_insns() {
+        // b::g has the largest instance, but a::f the most instructions
+        let report = mono_items_report(~[
+            mono_item("b::g", "<uint>", 30),
+            mono_item("a::f", "<int>", 10),
+            mono_item("a::f", "<float>", 25)
+        ]);
+        fail_unless!(report ==
+                     ~"--- monomorphized items, by LLVM instructions ---\n\
+                       35      a::f (2 instances)\n\
+                       \x20   25      <float>\n\
+                       \x20   10      <int>\n\
+                       30      b::g (1 instance)\n\
+                       \x20   30      <uint>\n");
+    }
+}
@@ -301,7 +301,15 @@ struct ctxt_ {
     destructors: HashMap<ast::def_id, ()>,
 
     // Maps a trait onto a mapping from self-ty to impl
-    trait_impls: HashMap<ast::def_id, HashMap<t, @Impl>>
+    trait_impls: HashMap<ast::def_id, HashMap<t, @Impl>>,
+
+    // The `unsafe` blocks that contain an unsafe operation, filled in by
+    // typeck for the unused_unsafe lint
+    used_unsafe: HashMap<ast::node_id, ()>,
+
+    // The `mut` local variables and arguments that are assigned to or
+    // mutably borrowed, filled in by borrowck for the unused_mut lint
+    used_mut_nodes: HashMap<ast::node_id, ()>
 }
 
 enum tbox_flag {
@@ -852,7 +860,9 @@ pub fn mk_ctxt(s: session::Session,
         supertraits: HashMap(),
         destructor_for_type: HashMap(),
         destructors: HashMap(),
-        trait_impls: HashMap()
+        trait_impls: HashMap(),
+        used_unsafe: HashMap(),
+        used_mut_nodes: HashMap()
      }
 }
 
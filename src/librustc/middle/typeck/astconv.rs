@@ -54,6 +54,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::const_eval;
 use middle::ty::{arg, field, substs};
 use middle::ty::{ty_param_substs_and_ty};
@@ -92,10 +93,11 @@ pub fn get_region_reporting_err(
                 Some(a) => fmt!("lifetime %s",
                                 lifetime_to_str(a, tcx.sess.intr()))
             };
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 span,
                 fmt!("Illegal %s: %s",
-                     descr, e.msg));
+                     descr, e.msg),
+                diagnostics::illegal_lifetime);
             e.replacement
         }
     }
@@ -150,11 +152,12 @@ pub fn ast_path_to_substs_and_ty<AC:AstConv,RS:region_scope + Copy + Durable>(
         None
       }
       (None, Some(_)) => {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             path.span,
             fmt!("no region bound is allowed on `%s`, \
                   which is not declared as containing region pointers",
-                 ty::item_path_str(tcx, did)));
+                 ty::item_path_str(tcx, did)),
+            diagnostics::unexpected_lifetime_param);
         None
       }
       (Some(_), None) => {
@@ -169,10 +172,11 @@ pub fn ast_path_to_substs_and_ty<AC:AstConv,RS:region_scope + Copy + Durable>(
 
     // Convert the type parameters supplied by the user.
     if !vec::same_length(*decl_bounds, path.types) {
-        self.tcx().sess.span_fatal(
+        self.tcx().sess.span_fatal_with_code(
             path.span,
             fmt!("wrong number of type arguments: expected %u but found %u",
-                 (*decl_bounds).len(), path.types.len()));
+                 (*decl_bounds).len(), path.types.len()),
+            diagnostics::wrong_number_of_type_params);
     }
     let tps = path.types.map(|a_t| ast_ty_to_ty(self, rscope, *a_t));
 
@@ -257,12 +261,13 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
                                         ty::RegionTraitStore(r)
                                     }
                                     ty::vstore_fixed(*) => {
-                                        tcx.sess.span_err(
+                                        tcx.sess.span_err_with_code(
                                             path.span,
                                             ~"@trait, ~trait or &trait \
                                               are the only supported \
                                               forms of casting-to-\
-                                              trait");
+                                              trait",
+                                            diagnostics::trait_object_cast);
                                         ty::BoxTraitStore
                                     }
                                 };
@@ -290,17 +295,19 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
                        flags: uint) {
         if (flags & NO_TPS) != 0u {
             if path.types.len() > 0u {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     path.span,
-                    ~"type parameters are not allowed on this type");
+                    ~"type parameters are not allowed on this type",
+                    diagnostics::wrong_number_of_type_params);
             }
         }
 
         if (flags & NO_REGIONS) != 0u {
             if path.rp.is_some() {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     path.span,
-                    ~"region parameters are not allowed on this type");
+                    ~"region parameters are not allowed on this type",
+                    diagnostics::unexpected_lifetime_param);
             }
         }
     }
@@ -310,9 +317,11 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
     match tcx.ast_ty_to_ty_cache.find(&ast_ty.id) {
       Some(ty::atttce_resolved(ty)) => return ty,
       Some(ty::atttce_unresolved) => {
-        tcx.sess.span_fatal(ast_ty.span, ~"illegal recursive type; \
-                                          insert an enum in the cycle, \
-                                          if this is desired");
+        tcx.sess.span_fatal_with_code(
+            ast_ty.span,
+            ~"illegal recursive type; insert an enum in the cycle, if \
+              this is desired",
+            diagnostics::recursive_type);
       }
       None => { /* go on */ }
     }
@@ -330,8 +339,10 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
                    |tmt| ty::mk_uniq(tcx, tmt))
       }
       ast::ty_vec(ref mt) => {
-        tcx.sess.span_err(ast_ty.span,
-                          ~"bare `[]` is not a type");
+        tcx.sess.span_err_with_code(
+            ast_ty.span,
+            ~"bare `[]` is not a type",
+            diagnostics::bare_vec_or_str);
         // return /something/ so they can at least get more errors
         ty::mk_evec(tcx, ast_mt_to_mt(self, rscope, mt),
                     ty::vstore_uniq)
@@ -361,9 +372,10 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
       }
       ast::ty_path(path, id) => {
         let a_def = match tcx.def_map.find(&id) {
-          None => tcx.sess.span_fatal(
+          None => tcx.sess.span_fatal_with_code(
               ast_ty.span, fmt!("unbound path %s",
-                                path_to_str(path, tcx.sess.intr()))),
+                                path_to_str(path, tcx.sess.intr())),
+              diagnostics::unresolved_name),
           Some(d) => d
         };
         match a_def {
@@ -389,8 +401,10 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
                 ty::mk_mach_float(tcx, ft)
               }
               ast::ty_str => {
-                tcx.sess.span_err(ast_ty.span,
-                                  ~"bare `str` is not a type");
+                tcx.sess.span_err_with_code(
+                    ast_ty.span,
+                    ~"bare `str` is not a type",
+                    diagnostics::bare_vec_or_str);
                 // return /something/ so they can at least get more errors
                 ty::mk_estr(tcx, ty::vstore_uniq)
               }
@@ -409,8 +423,10 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
             ty::mk_self(tcx, did)
           }
           _ => {
-            tcx.sess.span_fatal(ast_ty.span,
-                                ~"found type name used as a variable");
+            tcx.sess.span_fatal_with_code(
+                ast_ty.span,
+                ~"found type name used as a variable",
+                diagnostics::not_a_type);
           }
         }
       }
@@ -425,16 +441,18 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:region_scope + Copy + Durable>(
                 ty::mk_evec(tcx, ast_mt_to_mt(self, rscope, a_mt),
                             ty::vstore_fixed(i as uint)),
               _ => {
-                tcx.sess.span_fatal(
-                    ast_ty.span, ~"expected constant expr for vector length");
+                tcx.sess.span_fatal_with_code(
+                    ast_ty.span, ~"expected constant expr for vector length",
+                    diagnostics::non_constant_vec_len);
               }
             }
           }
           Err(ref r) => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 ast_ty.span,
                 fmt!("expected constant expr for vector length: %s",
-                     *r));
+                     *r),
+                diagnostics::non_constant_vec_len);
           }
         }
       }
@@ -10,6 +10,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::pat_util::{PatIdMap, pat_id_map, pat_is_binding, pat_is_const};
 use middle::ty;
 use middle::typeck::check::demand;
@@ -150,11 +151,12 @@ pub fn check_pat_variant(pcx: pat_ctxt, pat: @ast::pat, path: @ast::path,
             kind_name = "structure";
         }
         _ => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 pat.span,
                 fmt!("mismatched types: expected `%s` but found enum or \
                       structure",
-                     fcx.infcx().ty_to_str(expected)));
+                     fcx.infcx().ty_to_str(expected)),
+                diagnostics::mismatched_types);
         }
     }
 
@@ -178,7 +180,8 @@ pub fn check_pat_variant(pcx: pat_ctxt, pat: @ast::pat, path: @ast::path,
                          arg_len,
                          if arg_len == 1u { ~"" } else { ~"s" });
             // XXX: This should not be fatal.
-            tcx.sess.span_fatal(pat.span, s);
+            tcx.sess.span_fatal_with_code(
+                pat.span, s, diagnostics::wrong_number_of_pattern_fields);
         }
 
         for subpats.each |pats| {
@@ -187,13 +190,14 @@ pub fn check_pat_variant(pcx: pat_ctxt, pat: @ast::pat, path: @ast::path,
             }
         }
     } else if subpats_len > 0u {
-        tcx.sess.span_fatal
+        tcx.sess.span_fatal_with_code
             (pat.span, fmt!("this pattern has %u field%s, but the \
                              corresponding %s has no fields",
                             subpats_len,
                             if subpats_len == 1u { ~"" }
                             else { ~"s" },
-                            kind_name));
+                            kind_name),
+             diagnostics::wrong_number_of_pattern_fields);
     }
 }
 
@@ -235,10 +239,12 @@ pub fn check_struct_pat_fields(pcx: pat_ctxt,
             }
             None => {
                 let name = pprust::path_to_str(path, tcx.sess.intr());
-                tcx.sess.span_err(span,
-                                  fmt!("struct `%s` does not have a field
-                                        named `%s`", name,
-                                       *tcx.sess.str_of(field.ident)));
+                tcx.sess.span_err_with_code(
+                    span,
+                    fmt!("struct `%s` does not have a field \
+                          named `%s`", name,
+                         *tcx.sess.str_of(field.ident)),
+                    diagnostics::no_field_found);
             }
         }
     }
@@ -249,9 +255,11 @@ pub fn check_struct_pat_fields(pcx: pat_ctxt,
             if found_fields.contains_key(&i) {
                 loop;
             }
-            tcx.sess.span_err(span,
-                              fmt!("pattern does not mention field `%s`",
-                                   *tcx.sess.str_of(field.ident)));
+            tcx.sess.span_err_with_code(
+                span,
+                fmt!("pattern does not mention field `%s`",
+                     *tcx.sess.str_of(field.ident)),
+                diagnostics::missing_fields);
         }
     }
 }
@@ -273,11 +281,13 @@ pub fn check_struct_pat(pcx: pat_ctxt, pat_id: ast::node_id, span: span,
         }
         Some(ast::def_struct(*)) | Some(ast::def_variant(*)) => {
             let name = pprust::path_to_str(path, tcx.sess.intr());
-            tcx.sess.span_err(span,
-                              fmt!("mismatched types: expected `%s` but \
-                                    found `%s`",
-                                   fcx.infcx().ty_to_str(expected),
-                                   name));
+            tcx.sess.span_err_with_code(
+                span,
+                fmt!("mismatched types: expected `%s` but \
+                      found `%s`",
+                     fcx.infcx().ty_to_str(expected),
+                     name),
+                diagnostics::mismatched_types);
         }
         _ => {
             tcx.sess.span_bug(span, ~"resolve didn't write in class");
@@ -286,8 +296,11 @@ pub fn check_struct_pat(pcx: pat_ctxt, pat_id: ast::node_id, span: span,
 
     // Forbid pattern-matching structs with destructors.
     if ty::has_dtor(tcx, class_id) {
-        tcx.sess.span_err(span, ~"deconstructing struct not allowed in \
-                                  pattern (it has a destructor)");
+        tcx.sess.span_err_with_code(
+            span,
+            ~"deconstructing struct not allowed in pattern (it has a \
+              destructor)",
+            diagnostics::destructure_drop_struct);
     }
 
     check_struct_pat_fields(pcx, span, path, fields, class_fields, class_id,
@@ -318,11 +331,13 @@ pub fn check_struct_like_enum_variant_pat(pcx: pat_ctxt,
         }
         Some(ast::def_struct(*)) | Some(ast::def_variant(*)) => {
             let name = pprust::path_to_str(path, tcx.sess.intr());
-            tcx.sess.span_err(span,
-                              fmt!("mismatched types: expected `%s` but \
-                                    found `%s`",
-                                   fcx.infcx().ty_to_str(expected),
-                                   name));
+            tcx.sess.span_err_with_code(
+                span,
+                fmt!("mismatched types: expected `%s` but \
+                      found `%s`",
+                     fcx.infcx().ty_to_str(expected),
+                     name),
+                diagnostics::mismatched_types);
         }
         _ => {
             tcx.sess.span_bug(span, ~"resolve didn't write in variant");
@@ -359,10 +374,13 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
         {
             // no-op
         } else if !ty::type_is_numeric(b_ty) {
-            tcx.sess.span_err(pat.span, ~"non-numeric type used in range");
+            tcx.sess.span_err_with_code(
+                pat.span, ~"non-numeric type used in range",
+                diagnostics::bad_range_pattern);
         } else if !valid_range_bounds(fcx.ccx, begin, end) {
-            tcx.sess.span_err(begin.span, ~"lower range bound must be less \
-                                           than upper");
+            tcx.sess.span_err_with_code(
+                begin.span, ~"lower range bound must be less than upper",
+                diagnostics::bad_range_pattern);
         }
         fcx.write_ty(pat.id, b_ty);
       }
@@ -429,10 +447,12 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
             }
             _ => {
                 // XXX: This should not be fatal.
-                tcx.sess.span_fatal(pat.span,
-                                    fmt!("mismatched types: expected `%s` \
-                                          but found struct",
-                                         fcx.infcx().ty_to_str(expected)));
+                tcx.sess.span_fatal_with_code(
+                    pat.span,
+                    fmt!("mismatched types: expected `%s` \
+                          but found struct",
+                         fcx.infcx().ty_to_str(expected)),
+                    diagnostics::mismatched_types);
             }
         }
 
@@ -444,18 +464,20 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
         let ex_elts = match s {
           ty::ty_tup(ref elts) => elts,
           _ => {
-            tcx.sess.span_fatal
+            tcx.sess.span_fatal_with_code
                 (pat.span,
                  fmt!("mismatched types: expected `%s`, found tuple",
-                      fcx.infcx().ty_to_str(expected)));
+                      fcx.infcx().ty_to_str(expected)),
+                 diagnostics::mismatched_types);
           }
         };
         let e_count = elts.len();
         if e_count != ex_elts.len() {
-            tcx.sess.span_fatal
+            tcx.sess.span_fatal_with_code
                 (pat.span, fmt!("mismatched types: expected a tuple \
                       with %u fields, found one with %u \
-                      fields", ex_elts.len(), e_count));
+                      fields", ex_elts.len(), e_count),
+                 diagnostics::mismatched_types);
         }
         let mut i = 0u;
         for elts.each |elt| {
@@ -472,11 +494,12 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
             fcx.write_ty(pat.id, expected);
           }
           _ => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 pat.span,
                 ~"mismatched types: expected `" +
                 fcx.infcx().ty_to_str(expected) +
-                ~"` found box");
+                ~"` found box",
+                diagnostics::mismatched_types);
           }
         }
       }
@@ -487,11 +510,12 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
             fcx.write_ty(pat.id, expected);
           }
           _ => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 pat.span,
                 ~"mismatched types: expected `" +
                 fcx.infcx().ty_to_str(expected) +
-                ~"` found uniq");
+                ~"` found uniq",
+                diagnostics::mismatched_types);
           }
         }
       }
@@ -502,11 +526,12 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
             fcx.write_ty(pat.id, expected);
           }
           _ => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 pat.span,
                 ~"mismatched types: expected `" +
                 fcx.infcx().ty_to_str(expected) +
-                ~"` found borrowed pointer");
+                ~"` found borrowed pointer",
+                diagnostics::mismatched_types);
           }
         }
       }
@@ -532,10 +557,11 @@ pub fn check_pat(pcx: pat_ctxt, pat: @ast::pat, expected: ty::t) {
             (mt, default_region_var)
           },
           _ => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 pat.span,
                 fmt!("mismatched type: expected `%s` but found vector",
-                     fcx.infcx().ty_to_str(expected))
+                     fcx.infcx().ty_to_str(expected)),
+                diagnostics::mismatched_types
             );
           }
         };
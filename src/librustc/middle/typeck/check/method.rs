@@ -81,6 +81,7 @@ obtained the type `Foo`, we would never match this method.
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::resolve;
 use middle::ty::*;
 use middle::ty;
@@ -1027,9 +1028,10 @@ pub impl LookupContext/&self {
         }
 
         if relevant_candidates.len() > 1 {
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 self.expr.span,
-                ~"multiple applicable methods in scope");
+                ~"multiple applicable methods in scope",
+                diagnostics::ambiguous_method);
             for uint::range(0, relevant_candidates.len()) |idx| {
                 self.report_candidate(idx, &relevant_candidates[idx].origin);
             }
@@ -1117,15 +1119,17 @@ pub impl LookupContext/&self {
             if num_supplied_tps == 0u {
                 self.fcx.infcx().next_ty_vars(candidate.num_method_tps)
             } else if candidate.num_method_tps == 0u {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     self.expr.span,
-                    ~"this method does not take type parameters");
+                    ~"this method does not take type parameters",
+                    diagnostics::wrong_number_of_type_params);
                 self.fcx.infcx().next_ty_vars(candidate.num_method_tps)
             } else if num_supplied_tps != candidate.num_method_tps {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     self.expr.span,
                     ~"incorrect number of type \
-                     parameters given for this method");
+                     parameters given for this method",
+                    diagnostics::wrong_number_of_type_params);
                 self.fcx.infcx().next_ty_vars(candidate.num_method_tps)
             } else {
                 self.supplied_tps.to_vec()
@@ -1172,16 +1176,18 @@ pub impl LookupContext/&self {
         }
 
         if ty::type_has_self(method_fty) {
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 self.expr.span,
                 ~"cannot call a method whose type contains a \
-                  self-type through a boxed trait");
+                  self-type through a boxed trait",
+                diagnostics::trait_object_method);
         }
 
         if candidate.num_method_tps > 0 {
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 self.expr.span,
-                ~"cannot call a generic method through a boxed trait");
+                ~"cannot call a generic method through a boxed trait",
+                diagnostics::trait_object_method);
         }
     }
 
@@ -1200,8 +1206,10 @@ pub impl LookupContext/&self {
         }
 
         if bad {
-            self.tcx().sess.span_err(self.expr.span,
-                                     ~"explicit call to destructor");
+            self.tcx().sess.span_err_with_code(
+                self.expr.span,
+                ~"explicit call to destructor",
+                diagnostics::explicit_destructor_call);
         }
     }
 
@@ -78,6 +78,7 @@ type parameter).
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::const_eval;
 use middle::pat_util::pat_id_map;
 use middle::pat_util;
@@ -186,6 +187,9 @@ pub struct FnCtxt {
     // Used by loop bodies that return from the outer function
     indirect_ret_ty: Option<ty::t>,
     purity: ast::purity,
+    // The innermost `unsafe` block that made the code unsafe, if any. An
+    // unsafe block nested in unsafe code doesn't count.
+    unsafe_blk: Option<ast::node_id>,
 
     // Sometimes we generate region pointers where the precise region
     // to use is not known. For example, an expression like `&x.f`
@@ -235,6 +239,7 @@ pub fn blank_fn_ctxt(ccx: @mut CrateCtxt,
         ret_ty: rty,
         indirect_ret_ty: None,
         purity: ast::pure_fn,
+        unsafe_blk: None,
         region_lb: region_bnd,
         in_scope_regions: @Nil,
         fn_kind: Vanilla,
@@ -260,7 +265,7 @@ pub fn check_bare_fn(ccx: @mut CrateCtxt,
     match ty::get(fty).sty {
         ty::ty_bare_fn(ref fn_ty) => {
             let fcx =
-                check_fn(ccx, self_info, fn_ty.purity,
+                check_fn(ccx, self_info, fn_ty.purity, None,
                          &fn_ty.sig, decl, body, Vanilla,
                          @Nil, blank_inherited(ccx));;
 
@@ -276,6 +281,7 @@ pub fn check_bare_fn(ccx: @mut CrateCtxt,
 pub fn check_fn(ccx: @mut CrateCtxt,
                 +self_info: Option<SelfInfo>,
                 purity: ast::purity,
+                unsafe_blk: Option<ast::node_id>,
                 fn_sig: &ty::FnSig,
                 decl: &ast::fn_decl,
                 body: &ast::blk,
@@ -291,6 +297,7 @@ pub fn check_fn(ccx: @mut CrateCtxt,
      * fn item there is still a bit more to do.
      *
      * - ...
+     * - unsafe_blk: the unsafe block the fn is in, for closures
      * - inherited_isr: regions in scope from the enclosing fn (if any)
      * - inherited: other fields inherited from the enclosing fn (if any)
      */
@@ -330,6 +337,7 @@ pub fn check_fn(ccx: @mut CrateCtxt,
             ret_ty: ret_ty,
             indirect_ret_ty: indirect_ret_ty,
             purity: purity,
+            unsafe_blk: unsafe_blk,
             region_lb: body.node.id,
             in_scope_regions: isr,
             fn_kind: fn_kind,
@@ -505,9 +513,11 @@ pub fn check_no_duplicate_fields(tcx: ty::ctxt,
         let (id, sp) = *p;
         match field_names.find(&id) {
           Some(orig_sp) => {
-            tcx.sess.span_err(sp, fmt!("Duplicate field \
-                                   name %s in record type declaration",
-                                        *tcx.sess.str_of(id)));
+            tcx.sess.span_err_with_code(
+                sp,
+                fmt!("Duplicate field name %s in record type declaration",
+                     *tcx.sess.str_of(id)),
+                diagnostics::duplicate_field);
             tcx.sess.span_note(orig_sp, ~"First declaration of \
                                           this field occurred here");
             break;
@@ -604,9 +614,10 @@ pub fn check_item(ccx: @mut CrateCtxt, it: @ast::item) {
             for m.items.each |item| {
                 let tpt = ty::lookup_item_type(ccx.tcx, local_def(item.id));
                 if !tpt.bounds.is_empty() {
-                    ccx.tcx.sess.span_err(
+                    ccx.tcx.sess.span_err_with_code(
                         item.span,
-                        fmt!("foreign items may not have type parameters"));
+                        fmt!("foreign items may not have type parameters"),
+                        diagnostics::foreign_item_type_params);
                 }
             }
         }
@@ -850,11 +861,16 @@ pub impl FnCtxt {
 
     fn require_unsafe(&self, sp: span, op: ~str) {
         match self.purity {
-          ast::unsafe_fn => {/*ok*/}
+          ast::unsafe_fn => {
+            for self.unsafe_blk.each |&id| {
+                self.ccx.tcx.used_unsafe.insert(id, ());
+            }
+          }
           _ => {
-            self.ccx.tcx.sess.span_err(
+            self.ccx.tcx.sess.span_err_with_code(
                 sp,
-                fmt!("%s requires unsafe function or block", op));
+                fmt!("%s requires unsafe function or block", op),
+                diagnostics::unsafe_required);
           }
         }
     }
@@ -878,10 +894,11 @@ pub impl FnCtxt {
 
     fn type_error_message(&self,
                           sp: span,
+                          code: &str,
                           mk_msg: &fn(~str) -> ~str,
                           actual_ty: ty::t,
                           err: Option<&ty::type_err>) {
-        self.infcx().type_error_message(sp, mk_msg, actual_ty, err);
+        self.infcx().type_error_message(sp, code, mk_msg, actual_ty, err);
     }
 
     fn report_mismatched_return_types(&self,
@@ -891,16 +908,20 @@ pub impl FnCtxt {
                                       err: &ty::type_err) {
         match self.fn_kind {
             ForLoop(_) if !ty::type_is_bool(e) && !ty::type_is_nil(a) =>
-                    self.tcx().sess.span_err(sp, fmt!("A for-loop body must \
+                    self.tcx().sess.span_err_with_code(sp,
+                        fmt!("A for-loop body must \
                         return (), but it returns %s here. \
                         Perhaps you meant to write a `do`-block?",
-                                            ppaux::ty_to_str(self.tcx(), a))),
+                             ppaux::ty_to_str(self.tcx(), a)),
+                        diagnostics::wrong_loop_body_type),
             DoBlock if ty::type_is_bool(e) && ty::type_is_nil(a) =>
                 // If we expected bool and got ()...
-                    self.tcx().sess.span_err(sp, fmt!("Do-block body must \
+                    self.tcx().sess.span_err_with_code(sp,
+                        fmt!("Do-block body must \
                         return %s, but returns () here. Perhaps you meant \
                         to write a `for`-loop?",
-                        ppaux::ty_to_str(self.tcx(), e))),
+                        ppaux::ty_to_str(self.tcx(), e)),
+                        diagnostics::wrong_loop_body_type),
             _ => self.infcx().report_mismatched_types(sp, e, a, err)
         }
     }
@@ -1189,7 +1210,8 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                                    else {"s were"},
                                    suffix);
 
-                    tcx.sess.span_err(sp, msg);
+                    tcx.sess.span_err_with_code(
+                        sp, msg, diagnostics::wrong_number_of_args);
 
                     vec::from_fn(supplied_arg_count, |_| ty::mk_err(tcx))
                 };
@@ -1198,7 +1220,9 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
             }
 
             _ => {
-                fcx.type_error_message(sp, |actual| {
+                fcx.type_error_message(sp,
+                                       diagnostics::not_a_function,
+                                       |actual| {
                     fmt!("expected function or foreign function but \
                           found `%s`", actual) }, in_fty, None);
 
@@ -1361,6 +1385,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
             }
             None => {
                 fcx.type_error_message(expr.span,
+                  diagnostics::no_method_found,
                   |actual| {
                       fmt!("type `%s` does not implement any method in scope \
                             named `%s`",
@@ -1386,21 +1411,6 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                              sugar)
     }
 
-    // A generic function for checking for or for-each loops
-    fn check_for(fcx: @mut FnCtxt,
-                 local: @ast::local,
-                 element_ty: ty::t,
-                 body: &ast::blk,
-                 node_id: ast::node_id)
-              -> bool {
-        let local_ty = fcx.local_ty(local.span, local.node.id);
-        demand::suptype(fcx, local.span, local_ty, element_ty);
-        let bot = check_decl_local(fcx, local);
-        check_block_no_value(fcx, body);
-        fcx.write_nil(node_id);
-        return bot;
-    }
-
     // A generic function for checking the then and else in an if
     // or if-check
     fn check_then_else(fcx: @mut FnCtxt,
@@ -1547,6 +1557,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
         }
         check_expr(fcx, rhs);
         fcx.type_error_message(ex.span,
+           diagnostics::unsupported_operator,
            |actual| {
                fmt!("binary operation %s cannot be applied to type `%s`",
                     ast_util::binop_to_str(op), actual)
@@ -1586,7 +1597,9 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                                DontAutoderefReceiver) {
           Some((ret_ty, _)) => ret_ty,
           _ => {
-              fcx.type_error_message(ex.span, |actual| {
+              fcx.type_error_message(ex.span,
+                                     diagnostics::unsupported_operator,
+                                     |actual| {
                   fmt!("cannot apply unary operator `%s` to type `%s`",
                               op_str, actual)
               }, rhs_t, None);
@@ -1676,7 +1689,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
             ty::determine_inherited_purity(copy fcx.purity, purity,
                                            fn_ty.sigil);
 
-        check_fn(fcx.ccx, None, inherited_purity,
+        check_fn(fcx.ccx, None, inherited_purity, fcx.unsafe_blk,
                  &fn_ty.sig, decl, body, fn_kind,
                  fcx.in_scope_regions, fcx.inh);
     }
@@ -1738,16 +1751,18 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                 // If we have resolved to a method but this is not in
                 // a callee position, error
                 if !is_callee {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         expr.span,
                         ~"attempted to take value of method \
-                          (try writing an anonymous function)");
+                          (try writing an anonymous function)",
+                        diagnostics::method_value);
                     // Add error type for the result
                     fcx.write_ty(expr.id, ty::mk_err(tcx));
                 }
             }
             None => {
                 fcx.type_error_message(expr.span,
+                  diagnostics::no_field_found,
                   |actual| {
                       fmt!("attempted access of field `%s` on type `%s`, but \
                             no field or method with that name was found",
@@ -1786,16 +1801,18 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
         for ast_fields.each |field| {
             match class_field_map.find(&field.node.ident) {
                 None => {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         field.span,
                         fmt!("structure has no field named `%s`",
-                             *tcx.sess.str_of(field.node.ident)));
+                             *tcx.sess.str_of(field.node.ident)),
+                        diagnostics::no_field_found);
                 }
                 Some((_, true)) => {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         field.span,
                         fmt!("field `%s` specified more than once",
-                             *tcx.sess.str_of(field.node.ident)));
+                             *tcx.sess.str_of(field.node.ident)),
+                        diagnostics::duplicate_field);
                 }
                 Some((field_id, false)) => {
                     let expected_field_type =
@@ -1827,14 +1844,16 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                     }
                 }
 
-                tcx.sess.span_err(span,
-                                  fmt!("missing field%s: %s",
-                                       if missing_fields.len() == 1 {
-                                           ~""
-                                       } else {
-                                           ~"s"
-                                       },
-                                       str::connect(missing_fields, ~", ")));
+                tcx.sess.span_err_with_code(
+                    span,
+                    fmt!("missing field%s: %s",
+                         if missing_fields.len() == 1 {
+                             ~""
+                         } else {
+                             ~"s"
+                         },
+                         str::connect(missing_fields, ~", ")),
+                    diagnostics::missing_fields);
             }
         }
 
@@ -2028,6 +2047,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                     result::Err(_) => {
                         fcx.type_error_message(
                             expr.span,
+                            diagnostics::wrong_loop_body_type,
                             |actual| {
                                 let did_you_mean = {
                                     if ty::type_is_nil(fty.sig.output) {
@@ -2063,6 +2083,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                     Some(expected_t) => {
                         fcx.type_error_message(
                             expr.span,
+                            diagnostics::non_closure_last_arg,
                             |actual| {
                                 fmt!("last argument in `for` call \
                                       has non-closure type: %s",
@@ -2218,20 +2239,24 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
               None => {
                 match sty {
                   ty::ty_enum(*) => {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         expr.span,
                         ~"can only dereference enums \
                          with a single variant which has a \
-                         single argument");
+                         single argument",
+                        diagnostics::cannot_deref);
                   }
                   ty::ty_struct(*) => {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         expr.span,
                         ~"can only dereference structs with one anonymous \
-                          field");
+                          field",
+                        diagnostics::cannot_deref);
                   }
                   _ => {
-                      fcx.type_error_message(expr.span, |actual| {
+                      fcx.type_error_message(expr.span,
+                                             diagnostics::cannot_deref,
+                                             |actual| {
                           fmt!("type %s cannot be dereferenced", actual)
                       }, oprnd_t, None);
                   }
@@ -2316,9 +2341,10 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                                     ret_ty, ty::mk_nil(tcx)) {
             result::Ok(_) => { /* fall through */ }
             result::Err(_) => {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     expr.span,
-                    ~"`return;` in function returning non-nil");
+                    ~"`return;` in function returning non-nil",
+                    diagnostics::return_without_value);
             }
           },
           Some(e) => {
@@ -2379,7 +2405,9 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
             Some(ty::ty_closure(_)) => expected.get(),
             _ => match expected {
                 Some(expected_t) => {
-                    fcx.type_error_message(expr.span, |actual| {
+                    fcx.type_error_message(expr.span,
+                                           diagnostics::non_closure_last_arg,
+                                           |actual| {
                         fmt!("last argument in `do` call \
                               has non-closure type: %s",
                              actual)
@@ -2436,12 +2464,16 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
 
           _ => {
             if ty::type_is_nil(t_e) {
-                fcx.type_error_message(expr.span, |actual| {
+                fcx.type_error_message(expr.span,
+                                       diagnostics::invalid_cast,
+                                       |actual| {
                     fmt!("cast from nil: `%s` as `%s`", actual,
                          fcx.infcx().ty_to_str(t_1))
                 }, t_e, None);
             } else if ty::type_is_nil(t_1) {
-                fcx.type_error_message(expr.span, |actual| {
+                fcx.type_error_message(expr.span,
+                                       diagnostics::invalid_cast,
+                                       |actual| {
                     fmt!("cast to nil: `%s` as `%s`", actual,
                          fcx.infcx().ty_to_str(t_1))
                 }, t_e, None);
@@ -2494,7 +2526,9 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                 supported here, then file an enhancement issue and record the
                 issue number in this comment.
                 */
-                fcx.type_error_message(expr.span, |actual| {
+                fcx.type_error_message(expr.span,
+                                       diagnostics::invalid_cast,
+                                       |actual| {
                     fmt!("non-scalar cast: `%s` as `%s`", actual,
                          fcx.infcx().ty_to_str(t_1))
                 }, t_e, None);
@@ -2576,7 +2610,9 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
                                          AutoderefReceiver) {
                       Some((ret_ty, _)) => fcx.write_ty(id, ret_ty),
                       _ => {
-                          fcx.type_error_message(expr.span, |actual|
+                          fcx.type_error_message(expr.span,
+                                                 diagnostics::cannot_index,
+                                                 |actual|
                               fmt!("cannot index a value of type `%s`",
                                    actual), base_t, None);
                           fcx.write_ty(id, ty::mk_err(tcx));
@@ -2606,7 +2642,7 @@ pub fn check_expr_with_unifier(fcx: @mut FnCtxt,
 
 pub fn require_integral(fcx: @mut FnCtxt, sp: span, t: ty::t) {
     if !type_is_integral(fcx, sp, t) {
-        fcx.type_error_message(sp, |actual| {
+        fcx.type_error_message(sp, diagnostics::mismatched_types, |actual| {
             fmt!("mismatched types: expected integral type but found `%s`",
                  actual)
         }, t, None);
@@ -2693,7 +2729,17 @@ pub fn check_block_with_expected(fcx0: @mut FnCtxt,
                                  expected: Option<ty::t>)
                               -> bool {
     let fcx = match blk.node.rules {
-      ast::unsafe_blk => @mut FnCtxt {purity: ast::unsafe_fn,.. copy *fcx0},
+      ast::unsafe_blk if fcx0.purity == ast::unsafe_fn => {
+        // Already unsafe, so the block is not needed
+        fcx0
+      }
+      ast::unsafe_blk => {
+        @mut FnCtxt {
+            purity: ast::unsafe_fn,
+            unsafe_blk: Some(blk.node.id),
+            .. copy *fcx0
+        }
+      }
       ast::default_blk => fcx0
     };
     do fcx.with_region_lb(blk.node.id) {
@@ -2769,10 +2815,12 @@ pub fn check_instantiable(tcx: ty::ctxt,
                           item_id: ast::node_id) {
     let item_ty = ty::node_id_to_type(tcx, item_id);
     if !ty::is_instantiable(tcx, item_ty) {
-        tcx.sess.span_err(sp, fmt!("this type cannot be instantiated \
-                  without an instance of itself; \
-                  consider using `Option<%s>`",
-                                   ppaux::ty_to_str(tcx, item_ty)));
+        tcx.sess.span_err_with_code(
+            sp,
+            fmt!("this type cannot be instantiated without an instance \
+                  of itself; consider using `Option<%s>`",
+                 ppaux::ty_to_str(tcx, item_ty)),
+            diagnostics::recursive_type);
     }
 }
 
@@ -2805,19 +2853,25 @@ pub fn check_enum_variants(ccx: @mut CrateCtxt,
                     *disr_val = val as int;
                   }
                   Ok(_) => {
-                    ccx.tcx.sess.span_err(e.span, ~"expected signed integer \
-                                                    constant");
+                    ccx.tcx.sess.span_err_with_code(
+                        e.span,
+                        ~"expected signed integer constant",
+                        diagnostics::invalid_discriminant);
                   }
                   Err(ref err) => {
-                    ccx.tcx.sess.span_err(e.span,
-                     fmt!("expected constant: %s", (*err)));
+                    ccx.tcx.sess.span_err_with_code(
+                        e.span,
+                        fmt!("expected constant: %s", (*err)),
+                        diagnostics::invalid_discriminant);
 
                   }
                 }
             }
             if vec::contains(*disr_vals, &*disr_val) {
-                ccx.tcx.sess.span_err(v.span,
-                                      ~"discriminator value already exists");
+                ccx.tcx.sess.span_err_with_code(
+                    v.span,
+                    ~"discriminator value already exists",
+                    diagnostics::duplicate_discriminant);
             }
             disr_vals.push(*disr_val);
             let ctor_ty = ty::node_id_to_type(ccx.tcx, v.node.id);
@@ -2883,9 +2937,11 @@ pub fn check_enum_variants(ccx: @mut CrateCtxt,
           _ => false
         }
     }) {
-        ccx.tcx.sess.span_err(sp, ~"illegal recursive enum type; \
-                                 wrap the inner value in a box to \
-                                 make it representable");
+        ccx.tcx.sess.span_err_with_code(
+            sp,
+            ~"illegal recursive enum type; wrap the inner value in a box \
+              to make it representable",
+            diagnostics::recursive_type);
     }
 
     // Check that it is possible to instantiate this enum:
@@ -2984,8 +3040,9 @@ pub fn instantiate_path(fcx: @mut FnCtxt,
       Some(_) => { // user supplied a lifetime parameter...
         match tpt.region_param {
           None => { // ...but the type is not lifetime parameterized!
-            fcx.ccx.tcx.sess.span_err
-                (span, ~"this item is not region-parameterized");
+            fcx.ccx.tcx.sess.span_err_with_code
+                (span, ~"this item is not region-parameterized",
+                 diagnostics::unexpected_lifetime_param);
             None
           }
           Some(_) => { // ...and the type is lifetime parameterized, ok.
@@ -3004,16 +3061,19 @@ pub fn instantiate_path(fcx: @mut FnCtxt,
     let tps = if ty_substs_len == 0 {
         fcx.infcx().next_ty_vars(ty_param_count)
     } else if ty_param_count == 0 {
-        fcx.ccx.tcx.sess.span_err
-            (span, ~"this item does not take type parameters");
+        fcx.ccx.tcx.sess.span_err_with_code
+            (span, ~"this item does not take type parameters",
+             diagnostics::wrong_number_of_type_params);
         fcx.infcx().next_ty_vars(ty_param_count)
     } else if ty_substs_len > ty_param_count {
-        fcx.ccx.tcx.sess.span_err
-            (span, ~"too many type parameters provided for this item");
+        fcx.ccx.tcx.sess.span_err_with_code
+            (span, ~"too many type parameters provided for this item",
+             diagnostics::wrong_number_of_type_params);
         fcx.infcx().next_ty_vars(ty_param_count)
     } else if ty_substs_len < ty_param_count {
-        fcx.ccx.tcx.sess.span_err
-            (span, ~"not enough type parameters provided for this item");
+        fcx.ccx.tcx.sess.span_err_with_code
+            (span, ~"not enough type parameters provided for this item",
+             diagnostics::wrong_number_of_type_params);
         fcx.infcx().next_ty_vars(ty_param_count)
     } else {
         pth.types.map(|aty| fcx.to_ty(*aty))
@@ -3032,7 +3092,9 @@ pub fn structurally_resolved_type(fcx: @mut FnCtxt, sp: span, tp: ty::t)
     match infer::resolve_type(fcx.infcx(), tp, force_tvar) {
         Ok(t_s) if !ty::type_is_ty_var(t_s) => return t_s,
         _ => {
-            fcx.type_error_message(sp, |_actual| {
+            fcx.type_error_message(sp,
+                                   diagnostics::type_annotations_needed,
+                                   |_actual| {
                 ~"the type of this value must be known in this context"
             }, tp, None);
             return ty::mk_err(fcx.tcx());
@@ -3080,7 +3142,8 @@ pub fn ast_expr_vstore_to_vstore(fcx: @mut FnCtxt,
         ast::expr_vstore_fixed(Some(u)) => {
             if n != u {
                 let s = fmt!("fixed-size sequence mismatch: %u vs. %u",u, n);
-                fcx.ccx.tcx.sess.span_err(e.span,s);
+                fcx.ccx.tcx.sess.span_err_with_code(
+                    e.span, s, diagnostics::fixed_size_mismatch);
             }
             ty::vstore_fixed(u)
         }
@@ -3143,9 +3206,10 @@ pub fn check_bounds_are_used(ccx: @mut CrateCtxt,
 
     for tps_used.eachi |i, b| {
         if !*b {
-            ccx.tcx.sess.span_err(
+            ccx.tcx.sess.span_err_with_code(
                 span, fmt!("type parameter `%s` is unused",
-                           *ccx.tcx.sess.str_of(tps.get(i).ident)));
+                           *ccx.tcx.sess.str_of(tps.get(i).ident)),
+                diagnostics::unused_type_param);
         }
     }
 }
@@ -3454,8 +3518,10 @@ pub fn check_intrinsic_type(ccx: @mut CrateCtxt, it: @ast::foreign_item) {
          ty::mk_i64(tcx))
      }
      ref other => {
-        tcx.sess.span_err(it.span, ~"unrecognized intrinsic function: `" +
-                          (*other) + ~"`");
+        tcx.sess.span_err_with_code(
+            it.span,
+            ~"unrecognized intrinsic function: `" + (*other) + ~"`",
+            diagnostics::bad_intrinsic);
         return;
       }
     };
@@ -3468,9 +3534,11 @@ pub fn check_intrinsic_type(ccx: @mut CrateCtxt, it: @ast::foreign_item) {
     let i_ty = ty::lookup_item_type(ccx.tcx, local_def(it.id));
     let i_n_tps = (*i_ty.bounds).len();
     if i_n_tps != n_tps {
-        tcx.sess.span_err(it.span, fmt!("intrinsic has wrong number \
-                                         of type parameters: found %u, \
-                                         expected %u", i_n_tps, n_tps));
+        tcx.sess.span_err_with_code(
+            it.span,
+            fmt!("intrinsic has wrong number of type parameters: \
+                  found %u, expected %u", i_n_tps, n_tps),
+            diagnostics::bad_intrinsic);
     } else {
         require_same_types(
             tcx, None, false, it.span, i_ty.ty, fty,
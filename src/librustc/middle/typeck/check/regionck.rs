@@ -29,6 +29,7 @@ this point a bit better.
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::freevars::get_freevars;
 use middle::pat_util::{pat_bindings, pat_is_binding};
 use middle::ty::{encl_region, re_scope};
@@ -353,10 +354,11 @@ pub fn constrain_auto_ref(rcx: @mut Rcx, expr: @ast::expr) {
             // for some reason, the code were to change so that in
             // some cases `region` is not a region variable, then
             // reporting an error would be the correct path.
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 expr.span,
                 ~"lifetime of borrowed pointer does not include \
-                  the expression being borrowed");
+                  the expression being borrowed",
+                diagnostics::reference_outlives_referent);
             note_and_explain_region(
                 tcx,
                 ~"lifetime of the borrowed pointer is",
@@ -385,9 +387,10 @@ pub fn constrain_free_variables(
                               region, en_region) {
           result::Ok(()) => {}
           result::Err(_) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 freevar.span,
-                ~"captured variable does not outlive the enclosing closure");
+                ~"captured variable does not outlive the enclosing closure",
+                diagnostics::reference_outlives_referent);
             note_and_explain_region(
                 tcx,
                 ~"captured variable is valid for ",
@@ -464,9 +467,10 @@ pub fn constrain_regions_in_type(
 
         match rcx.fcx.mk_subr(true, span, encl_region, region) {
           result::Err(_) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 span,
-                fmt!("reference is not valid outside of its lifetime"));
+                fmt!("reference is not valid outside of its lifetime"),
+                diagnostics::reference_outlives_referent);
             note_and_explain_region(
                 tcx,
                 ~"the reference is only valid for ",
@@ -10,6 +10,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::resolve::Impl;
 use middle::ty::{param_ty, substs};
 use middle::ty;
@@ -113,12 +114,13 @@ pub fn lookup_vtables(vcx: &VtableContext,
             match lookup_vtable(vcx, location_info, *ty, trait_ty, is_early) {
                 Some(vtable) => result.push(vtable),
                 None => {
-                    vcx.tcx().sess.span_fatal(
+                    vcx.tcx().sess.span_fatal_with_code(
                         location_info.span,
                         fmt!("failed to find an implementation of \
                               trait %s for %s",
                              ppaux::ty_to_str(vcx.tcx(), trait_ty),
-                             ppaux::ty_to_str(vcx.tcx(), *ty)));
+                             ppaux::ty_to_str(vcx.tcx(), *ty)),
+                        diagnostics::trait_not_implemented);
                 }
             }
         }
@@ -417,9 +419,10 @@ pub fn lookup_vtable(vcx: &VtableContext,
                 1 => { return Some(/*bad*/copy found[0]); }
                 _ => {
                     if !is_early {
-                        vcx.tcx().sess.span_err(
+                        vcx.tcx().sess.span_err_with_code(
                             location_info.span,
-                            ~"multiple applicable methods in scope");
+                            ~"multiple applicable methods in scope",
+                            diagnostics::ambiguous_method);
                     }
                     return Some(/*bad*/copy found[0]);
                 }
@@ -438,11 +441,12 @@ pub fn fixup_ty(vcx: &VtableContext,
     match resolve_type(vcx.infcx, ty, resolve_and_force_all_but_regions) {
         Ok(new_type) => Some(new_type),
         Err(e) if !is_early => {
-            tcx.sess.span_fatal(
+            tcx.sess.span_fatal_with_code(
                 location_info.span,
                 fmt!("cannot determine a type \
                       for this bounded type parameter: %s",
-                     fixup_err_to_str(e)))
+                     fixup_err_to_str(e)),
+                diagnostics::type_annotations_needed)
         }
         Err(_) => {
             None
@@ -606,12 +610,13 @@ pub fn early_resolve_expr(ex: @ast::expr,
                                   }
                               }
                               None => {
-                                  fcx.tcx().sess.span_err(
+                                  fcx.tcx().sess.span_err_with_code(
                                       ex.span,
                                       fmt!("failed to find an implementation \
                                             of trait %s for %s",
                                            fcx.infcx().ty_to_str(target_ty),
-                                           fcx.infcx().ty_to_str(mt.ty)));
+                                           fcx.infcx().ty_to_str(mt.ty)),
+                                      diagnostics::trait_not_implemented);
                               }
                           }
 
@@ -631,34 +636,38 @@ pub fn early_resolve_expr(ex: @ast::expr,
                       }
 
                       (_, ty::BareTraitStore) => {
-                          fcx.ccx.tcx.sess.span_err(
+                          fcx.ccx.tcx.sess.span_err_with_code(
                               ex.span,
                               ~"a sigil (`@`, `~`, or `&`) must be specified \
-                                when casting to a trait");
+                                when casting to a trait",
+                              diagnostics::trait_object_cast);
                       }
 
                       (_, ty::BoxTraitStore) => {
-                          fcx.ccx.tcx.sess.span_err(
+                          fcx.ccx.tcx.sess.span_err_with_code(
                               ex.span,
                               fmt!("can only cast an @-pointer \
                                     to an @-object, not a %s",
-                                   ty::ty_sort_str(fcx.tcx(), ty)));
+                                   ty::ty_sort_str(fcx.tcx(), ty)),
+                              diagnostics::trait_object_cast);
                       }
 
                       (_, ty::UniqTraitStore) => {
-                          fcx.ccx.tcx.sess.span_err(
+                          fcx.ccx.tcx.sess.span_err_with_code(
                               ex.span,
                               fmt!("can only cast an ~-pointer \
                                     to a ~-object, not a %s",
-                                   ty::ty_sort_str(fcx.tcx(), ty)));
+                                   ty::ty_sort_str(fcx.tcx(), ty)),
+                              diagnostics::trait_object_cast);
                       }
 
                       (_, ty::RegionTraitStore(_)) => {
-                          fcx.ccx.tcx.sess.span_err(
+                          fcx.ccx.tcx.sess.span_err_with_code(
                               ex.span,
                               fmt!("can only cast an &-pointer \
                                     to an &-object, not a %s",
-                                   ty::ty_sort_str(fcx.tcx(), ty)));
+                                   ty::ty_sort_str(fcx.tcx(), ty)),
+                              diagnostics::trait_object_cast);
                       }
                   }
               }
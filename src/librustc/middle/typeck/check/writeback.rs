@@ -14,6 +14,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::pat_util;
 use middle::ty::arg;
 use middle::ty;
@@ -40,11 +41,12 @@ fn resolve_type_vars_in_type(fcx: @mut FnCtxt, sp: span, typ: ty::t)
         Ok(new_type) => return Some(new_type),
         Err(e) => {
             if !fcx.ccx.tcx.sess.has_errors() {
-                fcx.ccx.tcx.sess.span_err(
+                fcx.ccx.tcx.sess.span_err_with_code(
                     sp,
                     fmt!("cannot determine a type \
                           for this expression: %s",
-                         infer::fixup_err_to_str(e)))
+                         infer::fixup_err_to_str(e)),
+                    diagnostics::type_annotations_needed)
             }
             return None;
         }
@@ -83,9 +85,10 @@ fn resolve_type_vars_for_node(wbcx: @mut WbCtxt, sp: span, id: ast::node_id)
             match resolve_region(fcx.infcx(), r, resolve_all | force_all) {
                 Err(e) => {
                     // This should not, I think, happen:
-                    fcx.ccx.tcx.sess.span_err(
+                    fcx.ccx.tcx.sess.span_err_with_code(
                         sp, fmt!("cannot resolve bound for closure: %s",
-                                 infer::fixup_err_to_str(e)));
+                                 infer::fixup_err_to_str(e)),
+                        diagnostics::lifetime_inference_failed);
                 }
                 Ok(r1) => {
                     let resolved_adj = @ty::AutoAddEnv(r1, s);
@@ -102,9 +105,10 @@ fn resolve_type_vars_for_node(wbcx: @mut WbCtxt, sp: span, id: ast::node_id)
                                          resolve_all | force_all) {
                         Err(e) => {
                             // This should not, I think, happen.
-                            fcx.ccx.tcx.sess.span_err(
+                            fcx.ccx.tcx.sess.span_err_with_code(
                                 sp, fmt!("cannot resolve scope of borrow: %s",
-                                         infer::fixup_err_to_str(e)));
+                                         infer::fixup_err_to_str(e)),
+                                diagnostics::lifetime_inference_failed);
                             Some(*autoref)
                         }
                         Ok(r) => {
@@ -244,11 +248,12 @@ fn visit_local(l: @ast::local, &&wbcx: @mut WbCtxt, v: wb_vt) {
             write_ty_to_tcx(wbcx.fcx.ccx.tcx, l.node.id, lty);
         }
         Err(e) => {
-            wbcx.fcx.ccx.tcx.sess.span_err(
+            wbcx.fcx.ccx.tcx.sess.span_err_with_code(
                 l.span,
                 fmt!("cannot determine a type \
                       for this local variable: %s",
-                     infer::fixup_err_to_str(e)));
+                     infer::fixup_err_to_str(e)),
+                diagnostics::type_annotations_needed);
             wbcx.success = false;
         }
     }
@@ -16,6 +16,7 @@
 
 use core::prelude::*;
 
+use diagnostics;
 use driver;
 use metadata::csearch::{ProvidedTraitMethodInfo, each_path, get_impl_traits};
 use metadata::csearch::{get_impls_for_mod};
@@ -78,11 +79,11 @@ pub fn get_base_type(inference_context: @mut InferCtxt,
             resolved_type = resulting_type;
         }
         _ => {
-            inference_context.tcx.sess.span_fatal(span,
-                                                  ~"the type of this value \
-                                                    must be known in order \
-                                                    to determine the base \
-                                                    type");
+            inference_context.tcx.sess.span_fatal_with_code(
+                span,
+                ~"the type of this value must be known in order to \
+                  determine the base type",
+                diagnostics::type_annotations_needed);
         }
     }
 
@@ -240,10 +241,11 @@ pub impl CoherenceChecker {
                                        self_type.ty) {
                 None => {
                     let session = self.crate_context.tcx.sess;
-                    session.span_err(item.span,
-                                     ~"no base type found for inherent \
-                                       implementation; implement a \
-                                       trait or new type instead");
+                    session.span_err_with_code(
+                        item.span,
+                        ~"no base type found for inherent implementation; \
+                          implement a trait or new type instead",
+                        diagnostics::invalid_inherent_impl);
                 }
                 Some(_) => {
                     // Nothing to do.
@@ -435,9 +437,10 @@ pub impl CoherenceChecker {
 
                     if self.polytypes_unify(polytype_a, polytype_b) {
                         let session = self.crate_context.tcx.sess;
-                        session.span_err(self.span_of_impl(implementation_b),
-                                         ~"conflicting implementations for a \
-                                           trait");
+                        session.span_err_with_code(
+                            self.span_of_impl(implementation_b),
+                            ~"conflicting implementations for a trait",
+                            diagnostics::conflicting_impls);
                         session.span_note(self.span_of_impl(implementation_a),
                                           ~"note conflicting implementation \
                                             here");
@@ -647,14 +650,15 @@ pub impl CoherenceChecker {
                                     // this is an error.
 
                                     let session = self.crate_context.tcx.sess;
-                                    session.span_err(item.span,
-                                                     ~"cannot implement \
-                                                      inherent methods for a \
-                                                      type outside the crate \
-                                                      the type was defined \
-                                                      in; define and \
-                                                      implement a trait or \
-                                                      new type instead");
+                                    session.span_err_with_code(
+                                        item.span,
+                                        ~"cannot implement inherent \
+                                          methods for a type outside \
+                                          the crate the type was \
+                                          defined in; define and \
+                                          implement a trait or new \
+                                          type instead",
+                                        diagnostics::invalid_inherent_impl);
                                 }
                                 _ => ()
                           }
@@ -669,12 +673,12 @@ pub impl CoherenceChecker {
 
                                 if trait_def_id.crate != local_crate {
                                     let session = self.crate_context.tcx.sess;
-                                    session.span_err(item.span,
-                                                     ~"cannot provide an \
-                                                       extension \
-                                                       implementation for a \
-                                                       trait not defined in \
-                                                       this crate");
+                                    session.span_err_with_code(
+                                        item.span,
+                                        ~"cannot provide an extension \
+                                          implementation for a trait \
+                                          not defined in this crate",
+                                        diagnostics::orphan_impl);
                                 }
                             }
                         }
@@ -719,9 +723,11 @@ pub impl CoherenceChecker {
         for (*ty::trait_methods(tcx, trait_did)).each |method| {
             if provided_names.contains(&method.ident) { loop; }
 
-            tcx.sess.span_err(trait_ref_span,
-                              fmt!("missing method `%s`",
-                                   *tcx.sess.str_of(method.ident)));
+            tcx.sess.span_err_with_code(
+                trait_ref_span,
+                fmt!("missing method `%s`",
+                     *tcx.sess.str_of(method.ident)),
+                diagnostics::missing_trait_method);
         }
     }
 
@@ -1008,10 +1014,11 @@ pub impl CoherenceChecker {
                     if impl_info.did.crate == ast::local_crate {
                         match tcx.items.find(&impl_info.did.node) {
                             Some(ast_map::node_item(@ref item, _)) => {
-                                tcx.sess.span_err((*item).span,
-                                                  ~"the Drop trait may only \
-                                                    be implemented on \
-                                                    structures");
+                                tcx.sess.span_err_with_code(
+                                    (*item).span,
+                                    ~"the Drop trait may only be \
+                                      implemented on structures",
+                                    diagnostics::drop_on_non_struct);
                             }
                             _ => {
                                 tcx.sess.bug(~"didn't find impl in ast map");
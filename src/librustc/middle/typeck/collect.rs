@@ -32,6 +32,7 @@ are represented as `ty_param()` instances.
 
 use core::prelude::*;
 
+use diagnostics;
 use metadata::csearch;
 use middle::ty::{InstantiatedTraitRef, arg};
 use middle::ty::{substs, ty_param_bounds_and_ty, ty_param_substs_and_ty};
@@ -329,8 +330,9 @@ pub fn ensure_supertraits(ccx: &CrateCtxt,
                             { other_trait.def_id == did }) {
             // This means a trait inherited from the same supertrait more
             // than once.
-            tcx.sess.span_err(sp, ~"Duplicate supertrait in trait \
-                                     declaration");
+            tcx.sess.span_err_with_code(
+                sp, ~"Duplicate supertrait in trait declaration",
+                diagnostics::duplicate_supertrait);
             return;
         }
         instantiated.push(InstantiatedTraitRef { def_id: did, tpt: tpt });
@@ -369,46 +371,53 @@ pub fn compare_impl_method(tcx: ty::ctxt,
         if impl_m.self_ty == ast::sty_static {
             // Needs to be a fatal error because otherwise,
             // method::transform_self_type_for_method ICEs
-            tcx.sess.span_fatal(cm.span,
-                 fmt!("method `%s` is declared as \
-                       static in its impl, but not in \
-                       its trait", *tcx.sess.str_of(impl_m.ident)));
+            tcx.sess.span_fatal_with_code(
+                cm.span,
+                fmt!("method `%s` is declared as static in its impl, \
+                      but not in its trait",
+                     *tcx.sess.str_of(impl_m.ident)),
+                diagnostics::impl_method_mismatch);
         }
         else if trait_m.self_ty == ast::sty_static {
-            tcx.sess.span_fatal(cm.span,
-                 fmt!("method `%s` is declared as \
-                       static in its trait, but not in \
-                       its impl", *tcx.sess.str_of(impl_m.ident)));
+            tcx.sess.span_fatal_with_code(
+                cm.span,
+                fmt!("method `%s` is declared as static in its trait, \
+                      but not in its impl",
+                     *tcx.sess.str_of(impl_m.ident)),
+                diagnostics::impl_method_mismatch);
         }
         else {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 cm.span,
                 fmt!("method `%s`'s self type does \
                       not match the trait method's \
-                      self type", *tcx.sess.str_of(impl_m.ident)));
+                      self type", *tcx.sess.str_of(impl_m.ident)),
+                diagnostics::impl_method_mismatch);
         }
     }
 
     if impl_m.tps.len() != trait_m.tps.len() {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             cm.span,
             fmt!("method `%s` has %u type %s, but its trait \
                   declaration has %u type %s",
                  *tcx.sess.str_of(trait_m.ident), impl_m.tps.len(),
                  pluralize(impl_m.tps.len(), ~"parameter"),
                  trait_m.tps.len(),
-                 pluralize(trait_m.tps.len(), ~"parameter")));
+                 pluralize(trait_m.tps.len(), ~"parameter")),
+            diagnostics::impl_method_mismatch);
         return;
     }
 
     if vec::len(impl_m.fty.sig.inputs) != vec::len(trait_m.fty.sig.inputs) {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             cm.span,
             fmt!("method `%s` has %u parameters \
                   but the trait has %u",
                  *tcx.sess.str_of(trait_m.ident),
                  vec::len(impl_m.fty.sig.inputs),
-                 vec::len(trait_m.fty.sig.inputs)));
+                 vec::len(trait_m.fty.sig.inputs)),
+            diagnostics::impl_method_mismatch);
         return;
     }
 
@@ -422,7 +431,7 @@ pub fn compare_impl_method(tcx: ty::ctxt,
         // Would be nice to use the ty param names in the error message,
         // but we don't have easy access to them here
         if impl_param_bounds.len() != trait_param_bounds.len() {
-           tcx.sess.span_err(
+           tcx.sess.span_err_with_code(
                cm.span,
                fmt!("in method `%s`, \
                      type parameter %u has %u %s, but the same type \
@@ -431,7 +440,8 @@ pub fn compare_impl_method(tcx: ty::ctxt,
                     i, impl_param_bounds.len(),
                     pluralize(impl_param_bounds.len(), ~"bound"),
                     trait_param_bounds.len(),
-                    pluralize(trait_param_bounds.len(), ~"bound")));
+                    pluralize(trait_param_bounds.len(), ~"bound")),
+               diagnostics::impl_method_mismatch);
            return;
         }
     }
@@ -478,11 +488,12 @@ pub fn compare_impl_method(tcx: ty::ctxt,
     match infer::mk_subty(infcx, false, cm.span, impl_fty, trait_fty) {
         result::Ok(()) => {}
         result::Err(ref terr) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 cm.span,
                 fmt!("method `%s` has an incompatible type: %s",
                      *tcx.sess.str_of(trait_m.ident),
-                     ty::type_err_to_str(tcx, terr)));
+                     ty::type_err_to_str(tcx, terr)),
+                diagnostics::impl_method_mismatch);
             ty::note_and_explain_type_err(tcx, terr);
         }
     }
@@ -539,11 +550,12 @@ pub fn check_methods_against_trait(ccx: &CrateCtxt,
             }
             None => {
                 // This method is not part of the trait
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     impl_m.span,
                     fmt!("method `%s` is not a member of trait `%s`",
                          *tcx.sess.str_of(impl_m.mty.ident),
-                         path_to_str(a_trait_ty.path, tcx.sess.intr())));
+                         path_to_str(a_trait_ty.path, tcx.sess.intr())),
+                    diagnostics::not_a_trait_method);
             }
         }
     }
@@ -604,10 +616,11 @@ pub fn ensure_no_ty_param_bounds(ccx: &CrateCtxt,
                                  thing: &'static str) {
     for generics.ty_params.each |ty_param| {
         if ty_param.bounds.len() > 0 {
-            ccx.tcx.sess.span_err(
+            ccx.tcx.sess.span_err_with_code(
                 span,
                 fmt!("trait bounds are not allowed in %s definitions",
-                     thing));
+                     thing),
+                diagnostics::bounds_not_allowed);
         }
     }
 }
@@ -813,10 +826,10 @@ pub fn instantiate_trait_ref(ccx: &CrateCtxt, t: @ast::trait_ref,
            ty::ty_trait(*) => {
               (t_id, tpt)
            }
-           _ => sess.span_fatal(sp, err),
+           _ => sess.span_fatal_with_code(sp, err, diagnostics::not_a_trait),
         }
       }
-      _ => sess.span_fatal(sp, err)
+      _ => sess.span_fatal_with_code(sp, err, diagnostics::not_a_trait)
     }
 }
 
@@ -971,9 +984,10 @@ pub fn compute_bounds(ccx: &CrateCtxt,
                         }
                     }
                     _ => {
-                        ccx.tcx.sess.span_err(
+                        ccx.tcx.sess.span_err_with_code(
                             (*b).span, ~"type parameter bounds must be \
-                                         trait types");
+                                         trait types",
+                            diagnostics::not_a_trait);
                         ~[]
                     }
                 }
@@ -253,6 +253,7 @@ pub use middle::typeck::infer::resolve::{resolve_ivar, resolve_all};
 pub use middle::typeck::infer::resolve::{resolve_nested_tvar};
 pub use middle::typeck::infer::resolve::{resolve_rvar};
 
+use diagnostics;
 use middle::ty::{TyVid, IntVid, FloatVid, RegionVid, Vid};
 use middle::ty;
 use middle::typeck::check::regionmanip::{replace_bound_regions_in_fn_sig};
@@ -471,9 +472,10 @@ fn resolve_borrowings(cx: @mut InferCtxt) {
 
           Err(e) => {
             let str = fixup_err_to_str(e);
-            cx.tcx.sess.span_err(
+            cx.tcx.sess.span_err_with_code(
                 item.span,
-                fmt!("could not resolve lifetime for borrow: %s", str));
+                fmt!("could not resolve lifetime for borrow: %s", str),
+                diagnostics::lifetime_inference_failed);
           }
         }
     }
@@ -707,7 +709,9 @@ pub impl InferCtxt {
         }
     }
 
-    fn type_error_message(@mut self, sp: span, mk_msg: &fn(~str) -> ~str,
+    // `code` is one of the error codes in `diagnostics`
+    fn type_error_message(@mut self, sp: span, code: &str,
+                          mk_msg: &fn(~str) -> ~str,
                           actual_ty: ty::t, err: Option<&ty::type_err>) {
         let actual_ty = self.resolve_type_vars_if_possible(actual_ty);
 
@@ -718,9 +722,10 @@ pub impl InferCtxt {
         let error_str = err.map_default(~"", |t_err|
                          fmt!(" (%s)",
                               ty::type_err_to_str(self.tcx, *t_err)));
-        self.tcx.sess.span_err(sp,
+        self.tcx.sess.span_err_with_code(sp,
            fmt!("%s%s", mk_msg(self.ty_to_str(actual_ty)),
-                error_str));
+                error_str),
+           code);
         for err.each |err| {
             ty::note_and_explain_type_err(self.tcx, *err)
         }
@@ -741,7 +746,8 @@ pub impl InferCtxt {
                 }
             }
         };
-        self.type_error_message(sp, mk_msg, a, Some(err));
+        self.type_error_message(sp, diagnostics::mismatched_types,
+                                mk_msg, a, Some(err));
     }
 
     fn replace_bound_regions_with_fresh_regions(@mut self,
@@ -538,6 +538,7 @@ more convincing in the future.
 
 use core::prelude::*;
 
+use diagnostics;
 use middle::region::is_subregion_of;
 use middle::region;
 use middle::ty;
@@ -885,9 +886,10 @@ pub impl RegionVarBindings {
                 // used in some type, and hence all lifetime variables
                 // should ultimately have some bounds.
 
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     self.var_spans[rid.to_uint()],
-                    fmt!("Unconstrained region variable #%u", rid.to_uint()));
+                    fmt!("Unconstrained region variable #%u", rid.to_uint()),
+                    diagnostics::lifetime_inference_failed);
 
                 // Touch of a hack: to suppress duplicate messages,
                 // replace the NoValue entry with ErrorValue.
@@ -1169,7 +1171,8 @@ priv impl RegionVarBindings {
 
     fn report_type_error(&mut self, span: span, terr: &ty::type_err) {
         let terr_str = ty::type_err_to_str(self.tcx, terr);
-        self.tcx.sess.span_err(span, terr_str);
+        self.tcx.sess.span_err_with_code(
+            span, terr_str, diagnostics::lifetime_inference_failed);
     }
 }
 
@@ -1524,10 +1527,11 @@ pub impl RegionVarBindings {
                         return;
                     }
 
-                    self.tcx.sess.span_err(
+                    self.tcx.sess.span_err_with_code(
                         self.var_spans[node_idx.to_uint()],
                         fmt!("cannot infer an appropriate lifetime \
-                              due to conflicting requirements"));
+                              due to conflicting requirements"),
+                        diagnostics::lifetime_inference_failed);
 
                     note_and_explain_region(
                         self.tcx,
@@ -1577,10 +1581,11 @@ pub impl RegionVarBindings {
                         return;
                     }
 
-                    self.tcx.sess.span_err(
+                    self.tcx.sess.span_err_with_code(
                         self.var_spans[node_idx.to_uint()],
                         fmt!("cannot infer an appropriate lifetime \
-                              due to conflicting requirements"));
+                              due to conflicting requirements"),
+                        diagnostics::lifetime_inference_failed);
 
                     note_and_explain_region(
                         self.tcx,
@@ -50,6 +50,8 @@ independently:
 
 use core::prelude::*;
 
+use diagnostics;
+use driver::session;
 use middle::resolve;
 use middle::ty::{ty_param_substs_and_ty, vstore_uniq};
 use middle::ty;
@@ -242,8 +244,9 @@ pub fn require_same_types(
     match infer::mk_eqty(l_infcx, t1_is_expected, span, t1, t2) {
         result::Ok(()) => true,
         result::Err(ref terr) => {
-            l_tcx.sess.span_err(span, msg() + ~": " +
-                                ty::type_err_to_str(l_tcx, terr));
+            l_tcx.sess.span_err_with_code(
+                span, msg() + ~": " + ty::type_err_to_str(l_tcx, terr),
+                diagnostics::mismatched_types);
             ty::note_and_explain_type_err(l_tcx, terr);
             false
         }
@@ -285,10 +288,11 @@ fn check_main_fn_ty(ccx: @mut CrateCtxt,
                     match it.node {
                         ast::item_fn(_, _, ref ps, _)
                         if ps.is_parameterized() => {
-                            tcx.sess.span_err(
+                            tcx.sess.span_err_with_code(
                                 main_span,
                                 ~"main function is not allowed \
-                                  to have type parameters");
+                                  to have type parameters",
+                                diagnostics::bad_main_fn);
                             return;
                         }
                         _ => ()
@@ -300,11 +304,12 @@ fn check_main_fn_ty(ccx: @mut CrateCtxt,
             let num_args = vec::len(fn_ty.sig.inputs);
             ok &= num_args == 0u;
             if !ok {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     main_span,
                     fmt!("Wrong type in main function: found `%s`, \
                           expected `fn() -> ()`",
-                         ppaux::ty_to_str(tcx, main_t)));
+                         ppaux::ty_to_str(tcx, main_t)),
+                    diagnostics::bad_main_fn);
             }
         }
         _ => {
@@ -320,7 +325,14 @@ fn check_for_main_fn(ccx: @mut CrateCtxt) {
     if !*tcx.sess.building_library {
         match *tcx.sess.main_fn {
           Some((id, sp)) => check_main_fn_ty(ccx, id, sp),
-          None => tcx.sess.err(~"main function not found")
+          None => tcx.sess.err_with_code(
+              ~"main function not found", diagnostics::main_not_found)
+        }
+    } else if tcx.sess.opts.crate_type == session::staticlib_crate {
+        // A static library's main is optional
+        match *tcx.sess.main_fn {
+          Some((id, sp)) => check_main_fn_ty(ccx, id, sp),
+          None => ()
         }
     }
 }
@@ -14,7 +14,7 @@ use middle::ty;
 use middle::ty::{arg, canon_mode};
 use middle::ty::{bound_region, br_anon, br_named, br_self, br_cap_avoid,
                  br_fresh};
-use middle::ty::{ctxt, field, method};
+use middle::ty::ctxt;
 use middle::ty::{mt, t, param_bound, param_ty};
 use middle::ty::{re_bound, re_free, re_scope, re_infer, re_static, Region};
 use middle::ty::{ReSkolemized, ReVar};
@@ -383,16 +383,6 @@ pub fn ty_to_str(cx: ctxt, typ: t) -> ~str {
             }
         }
     }
-    fn method_to_str(cx: ctxt, m: method) -> ~str {
-        bare_fn_to_str(cx,
-                       m.fty.purity,
-                       m.fty.abi,
-                       Some(m.ident),
-                       &m.fty.sig) + ~";"
-    }
-    fn field_to_str(cx: ctxt, f: field) -> ~str {
-        return *cx.sess.str_of(f.ident) + ~": " + mt_to_str(cx, &f.mt);
-    }
 
     // if there is an id, print that instead of the structural type:
     /*for ty::type_def_id(typ).each |def_id| {
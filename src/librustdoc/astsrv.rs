@@ -15,27 +15,71 @@ Rustdoc is intended to be parallel, and the rustc AST is filled with
 shared boxes. The AST service attempts to provide a single place to
 query AST-related information, shielding the rest of Rustdoc from its
 non-sendableness.
+
+A service created with `from_file_with_links` also resolves the crate,
+against the crates it uses, so that the paths in each item's signature,
+and those written in its doc comment, can be linked to the items they
+name.
 */
 
 use core::prelude::*;
 
+use attr_parser;
+use link_pass;
 use parse;
+use unindent_pass;
 
 use core::cell::Cell;
 use core::comm::{stream, Chan, SharedChan, Port};
+use core::hashmap::linear::LinearMap;
+use core::io::WriterUtil;
+use core::io;
+use core::str;
+use core::task;
+use core::vec;
 use rustc::driver::driver;
 use rustc::driver::session::Session;
 use rustc::driver::session::{basic_options, options};
+use rustc::driver::session;
 use rustc::front;
+use rustc::metadata::{creader, csearch};
+use rustc::middle::lang_items;
+use rustc::middle::resolve;
 use syntax::ast;
 use syntax::ast_map;
+use syntax::attr;
+use syntax::codemap;
+use syntax::visit;
 use syntax;
 
 pub struct Ctxt {
     ast: @ast::crate,
-    ast_map: ast_map::map
+    ast_map: ast_map::map,
+    links: @LinkMap,
+    /// The paths written in the doc comment of each item, and in those
+    /// of its variants and methods
+    doc_links: @LinkMap
+}
+
+/// The definition a path in a signature or a doc comment names
+#[deriving_eq]
+pub enum Link {
+    /// An item of this crate
+    LocalLink(ast::node_id),
+    /// A type, trait or module of another crate, by its path, starting
+    /// with the crate name
+    ExternLink(~[~str]),
+    /// A function or constant of another crate, by its path
+    ExternValueLink(~[~str])
 }
 
+/**
+ * The paths used by the signature of each item, keyed by the item's id,
+ * with the definitions they name. Paths that name type parameters,
+ * primitive types and the like are left out.
+ */
+pub type LinkMap = LinearMap<ast::node_id, ~[(~str, Link)]>;
+
 type SrvOwner<T> = &'self fn(srv: Srv) -> T;
 pub type CtxtHandler<T> = ~fn(ctxt: Ctxt) -> T;
 type Parser = ~fn(Session, s: ~str) -> @ast::crate;
@@ -58,21 +102,34 @@ impl Clone for Srv {
 }
 
 pub fn from_str<T>(source: ~str, owner: SrvOwner<T>) -> T {
-    run(owner, copy source, parse::from_str_sess)
+    run(owner, copy source, parse::from_str_sess, None)
 }
 
 pub fn from_file<T>(file: ~str, owner: SrvOwner<T>) -> T {
-    run(owner, copy file, |sess, f| parse::from_file_sess(sess, &Path(f)))
+    run(owner, copy file, |sess, f| parse::from_file_sess(sess, &Path(f)),
+        None)
+}
+
+pub fn from_str_with_links<T>(source: ~str, owner: SrvOwner<T>) -> T {
+    run(owner, copy source, parse::from_str_sess,
+        Some(parse::from_str_sess))
+}
+
+pub fn from_file_with_links<T>(file: ~str, owner: SrvOwner<T>) -> T {
+    run(owner, copy file, |sess, f| parse::from_file_sess(sess, &Path(f)),
+        Some(|sess, f| parse::from_file_sess(sess, &Path(f))))
 }
 
-fn run<T>(owner: SrvOwner<T>, source: ~str, parse: Parser) -> T {
+fn run<T>(owner: SrvOwner<T>, source: ~str, parse: Parser,
+          link_parse: Option<Parser>) -> T {
 
     let (po, ch) = stream();
 
     let source = Cell(source);
     let parse = Cell(parse);
+    let link_parse = Cell(link_parse);
     do task::spawn {
-        act(&po, source.take(), parse.take());
+        act(&po, source.take(), parse.take(), link_parse.take());
     }
 
     let srv_ = Srv {
@@ -84,12 +141,20 @@ fn run<T>(owner: SrvOwner<T>, source: ~str, parse: Parser) -> T {
     res
 }
 
-fn act(po: &Port<Msg>, source: ~str, parse: Parser) {
+fn act(po: &Port<Msg>, source: ~str, parse: Parser,
+       link_parse: Option<Parser>) {
+    let (links, doc_links) = match link_parse {
+      Some(link_parse) => resolve_links(copy source, link_parse),
+      None => (LinearMap::new(), LinearMap::new())
+    };
+
     let sess = build_session();
 
     let ctxt = build_ctxt(
         sess,
-        parse(sess, copy source)
+        parse(sess, copy source),
+        links,
+        doc_links
     );
 
     let mut keep_going = true;
@@ -116,22 +181,310 @@ pub fn exec<T:Owned>(
 }
 
 fn build_ctxt(sess: Session,
-              ast: @ast::crate) -> Ctxt {
+              ast: @ast::crate,
+              links: LinkMap,
+              doc_links: LinkMap) -> Ctxt {
+
+    let ast = build_ast(sess, ast);
+    let ast_map = ast_map::map_crate(sess.diagnostic(), *ast);
 
+    Ctxt {
+        ast: ast,
+        ast_map: ast_map,
+        links: @links,
+        doc_links: @doc_links
+    }
+}
+
+fn build_ast(sess: Session, ast: @ast::crate) -> @ast::crate {
     use rustc::front::config;
 
     let ast = config::strip_unconfigured_items(ast);
     let ast = syntax::ext::expand::expand_crate(sess.parse_sess,
                                                 copy sess.opts.cfg, ast);
-    let ast = front::test::modify_for_testing(sess, ast);
-    let ast_map = ast_map::map_crate(sess.diagnostic(), *ast);
+    front::test::modify_for_testing(sess, ast)
+}
 
-    Ctxt {
-        ast: ast,
-        ast_map: ast_map,
+/**
+ * Resolves the crate in its own task, since a crate that doesn't resolve
+ * (usually because a crate it uses can't be found) takes its task down.
+ * The crate is parsed again there, the same way, so its node ids are the
+ * ones the service hands out. Returns the links of the signatures and
+ * those of the doc comments.
+ */
+fn resolve_links(source: ~str, parse: Parser) -> (LinkMap, LinkMap) {
+    let source = Cell(source);
+    let parse = Cell(parse);
+    let result = do task::try {
+        let sess = build_session();
+        let parse = parse.take();
+        let ast = build_ast(sess, parse(sess, source.take()));
+        let ast = front::core_inject::maybe_inject_libcore_ref(sess, ast);
+        creader::read_crates(sess.diagnostic(), *ast, sess.cstore,
+                             sess.filesearch,
+                             session::sess_os_to_meta_os(sess.targ_cfg.os),
+                             false, sess.parse_sess.interner);
+        let lang_items = lang_items::collect_language_items(ast, sess);
+        // The resolver is kept to resolve the paths in doc comments,
+        // which the def map doesn't have
+        let resolver = @mut resolve::Resolver(sess, lang_items, ast);
+        resolver.resolve();
+        (collect_links(sess, ast, *resolver.def_map),
+         collect_doc_links(sess, ast, resolver))
+    };
+    match result {
+      Ok(links) => links,
+      Err(()) => {
+        io::stderr().write_line(
+            ~"warning: couldn't resolve the crate; types won't be linked");
+        (LinearMap::new(), LinearMap::new())
+      }
+    }
+}
+
+fn collect_links(sess: Session, crate: @ast::crate,
+                 def_map: resolve::DefMap) -> LinkMap {
+    let links = @mut LinearMap::new();
+    let visitor = visit::mk_simple_visitor(@visit::SimpleVisitor {
+        visit_item: |item| {
+            match item.node {
+              ast::item_mod(_) | ast::item_foreign_mod(_) => (),
+              _ => {
+                let item_links = item_links(sess, def_map, |v| {
+                    (v.visit_item)(item, (), v)
+                });
+                links.insert(item.id, item_links);
+              }
+            }
+        },
+        visit_foreign_item: |item| {
+            let item_links = item_links(sess, def_map, |v| {
+                (v.visit_foreign_item)(item, (), v)
+            });
+            links.insert(item.id, item_links);
+        },
+        .. *visit::default_simple_visitor()
+    });
+    visit::visit_crate(*crate, (), visitor);
+    copy *links
+}
+
+/// Finds the links for the paths `walk` visits
+fn item_links(sess: Session, def_map: resolve::DefMap,
+              walk: &fn(visit::vt<()>)) -> ~[(~str, Link)] {
+    let links = @mut ~[];
+    let add_link: @fn(@ast::path, ast::node_id) = |path, id| {
+        match def_map.find(&id).chain(|def| def_link(sess, def)) {
+          Some(link) => links.push((path_str(sess, path), link)),
+          None => ()
+        }
+    };
+    let visitor = visit::mk_simple_visitor(@visit::SimpleVisitor {
+        visit_ty: |ty| {
+            match ty.node {
+              ast::ty_path(path, id) => add_link(path, id),
+              _ => ()
+            }
+        },
+        visit_item: |item| {
+            // Trait references aren't visited as types
+            match item.node {
+              ast::item_impl(_, Some(t), _, _) => add_link(t.path, t.ref_id),
+              ast::item_trait(_, ref traits, _) => {
+                for traits.each |t| { add_link(t.path, t.ref_id) }
+              }
+              _ => ()
+            }
+        },
+        .. *visit::default_simple_visitor()
+    });
+    walk(visitor);
+    copy *links
+}
+
+fn def_link(sess: Session, def: ast::def) -> Option<Link> {
+    let def_id = match def {
+      ast::def_ty(def_id) | ast::def_struct(def_id) => def_id,
+      _ => return None
+    };
+    if def_id.crate == ast::local_crate {
+        Some(LocalLink(def_id.node))
+    } else {
+        Some(ExternLink(extern_path(sess, def_id)))
     }
 }
 
+/// The path of an item of another crate, starting with the crate name
+fn extern_path(sess: Session, def_id: ast::def_id) -> ~[~str] {
+    let path = csearch::get_path(sess.cstore, def_id);
+    do path.map |elt| {
+        match *elt {
+          ast_map::path_mod(id) | ast_map::path_name(id) => {
+            copy *sess.str_of(id)
+          }
+        }
+    }
+}
+
+/**
+ * Resolves the paths written in the doc comment of each item, as if they
+ * were written where the item is. The paths that are clearly meant as
+ * links but name nothing are warned about.
+ */
+fn collect_doc_links(sess: Session, crate: @ast::crate,
+                     resolver: @mut resolve::Resolver) -> LinkMap {
+    let mut links = LinearMap::new();
+    links.insert(ast::crate_node_id,
+                 doc_links(sess, resolver, ~[], crate.node.attrs));
+    collect_mod_doc_links(sess, resolver, &mut links, ~[],
+                          &crate.node.module);
+    links
+}
+
+/// Adds the doc links of the items of `module`, which `scope` names
+fn collect_mod_doc_links(sess: Session, resolver: @mut resolve::Resolver,
+                         links: &mut LinkMap, scope: ~[ast::ident],
+                         module: &ast::_mod) {
+    for module.items.each |item| {
+        // A module's docs are written in it
+        let doc_scope = match item.node {
+          ast::item_mod(_) => vec::append_one(copy scope, item.ident),
+          _ => copy scope
+        };
+        let mut item_links =
+            doc_links(sess, resolver, copy doc_scope, item.attrs);
+        match item.node {
+          ast::item_mod(ref module) => {
+            collect_mod_doc_links(sess, resolver, links, doc_scope, module);
+          }
+          ast::item_foreign_mod(ref module) => {
+            for module.items.each |foreign_item| {
+                links.insert(foreign_item.id,
+                             doc_links(sess, resolver, copy scope,
+                                       foreign_item.attrs));
+            }
+          }
+          ast::item_enum(ref enum_def, _) => {
+            for enum_def.variants.each |variant| {
+                item_links += doc_links(sess, resolver, copy scope,
+                                        variant.node.attrs);
+            }
+          }
+          ast::item_trait(_, _, ref methods) => {
+            for methods.each |method| {
+                let attrs = match *method {
+                  ast::required(ref method) => copy method.attrs,
+                  ast::provided(method) => copy method.attrs
+                };
+                item_links += doc_links(sess, resolver, copy scope, attrs);
+            }
+          }
+          ast::item_impl(_, _, _, ref methods) => {
+            for methods.each |method| {
+                item_links += doc_links(sess, resolver, copy scope,
+                                        method.attrs);
+            }
+          }
+          _ => ()
+        }
+        links.insert(item.id, item_links);
+    }
+}
+
+/// Resolves the paths in the doc comment that `attrs` make up
+fn doc_links(sess: Session, resolver: @mut resolve::Resolver,
+             scope: ~[ast::ident],
+             attrs: &[ast::attribute]) -> ~[(~str, Link)] {
+    let text = match attr_parser::parse_desc(vec::from_slice(attrs)) {
+      Some(text) => unindent_pass::unindent(text),
+      None => return ~[]
+    };
+    let mut links = ~[];
+    let mut seen = ~[];
+    for vec::consume(link_pass::find_paths(text)) |_, (path, explicit)| {
+        if seen.contains(&path) { loop; }
+        match resolve_doc_path(sess, resolver, copy scope, path) {
+          Some(link) => links.push((copy path, link)),
+          None if explicit => {
+            sess.span_warn(doc_span(attrs, path),
+                           fmt!("unresolved link to `%s`", path));
+          }
+          None => ()
+        }
+        seen.push(path);
+    }
+    links
+}
+
+/// Resolves a path written in the module that `scope` names
+fn resolve_doc_path(sess: Session, resolver: @mut resolve::Resolver,
+                    scope: ~[ast::ident], path: &str) -> Option<Link> {
+    let scope = if str::starts_with(path, "::") { ~[] } else { scope };
+    let names = str::split_str(str::trim_left_chars(path, ~[':']), "::");
+    let idents = names.map(|name| sess.ident_of(copy *name));
+
+    // A method is linked to the type or trait it is on
+    if idents.len() > 1 {
+        let prefix = vec::slice(idents, 0, idents.len() - 1);
+        match resolver.resolve_doc_path(scope, prefix, resolve::TypeNS) {
+          Some(def @ ast::def_ty(_)) | Some(def @ ast::def_struct(_)) => {
+            return doc_def_link(sess, def);
+          }
+          _ => ()
+        }
+    }
+
+    let def = match resolver.resolve_doc_path(scope, idents,
+                                              resolve::TypeNS) {
+      None => resolver.resolve_doc_path(scope, idents, resolve::ValueNS),
+      def => def
+    };
+    def.chain(|def| doc_def_link(sess, def))
+}
+
+/// The link for a path in a doc comment that names `def`
+fn doc_def_link(sess: Session, def: ast::def) -> Option<Link> {
+    let (def_id, is_type) = match def {
+      ast::def_mod(def_id) | ast::def_foreign_mod(def_id)
+      | ast::def_ty(def_id) | ast::def_struct(def_id) => (def_id, true),
+      // Variants are documented with their enum, and static methods
+      // with their trait
+      ast::def_variant(enum_id, _) => (enum_id, true),
+      ast::def_static_method(_, Some(trait_id), _) => (trait_id, true),
+      ast::def_fn(def_id, _) | ast::def_const(def_id) => (def_id, false),
+      _ => return None
+    };
+    if def_id.crate == ast::local_crate {
+        Some(LocalLink(def_id.node))
+    } else if is_type {
+        Some(ExternLink(extern_path(sess, def_id)))
+    } else {
+        Some(ExternValueLink(extern_path(sess, def_id)))
+    }
+}
+
+/// The span of the doc attribute `path` is written in
+fn doc_span(attrs: &[ast::attribute], path: &str) -> codemap::span {
+    let doc_attrs = attr::find_attrs_by_name(attrs, ~"doc");
+    let found = do vec::find(doc_attrs) |doc_attr| {
+        let meta = attr::attr_meta(attr::desugar_doc_attr(doc_attr));
+        match attr::get_meta_item_value_str(meta) {
+          Some(text) => str::contains(*text, path),
+          None => false
+        }
+    };
+    match found {
+      Some(doc_attr) => doc_attr.span,
+      None => doc_attrs[0].span
+    }
+}
+
+fn path_str(sess: Session, path: @ast::path) -> ~str {
+    let idents = path.idents.map(|id| copy *sess.str_of(*id));
+    let path_str = str::connect(idents, ~"::");
+    if path.global { ~"::" + path_str } else { path_str }
+}
+
 fn build_session() -> Session {
     let sopts: @options = basic_options();
     let emitter = syntax::diagnostic::emit;
@@ -166,6 +519,86 @@ fn should_ignore_external_import_paths_that_dont_exist() {
     from_str(source, |_srv| { } )
 }
 
+#[test]
+fn should_not_resolve_links_by_default() {
+    let source = ~"struct S { x: int } fn a(s: S) { }";
+    do from_str(source) |srv| {
+        do exec(srv) |ctxt| {
+            fail_unless!(ctxt.links.is_empty());
+        }
+    }
+}
+
+#[test]
+fn should_link_paths_to_local_items() {
+    let source = ~"struct S { x: int } fn a(s: S) { }";
+    do from_str_with_links(source) |srv| {
+        do exec(srv) |ctxt| {
+            let s_id = ctxt.ast.node.module.items[0].id;
+            let a_id = ctxt.ast.node.module.items[1].id;
+            fail_unless!(*ctxt.links.get(&a_id) ==
+                         ~[(~"S", LocalLink(s_id))]);
+        }
+    }
+}
+
+#[test]
+fn should_link_trait_references() {
+    let source = ~"trait T { } impl T for int { }";
+    do from_str_with_links(source) |srv| {
+        do exec(srv) |ctxt| {
+            let t_id = ctxt.ast.node.module.items[0].id;
+            let impl_id = ctxt.ast.node.module.items[1].id;
+            fail_unless!(*ctxt.links.get(&impl_id) ==
+                         ~[(~"T", LocalLink(t_id))]);
+        }
+    }
+}
+
+#[test]
+fn should_link_paths_in_docs() {
+    let source = ~"#[doc = \"See `S` and [a::f]\"] struct S { x: int } \
+                   mod a { pub fn f() { } }";
+    do from_str_with_links(source) |srv| {
+        do exec(srv) |ctxt| {
+            let s_id = ctxt.ast.node.module.items[0].id;
+            let f_id = match ctxt.ast.node.module.items[1].node {
+              ast::item_mod(ref module) => module.items[0].id,
+              _ => fail!()
+            };
+            fail_unless!(*ctxt.doc_links.get(&s_id) ==
+                         ~[(~"S", LocalLink(s_id)),
+                           (~"a::f", LocalLink(f_id))]);
+        }
+    }
+}
+
+#[test]
+fn should_link_paths_in_method_docs_to_their_type() {
+    let source = ~"struct S { x: int } \
+                   impl S { #[doc = \"Unlike `S::b`\"] fn a(&self) { } \
+                   fn b(&self) { } }";
+    do from_str_with_links(source) |srv| {
+        do exec(srv) |ctxt| {
+            let s_id = ctxt.ast.node.module.items[0].id;
+            let impl_id = ctxt.ast.node.module.items[1].id;
+            fail_unless!(*ctxt.doc_links.get(&impl_id) ==
+                         ~[(~"S::b", LocalLink(s_id))]);
+        }
+    }
+}
+
+#[test]
+fn should_not_link_unresolved_paths_in_docs() {
+    let source = ~"#[doc = \"See `b` and [c]\"] fn a() { }";
+    do from_str_with_links(source) |srv| {
+        do exec(srv) |ctxt| {
+            let a_id = ctxt.ast.node.module.items[0].id;
+            fail_unless!(ctxt.doc_links.get(&a_id).is_empty());
+        }
+    }
+}
+
 #[test]
 fn srv_should_return_request_result() {
     let source = ~"fn a() { }";
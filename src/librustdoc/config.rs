@@ -12,6 +12,7 @@ use core::prelude::*;
 
 use core::cell::Cell;
 use core::cmp;
+use core::float;
 use core::os;
 use core::result;
 use core::run;
@@ -25,7 +26,9 @@ pub enum OutputFormat {
     /// Markdown
     pub Markdown,
     /// HTML, via markdown and pandoc
-    pub PandocHtml
+    pub PandocHtml,
+    /// HTML, written by rustdoc itself
+    pub NativeHtml
 }
 
 impl cmp::Eq for OutputFormat {
@@ -56,7 +59,17 @@ pub struct Config {
     output_dir: Path,
     output_format: OutputFormat,
     output_style: OutputStyle,
-    pandoc_cmd: Option<~str>
+    pandoc_cmd: Option<~str>,
+    /// Compile and run the code blocks in the docs instead of writing them
+    test: bool,
+    /// Where to look for the crates the code blocks use
+    lib_paths: ~[Path],
+    /// Count the documented public items instead of writing the docs
+    coverage: bool,
+    /// Print the coverage report as JSON instead of as a table
+    coverage_json: bool,
+    /// The percentage of public items that must be documented
+    coverage_min: Option<float>
 }
 
 impl Clone for Config {
@@ -67,6 +80,11 @@ fn opt_output_dir() -> ~str { ~"output-dir" }
 fn opt_output_format() -> ~str { ~"output-format" }
 fn opt_output_style() -> ~str { ~"output-style" }
 fn opt_pandoc_cmd() -> ~str { ~"pandoc-cmd" }
+fn opt_test() -> ~str { ~"test" }
+fn opt_lib_path() -> ~str { ~"L" }
+fn opt_coverage() -> ~str { ~"coverage" }
+fn opt_coverage_json() -> ~str { ~"coverage-json" }
+fn opt_coverage_min() -> ~str { ~"coverage-min" }
 fn opt_help() -> ~str { ~"h" }
 
 fn opts() -> ~[(getopts::Opt, ~str)] {
@@ -74,11 +92,21 @@ fn opts() -> ~[(getopts::Opt, ~str)] {
         (getopts::optopt(opt_output_dir()),
          ~"--output-dir <val>     put documents here"),
         (getopts::optopt(opt_output_format()),
-         ~"--output-format <val>  either 'markdown' or 'html'"),
+         ~"--output-format <val>  'markdown', 'html' or 'native-html'"),
         (getopts::optopt(opt_output_style()),
          ~"--output-style <val>   either 'doc-per-crate' or 'doc-per-mod'"),
         (getopts::optopt(opt_pandoc_cmd()),
          ~"--pandoc-cmd <val>     the command for running pandoc"),
+        (getopts::optflag(opt_test()),
+         ~"--test                 run the code examples, as tests"),
+        (getopts::optmulti(opt_lib_path()),
+         ~"-L <path>              where the examples find their crates"),
+        (getopts::optflag(opt_coverage()),
+         ~"--coverage             count the documented public items"),
+        (getopts::optflag(opt_coverage_json()),
+         ~"--coverage-json        print the coverage as JSON"),
+        (getopts::optopt(opt_coverage_min()),
+         ~"--coverage-min <val>   fail below this percentage of coverage"),
         (getopts::optflag(opt_help()),
          ~"-h                     print help")
     ]
@@ -101,7 +129,12 @@ pub fn default_config(input_crate: &Path) -> Config {
         output_dir: Path("."),
         output_format: PandocHtml,
         output_style: DocPerMod,
-        pandoc_cmd: None
+        pandoc_cmd: None,
+        test: false,
+        lib_paths: ~[],
+        coverage: false,
+        coverage_json: false,
+        coverage_min: None
     }
 }
 
@@ -191,6 +224,40 @@ fn config_from_opts(
             }
         }
     };
+    let result = do result::chain(result) |config| {
+        let lib_paths = getopts::opt_strs(matches, opt_lib_path());
+        result::Ok(Config {
+            test: getopts::opt_present(matches, opt_test()),
+            lib_paths: lib_paths.map(|s| Path(*s)),
+            .. config
+        })
+    };
+    let result = do result::chain(result) |config| {
+        let coverage_min =
+            getopts::opt_maybe_str(matches, opt_coverage_min());
+        do coverage_min.map_default(result::Ok(copy config))
+            |coverage_min| {
+            do result::chain(parse_coverage_min(*coverage_min))
+                |coverage_min| {
+                result::Ok(Config {
+                    coverage_min: Some(coverage_min),
+                    .. copy config
+                })
+            }
+        }
+    };
+    let result = do result::chain(result) |config| {
+        let coverage_json = getopts::opt_present(matches,
+                                                 opt_coverage_json());
+        // Asking for the report in some form asks for the report
+        let coverage = getopts::opt_present(matches, opt_coverage())
+            || coverage_json || config.coverage_min.is_some();
+        result::Ok(Config {
+            coverage: coverage,
+            coverage_json: coverage_json,
+            .. config
+        })
+    };
     let program_output = Cell(program_output);
     let result = do result::chain(result) |config| {
         let pandoc_cmd = getopts::opt_maybe_str(matches, opt_pandoc_cmd());
@@ -210,6 +277,7 @@ fn parse_output_format(output_format: &str) -> Result<OutputFormat, ~str> {
     match output_format.to_str() {
       ~"markdown" => result::Ok(Markdown),
       ~"html" => result::Ok(PandocHtml),
+      ~"native-html" => result::Ok(NativeHtml),
       _ => result::Err(fmt!("unknown output format '%s'", output_format))
     }
 }
@@ -222,6 +290,16 @@ fn parse_output_style(output_style: &str) -> Result<OutputStyle, ~str> {
     }
 }
 
+fn parse_coverage_min(coverage_min: &str) -> Result<float, ~str> {
+    match float::from_str(coverage_min) {
+      Some(percent) if percent >= 0.0 && percent <= 100.0 => {
+        result::Ok(percent)
+      }
+      _ => result::Err(fmt!("invalid coverage percentage '%s'",
+                            coverage_min))
+    }
+}
+
 fn maybe_find_pandoc(
     config: &Config,
     maybe_pandoc_cmd: Option<~str>,
@@ -342,6 +420,14 @@ fn should_set_output_format_to_pandoc_html_if_requested() {
     fail_unless!(config.get().output_format == PandocHtml);
 }
 
+#[test]
+fn should_set_output_format_to_native_html_if_requested() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--output-format", ~"native-html"
+    ]);
+    fail_unless!(config.get().output_format == NativeHtml);
+}
+
 #[test]
 fn should_error_on_bogus_format() {
     let config = test::parse_config(~[
@@ -393,3 +479,64 @@ fn should_set_pandoc_command_when_using_pandoc() {
     let config = test::parse_config(~[~"rustdoc", ~"crate.rc"]);
     fail_unless!(config.get().pandoc_cmd == Some(~"pandoc"));
 }
+
+#[test]
+fn should_not_need_pandoc_for_native_html() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--output-format", ~"native-html"
+    ]);
+    fail_unless!(config.get().pandoc_cmd.is_none());
+}
+
+#[test]
+fn should_not_run_tests_by_default() {
+    let config = test::parse_config(~[~"rustdoc", ~"crate.rc"]);
+    fail_unless!(!config.get().test);
+}
+
+#[test]
+fn should_run_tests_if_requested() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--test", ~"-L", ~"a", ~"-L", ~"b"
+    ]);
+    fail_unless!(config.get().test);
+    fail_unless!(config.get().lib_paths == ~[Path("a"), Path("b")]);
+}
+
+#[test]
+fn should_not_report_coverage_by_default() {
+    let config = test::parse_config(~[~"rustdoc", ~"crate.rc"]);
+    fail_unless!(!config.get().coverage);
+    fail_unless!(config.get().coverage_min.is_none());
+}
+
+#[test]
+fn should_set_coverage_options() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--coverage", ~"--coverage-json",
+        ~"--coverage-min", ~"87.5"
+    ]);
+    fail_unless!(config.get().coverage);
+    fail_unless!(config.get().coverage_json);
+    fail_unless!(config.get().coverage_min == Some(87.5));
+}
+
+#[test]
+fn should_report_coverage_for_coverage_options() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--coverage-json"
+    ]);
+    fail_unless!(config.get().coverage);
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--coverage-min", ~"50"
+    ]);
+    fail_unless!(config.get().coverage);
+}
+
+#[test]
+fn should_error_with_bad_coverage_min() {
+    let config = test::parse_config(~[
+        ~"rustdoc", ~"crate.rc", ~"--coverage", ~"--coverage-min", ~"120"
+    ]);
+    fail_unless!(config.get_err() == ~"invalid coverage percentage '120'");
+}
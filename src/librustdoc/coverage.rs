@@ -0,0 +1,318 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Counts the public items that are documented and those that aren't, by
+module and by kind, for `rustdoc --coverage`.
+
+Functions, structs, enums, traits, methods and constants are counted.
+The methods of trait impls are left out, since they are documented by
+their trait. The report is printed as a table, or as JSON, and rustdoc
+exits with an error when less of the crate is documented than
+`--coverage-min` asks for.
+*/
+
+use core::prelude::*;
+
+use config::Config;
+use doc::ItemUtils;
+use doc;
+
+use core::hashmap::linear::LinearMap;
+use core::io::WriterUtil;
+use core::io;
+use core::os;
+use core::str;
+use core::uint;
+use core::vec;
+use std::json;
+use syntax::ast;
+
+/// How many of the items of one kind in one module are documented
+#[deriving_eq]
+pub struct Count {
+    /// The path of the module, starting with the crate name
+    module: ~str,
+    kind: ~str,
+    documented: uint,
+    total: uint
+}
+
+/// The kinds of item counted, in the order they are reported
+fn kinds() -> ~[~str] {
+    ~[~"fn", ~"struct", ~"enum", ~"trait", ~"method", ~"const"]
+}
+
+/**
+ * Prints the coverage of `doc`, which should hold only public items, and
+ * sets an error exit status if it is below `config.coverage_min`
+ */
+pub fn report(config: Config, doc: doc::Doc) {
+    let counts = count(&doc);
+    if config.coverage_json {
+        io::println(to_json(counts));
+    } else {
+        io::println(to_table(counts));
+    }
+
+    let coverage = coverage(counts);
+    match config.coverage_min {
+      Some(min) if coverage < min => {
+        io::stderr().write_line(
+            fmt!("error: %.1f%% of the public items are documented, \
+                  and %.1f%% must be", coverage, min));
+        os::set_exit_status(1);
+      }
+      _ => ()
+    }
+}
+
+/// Counts the items of each kind in each module of `doc`
+pub fn count(doc: &doc::Doc) -> ~[Count] {
+    let mut counts = ~[];
+    count_mod(&mut counts, &doc.cratemod());
+    counts
+}
+
+fn count_mod(counts: &mut ~[Count], doc: &doc::ModDoc) {
+    let module = if doc.id() == ast::crate_node_id {
+        doc.name()
+    } else {
+        str::connect(doc.path() + ~[doc.name()], ~"::")
+    };
+
+    // The kind of each item, and whether it is documented
+    let mut items = ~[];
+    for doc.items.each |item| {
+        match *item {
+          doc::FnTag(_) => items.push((~"fn", is_documented(item.desc()))),
+          doc::StructTag(_) => {
+            items.push((~"struct", is_documented(item.desc())));
+          }
+          doc::EnumTag(_) => {
+            items.push((~"enum", is_documented(item.desc())));
+          }
+          doc::ConstTag(_) => {
+            items.push((~"const", is_documented(item.desc())));
+          }
+          doc::TraitTag(ref trait_doc) => {
+            items.push((~"trait", is_documented(item.desc())));
+            for trait_doc.methods.each |method| {
+                items.push((~"method", is_documented(copy method.desc)));
+            }
+          }
+          doc::ImplTag(ref impl_doc) if impl_doc.trait_types.is_empty() => {
+            for impl_doc.methods.each |method| {
+                items.push((~"method", is_documented(copy method.desc)));
+            }
+          }
+          doc::NmodTag(ref nmod_doc) => {
+            for nmod_doc.fns.each |fn_doc| {
+                items.push((~"fn", is_documented(fn_doc.desc())));
+            }
+          }
+          _ => ()
+        }
+    }
+
+    for kinds().each |kind| {
+        let of_kind = items.filtered(|item| item.first() == *kind);
+        if !of_kind.is_empty() {
+            counts.push(Count {
+                module: copy module,
+                kind: copy *kind,
+                documented: of_kind.filtered(|item| item.second()).len(),
+                total: of_kind.len()
+            });
+        }
+    }
+
+    for doc.mods().each |mod_doc| {
+        count_mod(counts, mod_doc);
+    }
+}
+
+fn is_documented(desc: Option<~str>) -> bool {
+    match desc {
+      Some(desc) => !str::is_whitespace(desc),
+      None => false
+    }
+}
+
+/// The percentage of the items counted that are documented
+pub fn coverage(counts: &[Count]) -> float {
+    let (documented, total) = total(counts);
+    percent(documented, total)
+}
+
+fn percent(documented: uint, total: uint) -> float {
+    if total == 0 {
+        100.0
+    } else {
+        (documented as float) * 100.0 / (total as float)
+    }
+}
+
+/**
+ * The counts as a table, with a row for each kind of item in each module,
+ * then the totals for each kind and for the crate
+ */
+pub fn to_table(counts: &[Count]) -> ~str {
+    let row = |module: &str, kind: &str, documented: uint, total: uint| {
+        ~[str::from_slice(module), str::from_slice(kind),
+          documented.to_str(), total.to_str(),
+          fmt!("%.1f%%", percent(documented, total))]
+    };
+
+    let mut rows = ~[~[~"module", ~"kind", ~"documented", ~"total",
+                       ~"coverage"]];
+    for counts.each |count| {
+        rows.push(row(count.module, count.kind, count.documented,
+                      count.total));
+    }
+    for kinds().each |kind| {
+        let of_kind = counts.filtered(|count| count.kind == *kind);
+        if !of_kind.is_empty() {
+            let (documented, total) = total(of_kind);
+            rows.push(row("total", *kind, documented, total));
+        }
+    }
+    let (documented, total) = total(counts);
+    rows.push(row("total", "all", documented, total));
+
+    let widths = do vec::from_fn(rows[0].len()) |i| {
+        vec::foldl(0, rows, |width, row| uint::max(width, row[i].len()))
+    };
+    let lines = do rows.map |row| {
+        let cells = do vec::mapi(*row) |i, cell| {
+            let padding = str::repeat(" ", widths[i] - cell.len());
+            // The module and kind are text, and the rest numbers
+            if i < 2 { *cell + padding } else { padding + *cell }
+        };
+        str::connect(cells, ~"  ")
+    };
+    str::connect(lines, ~"\n")
+}
+
+/// How many of the items counted are documented, and how many there are
+fn total(counts: &[Count]) -> (uint, uint) {
+    let mut documented = 0;
+    let mut total = 0;
+    for counts.each |count| {
+        documented += count.documented;
+        total += count.total;
+    }
+    (documented, total)
+}
+
+/// The counts and the coverage of the crate as a JSON object
+pub fn to_json(counts: &[Count]) -> ~str {
+    let count_objs = do counts.map |count| {
+        let mut obj = LinearMap::new();
+        obj.insert(~"module", json::String(copy count.module));
+        obj.insert(~"kind", json::String(copy count.kind));
+        obj.insert(~"documented", json::Number(count.documented as float));
+        obj.insert(~"total", json::Number(count.total as float));
+        json::Object(~obj)
+    };
+    let mut obj = LinearMap::new();
+    obj.insert(~"counts", json::List(count_objs));
+    obj.insert(~"coverage", json::Number(coverage(counts)));
+    json::to_str(&json::Object(~obj))
+}
+
+#[test]
+fn should_count_items_by_kind() {
+    let counts = test::count(
+        ~"#[doc = \"A\"] pub fn a() { } pub fn b() { } \
+         pub struct S { x: int } #[doc = \"C\"] pub const c: int = 0;");
+    fail_unless!(counts == ~[
+        Count { module: ~"test", kind: ~"fn", documented: 1, total: 2 },
+        Count { module: ~"test", kind: ~"struct", documented: 0, total: 1 },
+        Count { module: ~"test", kind: ~"const", documented: 1, total: 1 }
+    ]);
+}
+
+#[test]
+fn should_count_items_by_module() {
+    let counts = test::count(~"pub mod a { pub mod b { pub fn c() { } } }");
+    fail_unless!(counts == ~[
+        Count { module: ~"a::b", kind: ~"fn", documented: 0, total: 1 }
+    ]);
+}
+
+#[test]
+fn should_not_count_private_items() {
+    let counts = test::count(~"fn a() { } mod b { pub fn c() { } }");
+    fail_unless!(counts.is_empty());
+}
+
+#[test]
+fn should_count_methods() {
+    let counts = test::count(
+        ~"pub trait T { #[doc = \"A\"] fn a(&self); } \
+         pub struct S { x: int } \
+         pub impl S { fn b(&self) { } } impl T for S { fn a(&self) { } }");
+    fail_unless!(counts[2] ==
+        Count { module: ~"test", kind: ~"method", documented: 1, total: 2 });
+}
+
+#[test]
+fn should_report_full_coverage_of_nothing() {
+    fail_unless!(coverage(~[]) == 100.0);
+}
+
+#[test]
+fn should_write_table() {
+    let table = to_table(~[
+        Count { module: ~"test", kind: ~"fn", documented: 1, total: 4 }
+    ]);
+    fail_unless!(table == ~"\
+        module  kind  documented  total  coverage\n\
+        test    fn             1      4     25.0%\n\
+        total   fn             1      4     25.0%\n\
+        total   all            1      4     25.0%");
+}
+
+#[test]
+fn should_write_json() {
+    let report = to_json(~[
+        Count { module: ~"test", kind: ~"fn", documented: 1, total: 2 }
+    ]);
+    let report = json::from_str(report).get();
+    match report {
+      json::Object(obj) => {
+        fail_unless!(*obj.get(&~"coverage") == json::Number(50.0));
+      }
+      _ => fail!()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use astsrv;
+    use attr_pass;
+    use coverage::{Count, count};
+    use extract;
+    use path_pass;
+    use prune_private_pass;
+    use tystr_pass;
+
+    pub fn count(source: ~str) -> ~[Count] {
+        do astsrv::from_str(copy source) |srv| {
+            let doc = extract::from_srv(srv.clone(), ~"test");
+            let doc = (tystr_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (path_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (attr_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (prune_private_pass::mk_pass().f)(srv.clone(), doc);
+            count(&doc)
+        }
+    }
+}
@@ -21,6 +21,9 @@
  * tests on this file
  */
 
+// Nothing here is used, it is only documented
+#[allow(dead_code)];
+
 use core::prelude::*;
 
 /// The base price of a muffin on a non-holiday
@@ -106,7 +106,8 @@ pub type FnDoc = SimpleItemDoc;
 #[deriving_eq]
 pub struct EnumDoc {
     item: ItemDoc,
-    variants: ~[VariantDoc]
+    variants: ~[VariantDoc],
+    impls: ~[ImplDoc]
 }
 
 #[deriving_eq]
@@ -119,7 +120,9 @@ pub struct VariantDoc {
 #[deriving_eq]
 pub struct TraitDoc {
     item: ItemDoc,
-    methods: ~[MethodDoc]
+    methods: ~[MethodDoc],
+    // The impls of this trait anywhere in the crate
+    implementors: ~[ImplDoc]
 }
 
 #[deriving_eq]
@@ -140,13 +143,19 @@ pub struct ImplDoc {
     methods: ~[MethodDoc]
 }
 
-pub type TyDoc = SimpleItemDoc;
+#[deriving_eq]
+pub struct TyDoc {
+    item: ItemDoc,
+    sig: Option<~str>,
+    impls: ~[ImplDoc]
+}
 
 #[deriving_eq]
 pub struct StructDoc {
     item: ItemDoc,
     fields: ~[~str],
-    sig: Option<~str>
+    sig: Option<~str>,
+    impls: ~[ImplDoc]
 }
 
 #[deriving_eq]
@@ -401,6 +410,10 @@ impl Item for ImplDoc {
     pure fn item(&self) -> ItemDoc { copy self.item }
 }
 
+impl Item for TyDoc {
+    pure fn item(&self) -> ItemDoc { copy self.item }
+}
+
 impl Item for StructDoc {
     pure fn item(&self) -> ItemDoc { copy self.item }
 }
@@ -0,0 +1,493 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Compiles and runs the code examples in the docs, for `rustdoc --test`.
+
+Every indented code block is an example, as is every fenced block whose
+info string says nothing but `rust`, `ignore`, `should_fail` or `no_run`.
+A fenced block tagged anything else (`notrust`, `sh`) is left alone.
+Lines beginning with `# ` are compiled but are meant to be hidden from
+readers, as with the tutorial's examples.
+
+Each example is wrapped in `fn main` unless it has one and linked
+against the crate being documented. It is then built by running the
+rustc that sits next to rustdoc, or the one on the PATH, and run as one
+test of a `std::test` run. Running rustc keeps the tests, which run in
+parallel tasks, from sharing the compiler's state. `ignore` examples are
+counted but not built, `no_run` ones are built but not run, and
+`should_fail` ones must exit with an error.
+*/
+
+use core::prelude::*;
+
+use config::Config;
+use doc::{ItemUtils, Item};
+use doc;
+
+use core::io::WriterUtil;
+use core::io;
+use core::os;
+use core::run;
+use core::str;
+use core::uint;
+use core::unstable::finally::Finally;
+use core::vec;
+use std::tempfile;
+use std::test;
+
+#[deriving_eq]
+pub struct CodeBlock {
+    code: ~str,
+    ignore: bool,
+    should_fail: bool,
+    no_run: bool
+}
+
+/// Finds the examples in a piece of doc text
+pub fn code_blocks(text: &str) -> ~[CodeBlock] {
+    let mut blocks = ~[];
+    // The marker of the fenced block we are in, and what it holds if it
+    // is an example
+    let mut fence = None;
+    let mut fenced = None;
+    let mut indented = false;
+    let mut prev_blank = true;
+    let mut code = ~[];
+
+    for str::lines_any(text).each |line| {
+        let line: &str = *line;
+        if fence.is_some() {
+            let closed = match fence {
+                Some(ref marker) => str::starts_with(line, *marker),
+                None => false
+            };
+            if closed {
+                push_block(&mut blocks, &fenced, code);
+                fence = None;
+                fenced = None;
+                code = ~[];
+                prev_blank = false;
+            } else {
+                code.push(line.to_owned());
+            }
+        } else if indented
+            && (is_indented(line) || str::is_whitespace(line)) {
+            code.push(unindent_line(line));
+        } else if prev_blank && is_indented(line) {
+            indented = true;
+            code = ~[unindent_line(line)];
+        } else {
+            if indented {
+                push_block(&mut blocks, &Some(rust_block()), code);
+                indented = false;
+                code = ~[];
+            }
+            match fence_start(line) {
+                Some((marker, info)) => {
+                    fence = Some(marker);
+                    fenced = block_kind(info);
+                }
+                None => ()
+            }
+            prev_blank = str::is_whitespace(line);
+        }
+    }
+    // A fence left open runs to the end of the text
+    if indented {
+        push_block(&mut blocks, &Some(rust_block()), code);
+    } else if fence.is_some() {
+        push_block(&mut blocks, &fenced, code);
+    }
+    blocks
+}
+
+fn rust_block() -> CodeBlock {
+    CodeBlock {
+        code: ~"",
+        ignore: false,
+        should_fail: false,
+        no_run: false
+    }
+}
+
+fn push_block(blocks: &mut ~[CodeBlock], kind: &Option<CodeBlock>,
+              code: ~[~str]) {
+    let mut code = code;
+    while !code.is_empty() && str::is_whitespace(*code.last()) {
+        code.pop();
+    }
+    match *kind {
+        Some(ref kind) if !code.is_empty() => {
+            blocks.push(CodeBlock {
+                code: str::connect(code.map(|line| unhide(*line)), "\n"),
+                .. copy *kind
+            });
+        }
+        _ => ()
+    }
+}
+
+pub fn is_indented(line: &str) -> bool {
+    str::starts_with(line, "    ") || str::starts_with(line, "\t")
+}
+
+fn unindent_line(line: &str) -> ~str {
+    if str::starts_with(line, "\t") {
+        str::slice(line, 1, line.len())
+    } else if str::starts_with(line, "    ") {
+        str::slice(line, 4, line.len())
+    } else {
+        ~""
+    }
+}
+
+/// True if a line of an example is hidden from readers
+pub fn is_hidden(line: &str) -> bool {
+    line == "#" || str::starts_with(line, "# ")
+}
+
+/// Strips the `# ` from a line hidden from readers
+fn unhide(line: &str) -> ~str {
+    if is_hidden(line) {
+        str::slice(line, uint::min(2, line.len()), line.len())
+    } else {
+        line.to_owned()
+    }
+}
+
+/// The marker and the info string of a line opening a fenced block
+pub fn fence_start(line: &str) -> Option<(~str, ~str)> {
+    if !str::starts_with(line, "```") && !str::starts_with(line, "~~~") {
+        return None;
+    }
+    let c = line[0] as char;
+    let len = str::find(line, |ch| ch != c).get_or_default(line.len());
+    Some((str::slice(line, 0, len),
+          str::trim(str::slice(line, len, line.len()))))
+}
+
+/// What a fenced block with the info string `info` holds, if an example
+fn block_kind(info: &str) -> Option<CodeBlock> {
+    let mut block = rust_block();
+    let tags = str::split(info, |c| {
+        c == ' ' || c == '\t' || c == ',' || c == '{' || c == '}'
+    });
+    for tags.each |tag| {
+        match str::trim_left_chars(*tag, ~['.']) {
+            ~"" | ~"rust" => (),
+            ~"ignore" | ~"xfail-test" => block.ignore = true,
+            ~"should_fail" => block.should_fail = true,
+            ~"no_run" => block.no_run = true,
+            _ => return None
+        }
+    }
+    Some(block)
+}
+
+/// True if a fenced block with the info string `info` is an example
+pub fn is_example(info: &str) -> bool {
+    block_kind(info).is_some()
+}
+
+/// Makes an example into a program using the crate `crate_name`
+pub fn make_program(crate_name: &str, code: &str) -> ~str {
+    let mut program = ~"";
+    if crate_name != "core"
+        && !str::contains(code, fmt!("extern mod %s", crate_name)) {
+        program += fmt!("extern mod %s;\n", crate_name);
+    }
+    if has_main(code) {
+        program += code;
+        program += "\n";
+    } else {
+        program += fmt!("fn main() {\n%s\n}\n", code);
+    }
+    program
+}
+
+/// True if a line of `code` starts the definition of `main`
+fn has_main(code: &str) -> bool {
+    for str::lines_any(code).each |line| {
+        let mut line = str::trim_left(*line);
+        if str::starts_with(line, "pub ") {
+            line = str::trim_left(str::slice(line, 4, line.len()));
+        }
+        if str::starts_with(line, "fn main") {
+            let rest = str::trim_left(str::slice(line, 7, line.len()));
+            if str::starts_with(rest, "(") { return true; }
+        }
+    }
+    false
+}
+
+/// The examples in the docs, each named after the item it documents
+fn find_tests(doc: &doc::Doc) -> ~[(~str, CodeBlock)] {
+    let mut tests = ~[];
+    mod_tests(&mut tests, &doc.cratemod());
+    tests
+}
+
+fn mod_tests(tests: &mut ~[(~str, CodeBlock)], doc: &doc::ModDoc) {
+    item_tests(tests, &doc.item);
+    for doc.items.each |tag| {
+        match *tag {
+            doc::ModTag(ref doc) => mod_tests(tests, doc),
+            doc::NmodTag(ref doc) => {
+                item_tests(tests, &doc.item);
+                for doc.fns.each |f| {
+                    item_tests(tests, &f.item);
+                }
+            }
+            doc::EnumTag(ref doc) => {
+                item_tests(tests, &doc.item);
+                for doc.variants.each |variant| {
+                    text_tests(tests, member_name(&doc.item, variant.name),
+                               &variant.desc, ~[]);
+                }
+            }
+            doc::TraitTag(ref doc) => {
+                item_tests(tests, &doc.item);
+                method_tests(tests, &doc.item, doc.methods);
+            }
+            doc::ImplTag(ref doc) => {
+                item_tests(tests, &doc.item);
+                method_tests(tests, &doc.item, doc.methods);
+            }
+            _ => item_tests(tests, &tag.item())
+        }
+    }
+}
+
+fn item_tests(tests: &mut ~[(~str, CodeBlock)], item: &doc::ItemDoc) {
+    let name = str::connect(item.path + ~[copy item.name], "::");
+    text_tests(tests, name, &item.desc, item.sections);
+}
+
+fn method_tests(tests: &mut ~[(~str, CodeBlock)], item: &doc::ItemDoc,
+                methods: &[doc::MethodDoc]) {
+    for methods.each |method| {
+        text_tests(tests, member_name(item, method.name),
+                   &method.desc, method.sections);
+    }
+}
+
+fn member_name(item: &doc::ItemDoc, name: &str) -> ~str {
+    str::connect(item.path + ~[copy item.name, name.to_owned()], "::")
+}
+
+fn text_tests(tests: &mut ~[(~str, CodeBlock)], name: ~str,
+              desc: &Option<~str>, sections: &[doc::Section]) {
+    let mut texts = ~[];
+    match *desc {
+        Some(ref desc) => texts.push(copy *desc),
+        None => ()
+    }
+    for sections.each |section| {
+        texts.push(copy section.body);
+    }
+    let mut n = 0u;
+    for texts.each |text| {
+        for code_blocks(*text).each |block| {
+            n += 1;
+            tests.push((fmt!("%s_%u", name, n), copy *block));
+        }
+    }
+}
+
+/**
+ * Builds and runs every example in the docs as a test, and fails if any
+ * of them does
+ */
+pub fn run_tests(config: Config, doc: doc::Doc) {
+    let crate_name = doc.cratemod().name();
+    let mut tests = ~[];
+    do vec::consume(find_tests(&doc)) |_, test| {
+        let (name, block) = test;
+        let program = make_program(crate_name, block.code);
+        let lib_paths = copy config.lib_paths;
+        let should_fail = block.should_fail;
+        let no_run = block.no_run;
+        tests.push(test::TestDescAndFn {
+            desc: test::TestDesc {
+                name: test::DynTestName(name),
+                ignore: block.ignore,
+                // The example itself may fail, but not its test
+                should_fail: false
+            },
+            testfn: test::DynTestFn(|| {
+                run_test(copy program, copy lib_paths, should_fail, no_run)
+            })
+        });
+    }
+    test::test_main(~[~"rustdoc"], tests);
+}
+
+fn run_test(program: ~str, lib_paths: ~[Path], should_fail: bool,
+            no_run: bool) {
+    let dir = match tempfile::mkdtemp(&os::tmpdir(), "rustdoctest") {
+        Some(dir) => dir,
+        None => fail!(~"couldn't create a directory to build the test in")
+    };
+    let src = dir.push(~"rust_out.rs");
+    let exe = dir.push(~"rust_out" + str::from_slice(os::EXE_SUFFIX));
+
+    do (|| {
+        compile(copy program, copy lib_paths, &src, &exe);
+        if !no_run {
+            let output = run::program_output(exe.to_str(), ~[]);
+            if should_fail && output.status == 0 {
+                fail!(~"test succeeded but should have failed");
+            }
+            if !should_fail && output.status != 0 {
+                fail!(fmt!("test exited with status %d\n%s%s",
+                           output.status, output.out, output.err));
+            }
+        }
+    }).finally {
+        for os::list_dir_path(&dir).each |file| {
+            os::remove_file(*file);
+        }
+        os::remove_dir(&dir);
+    }
+}
+
+fn compile(program: ~str, lib_paths: ~[Path], src: &Path, exe: &Path) {
+    match io::file_writer(src, ~[io::Create, io::Truncate]) {
+        Ok(w) => w.write_str(program),
+        Err(e) => fail!(fmt!("couldn't write %s: %s", src.to_str(), e))
+    }
+    let mut args = ~[~"-o", exe.to_str(), src.to_str()];
+    for lib_paths.each |path| {
+        args.push(~"-L");
+        args.push(path.to_str());
+    }
+    let output = run::program_output(rustc_path(), args);
+    if output.status != 0 {
+        fail!(fmt!("test failed to compile\n%s%s", output.out, output.err));
+    }
+}
+
+/// The rustc installed next to rustdoc, or else the one on the PATH
+fn rustc_path() -> ~str {
+    let rustc = ~"rustc" + str::from_slice(os::EXE_SUFFIX);
+    match os::self_exe_path() {
+        Some(dir) if os::path_exists(&dir.push(rustc)) => {
+            dir.push(rustc).to_str()
+        }
+        _ => rustc
+    }
+}
+
+#[test]
+fn should_find_indented_blocks() {
+    let blocks = code_blocks("text\n\n    let a = 1;\n\n    a;\n\nmore");
+    fail_unless!(blocks == ~[CodeBlock {
+        code: ~"let a = 1;\n\na;",
+        .. rust_block()
+    }]);
+}
+
+#[test]
+fn should_not_find_indented_paragraph_lines() {
+    fail_unless!(code_blocks("text\n    more text").is_empty());
+}
+
+#[test]
+fn should_find_fenced_blocks() {
+    let blocks = code_blocks("text\n```\nlet a = 1;\n```\n~~~ rust\na;\n~~~");
+    fail_unless!(blocks.map(|b| copy b.code) == ~[~"let a = 1;", ~"a;"]);
+}
+
+#[test]
+fn should_skip_blocks_in_other_languages() {
+    fail_unless!(code_blocks("```notrust\n$ make\n```").is_empty());
+    fail_unless!(code_blocks("~~~ {.sh}\n$ make\n~~~").is_empty());
+}
+
+#[test]
+fn should_mark_blocks_by_tag() {
+    let blocks = code_blocks(
+        "```ignore\na\n```\n```should_fail\nb\n```\n\
+         ~~~ {.rust .no_run}\nc\n~~~");
+    fail_unless!(blocks[0].ignore && !blocks[0].no_run);
+    fail_unless!(blocks[1].should_fail && !blocks[1].ignore);
+    fail_unless!(blocks[2].no_run && !blocks[2].should_fail);
+}
+
+#[test]
+fn should_keep_hidden_lines() {
+    let blocks = code_blocks("```\n# use core::vec;\n#\nvec::len(v);\n```");
+    fail_unless!(blocks[0].code == ~"use core::vec;\n\nvec::len(v);");
+}
+
+#[test]
+fn should_wrap_examples_in_main() {
+    fail_unless!(make_program("std", "let a = 1;")
+                 == ~"extern mod std;\nfn main() {\nlet a = 1;\n}\n");
+}
+
+#[test]
+fn should_not_wrap_examples_with_main() {
+    fail_unless!(make_program("core", "fn main() { }")
+                 == ~"fn main() { }\n");
+}
+
+#[test]
+fn should_only_take_main_itself_for_main() {
+    fail_unless!(make_program("core", "fn main_loop() { }")
+                 == ~"fn main() {\nfn main_loop() { }\n}\n");
+    fail_unless!(make_program("core", "// no fn main() here")
+                 == ~"fn main() {\n// no fn main() here\n}\n");
+    fail_unless!(make_program("core", "pub fn main () { }")
+                 == ~"pub fn main () { }\n");
+}
+
+#[test]
+fn should_not_link_crate_twice() {
+    fail_unless!(make_program("std", "extern mod std;\nfn main() { }")
+                 == ~"extern mod std;\nfn main() { }\n");
+}
+
+#[test]
+fn should_name_tests_after_items() {
+    let doc = test::mk_doc(
+        ~"#[doc = \"a\n\n    a();\"] mod a { \
+         #[doc = \"```\nb();\n```\n\n    c();\"] pub fn b() { } }");
+    let names = find_tests(&doc).map(|t| copy t.first());
+    fail_unless!(names == ~[~"a_1", ~"a::b_1", ~"a::b_2"]);
+}
+
+#[test]
+fn should_find_method_examples() {
+    let doc = test::mk_doc(
+        ~"trait t { #[doc = \"f\n\n    a();\"] fn f(&self); }");
+    let names = find_tests(&doc).map(|t| copy t.first());
+    fail_unless!(names == ~[~"t::f_1"]);
+}
+
+#[cfg(test)]
+mod test {
+    use astsrv;
+    use attr_pass;
+    use doc;
+    use extract;
+    use path_pass;
+    use unindent_pass;
+
+    pub fn mk_doc(source: ~str) -> doc::Doc {
+        do astsrv::from_str(copy source) |srv| {
+            let doc = extract::from_srv(srv.clone(), ~"");
+            let doc = (path_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (attr_pass::mk_pass().f)(srv.clone(), doc);
+            (unindent_pass::mk_pass().f)(srv.clone(), doc)
+        }
+    }
+}
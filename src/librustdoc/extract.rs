@@ -195,7 +195,8 @@ fn enumdoc_from_enum(
 ) -> doc::EnumDoc {
     doc::EnumDoc {
         item: itemdoc,
-        variants: variantdocs_from_variants(variants)
+        variants: variantdocs_from_variants(variants),
+        impls: ~[]
     }
 }
 
@@ -255,7 +256,8 @@ fn traitdoc_from_trait(
                 }
               }
             }
-        }
+        },
+        implementors: ~[]
     }
 }
 
@@ -301,9 +303,10 @@ fn should_extract_impl_methods() {
 fn tydoc_from_ty(
     itemdoc: doc::ItemDoc
 ) -> doc::TyDoc {
-    doc::SimpleItemDoc {
+    doc::TyDoc {
         item: itemdoc,
-        sig: None
+        sig: None,
+        impls: ~[]
     }
 }
 
@@ -325,7 +328,8 @@ fn structdoc_from_struct(
                 ast::unnamed_field => ~"(unnamed)",
             }
         },
-        sig: None
+        sig: None,
+        impls: ~[]
     }
 }
 
@@ -350,7 +350,7 @@ pub fn default_seq_fold_type<T>(
     fold: &Fold<T>,
     doc: doc::TyDoc
 ) -> doc::TyDoc {
-    doc::SimpleItemDoc {
+    doc::TyDoc {
         item: (fold.fold_item)(fold, copy doc.item),
         .. doc
     }
@@ -0,0 +1,916 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Generate HTML from a document tree, without going through pandoc
+
+Every page gets a sidebar listing the items of its module, and the paths
+in signatures link to the items they name, when the AST service resolved
+them. A search index listing every item is written next to the pages, as
+`search-index.json`.
+*/
+
+use core::prelude::*;
+
+use astsrv;
+use config;
+use doctest;
+use doc::ItemUtils;
+use doc;
+use markdown_index_pass::item_anchor;
+use markdown_pass::{header_kind, header_name, header_text, make_title};
+use markdown_writer::Writer;
+use markdown_writer::WriterUtils;
+use markdown_writer::WriterFactory;
+use markdown_writer;
+use pass::Pass;
+
+use core::cell::Cell;
+use core::char;
+use core::hashmap::linear::LinearMap;
+use core::str;
+use core::uint;
+use core::vec;
+use std::json;
+use syntax;
+
+/// The file each item is written to, and the anchor it is written under
+pub type Hrefs = LinearMap<doc::AstId, ~str>;
+
+pub fn mk_pass(
+    config: config::Config,
+    writer_factory: WriterFactory
+) -> Pass {
+    let writer_factory = Cell(writer_factory);
+    Pass {
+        name: ~"html",
+        f: |srv, doc| run(srv, doc, copy config, writer_factory.take())
+    }
+}
+
+fn run(
+    srv: astsrv::Srv,
+    doc: doc::Doc,
+    config: config::Config,
+    writer_factory: WriterFactory
+) -> doc::Doc {
+    let hrefs = @item_hrefs(&config, &doc);
+    write_html(srv, &doc, hrefs, copy config, writer_factory);
+
+    let index = config.output_dir.push("search-index.json");
+    markdown_writer::write_file(&index, search_index(&doc, hrefs));
+    doc
+}
+
+/**
+ * The id of the header an item is written under. Items are told apart by
+ * namespace rather than by kind, so a path can be linked to without
+ * knowing what kind of item it names.
+ */
+pub fn anchor(config: &config::Config, doc: &doc::ItemTag) -> ~str {
+    let name = if config.output_style == config::DocPerCrate {
+        // Every module is written to the same page
+        str::connect(doc.path() + ~[doc.name()], ~"::")
+    } else {
+        doc.name()
+    };
+    match *doc {
+      doc::FnTag(_) | doc::ConstTag(_) => ~"value." + name,
+      doc::ImplTag(_) => fmt!("impl.%d", doc.id()),
+      _ => ~"type." + name
+    }
+}
+
+/**
+ * Where each item of `doc` is written, for the output format. The item a
+ * page is for is linked to by the page alone.
+ */
+pub fn item_hrefs(config: &config::Config, doc: &doc::Doc) -> Hrefs {
+    let mut hrefs = LinearMap::new();
+    for doc.pages.each |page| {
+        let filename =
+            markdown_writer::make_filename(copy *config, copy *page);
+        let filename = filename.to_str();
+        let doc = page_item(copy *page);
+        hrefs.insert(doc.id(), copy filename);
+        add_hrefs(&mut hrefs, config, filename, &doc);
+    }
+    hrefs
+}
+
+fn add_hrefs(
+    hrefs: &mut Hrefs,
+    config: &config::Config,
+    filename: &str,
+    doc: &doc::ItemTag
+) {
+    for contents(doc).each |item| {
+        hrefs.insert(item.id(),
+                     fmt!("%s#%s", filename, item_anchor(config, item)));
+        add_hrefs(hrefs, config, filename, item);
+    }
+}
+
+/**
+ * Where the docs of an item in another crate are, assuming they were
+ * written a page per module to a directory named after the crate, next
+ * to this one. `namespace` is the namespace of the item's anchor, `type`
+ * or `value`.
+ */
+pub fn extern_href(path: &[~str], namespace: &str) -> ~str {
+    fail_unless!(path.len() > 1);
+    let mods = vec::slice(path, 1, path.len() - 1);
+    let page = if mods.is_empty() {
+        ~"index"
+    } else {
+        str::connect(mods, ~"_")
+    };
+    fmt!("../%s/%s.html#%s.%s", path[0], page, namespace,
+         path[path.len() - 1])
+}
+
+#[test]
+fn should_link_to_other_crates_by_module_page() {
+    fail_unless!(extern_href(~[~"core", ~"option", ~"Option"], "type")
+                 == ~"../core/option.html#type.Option");
+    fail_unless!(extern_href(~[~"std", ~"Bitv"], "type")
+                 == ~"../std/index.html#type.Bitv");
+    fail_unless!(extern_href(~[~"core", ~"str", ~"len"], "value")
+                 == ~"../core/str.html#value.len");
+}
+
+fn page_item(page: doc::Page) -> doc::ItemTag {
+    match page {
+      doc::CratePage(doc) => doc::ModTag(copy doc.topmod),
+      doc::ItemPage(doc) => doc
+    }
+}
+
+fn contents(doc: &doc::ItemTag) -> ~[doc::ItemTag] {
+    match *doc {
+      doc::ModTag(ref doc) => copy doc.items,
+      doc::NmodTag(ref doc) => doc.fns.map(|doc| doc::FnTag(copy *doc)),
+      doc::EnumTag(ref doc) => impl_tags(doc.impls),
+      doc::TyTag(ref doc) => impl_tags(doc.impls),
+      doc::StructTag(ref doc) => impl_tags(doc.impls),
+      _ => ~[]
+    }
+}
+
+fn impl_tags(docs: &[doc::ImplDoc]) -> ~[doc::ItemTag] {
+    docs.map(|doc| doc::ImplTag(copy *doc))
+}
+
+/// Lists the path, kind, link and brief description of every item
+pub fn search_index(doc: &doc::Doc, hrefs: &Hrefs) -> ~str {
+    let mut entries = ~[];
+    for doc.pages.each |page| {
+        index_item(&mut entries, hrefs, &page_item(copy *page));
+    }
+    json::to_str(&json::List(entries))
+}
+
+fn index_item(entries: &mut ~[json::Json], hrefs: &Hrefs,
+              doc: &doc::ItemTag) {
+    let indexed = match *doc {
+      // Impls have no path of their own
+      doc::ImplTag(_) => false,
+      _ => doc.id() != syntax::ast::crate_node_id
+    };
+    if indexed {
+        let path = str::connect(doc.path() + ~[doc.name()], ~"::");
+        let mut entry = LinearMap::new();
+        entry.insert(~"path", json::String(path));
+        entry.insert(~"kind", json::String(header_kind(copy *doc)));
+        entry.insert(~"href", json::String(copy *hrefs.get(&doc.id())));
+        entry.insert(~"brief", match doc.brief() {
+          Some(brief) => json::String(brief),
+          None => json::Null
+        });
+        entries.push(json::Object(~entry));
+    }
+    for contents(doc).each |item| {
+        index_item(entries, hrefs, item);
+    }
+}
+
+struct Ctxt {
+    srv: astsrv::Srv,
+    config: config::Config,
+    hrefs: @Hrefs,
+    w: Writer
+}
+
+fn write_html(
+    srv: astsrv::Srv,
+    doc: &doc::Doc,
+    hrefs: @Hrefs,
+    config: config::Config,
+    writer_factory: WriterFactory
+) {
+    for doc.pages.each |page| {
+        let ctxt = Ctxt {
+            srv: srv.clone(),
+            config: copy config,
+            hrefs: hrefs,
+            w: writer_factory(copy *page)
+        };
+        write_page(&ctxt, copy *page);
+    }
+}
+
+fn write_page(ctxt: &Ctxt, page: doc::Page) {
+    let doc = page_item(copy page);
+    ctxt.w.write_line(~"<!DOCTYPE html>");
+    ctxt.w.write_line(~"<html>");
+    ctxt.w.write_line(~"<head>");
+    ctxt.w.write_line(~"<meta charset=\"utf-8\">");
+    ctxt.w.write_line(fmt!("<title>%s</title>", escape(make_title(page))));
+    ctxt.w.write_line(~"<link rel=\"stylesheet\" href=\"rust.css\">");
+    ctxt.w.write_line(~"</head>");
+    ctxt.w.write_line(~"<body>");
+    write_sidebar(ctxt, &doc);
+    ctxt.w.write_line(~"<div class=\"content\">");
+    ctxt.w.write_line(fmt!("<h1>%s</h1>",
+                           render_inline(header_text(copy doc))));
+    write_item_contents(ctxt, doc);
+    ctxt.w.write_line(~"</div>");
+    ctxt.w.write_line(~"</body>");
+    ctxt.w.write_line(~"</html>");
+    ctxt.w.write_done();
+}
+
+fn write_sidebar(ctxt: &Ctxt, doc: &doc::ItemTag) {
+    let index = match *doc {
+      doc::ModTag(ref doc) => copy doc.index,
+      doc::NmodTag(ref doc) => copy doc.index,
+      _ => None
+    };
+    ctxt.w.write_line(~"<div class=\"sidebar\">");
+    ctxt.w.write_line(fmt!("<h3>%s</h3>", escape(header_name(copy *doc))));
+    match index {
+      Some(index) if !index.entries.is_empty() => {
+        ctxt.w.write_line(~"<ul>");
+        for index.entries.each |entry| {
+            ctxt.w.write_line(fmt!("<li><a href=\"%s\" title=\"%s\">%s</a>\
+                                    </li>",
+                                   escape(entry.link), escape(entry.kind),
+                                   escape(entry.name)));
+        }
+        ctxt.w.write_line(~"</ul>");
+      }
+      _ => ()
+    }
+    ctxt.w.write_line(~"</div>");
+}
+
+fn write_item(ctxt: &Ctxt, doc: doc::ItemTag) {
+    ctxt.w.write_line(fmt!("<h2 id=\"%s\">%s</h2>",
+                           escape(anchor(&ctxt.config, &doc)),
+                           render_inline(header_text(copy doc))));
+    write_item_contents(ctxt, doc);
+}
+
+fn write_item_contents(ctxt: &Ctxt, doc: doc::ItemTag) {
+    let links = path_hrefs(ctxt, doc.id());
+    match doc {
+      doc::ModTag(doc) => write_mod_contents(ctxt, doc),
+      doc::NmodTag(doc) => write_nmod(ctxt, doc),
+      doc::FnTag(doc) | doc::ConstTag(doc) => {
+        write_sig(ctxt, &links, copy doc.sig);
+        write_common(ctxt, doc.desc(), doc.sections());
+      }
+      doc::EnumTag(doc) => {
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_variants(ctxt, &links, doc.variants);
+        write_impls(ctxt, doc.impls);
+      }
+      doc::TraitTag(doc) => {
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_methods(ctxt, &links, 3, doc.methods);
+        write_implementors(ctxt, doc.implementors);
+      }
+      doc::ImplTag(doc) => {
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_methods(ctxt, &links, 3, doc.methods);
+      }
+      doc::TyTag(doc) => {
+        write_sig(ctxt, &links, copy doc.sig);
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_impls(ctxt, doc.impls);
+      }
+      doc::StructTag(doc) => {
+        write_sig(ctxt, &links, copy doc.sig);
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_impls(ctxt, doc.impls);
+      }
+    }
+}
+
+/// Writes the impls for a type under its header
+fn write_impls(ctxt: &Ctxt, docs: &[doc::ImplDoc]) {
+    for docs.each |doc| {
+        let tag = doc::ImplTag(copy *doc);
+        ctxt.w.write_line(fmt!("<h3 id=\"%s\">%s</h3>",
+                               escape(anchor(&ctxt.config, &tag)),
+                               render_inline(header_text(tag))));
+        let links = path_hrefs(ctxt, doc.id());
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_methods(ctxt, &links, 4, doc.methods);
+    }
+}
+
+fn write_implementors(ctxt: &Ctxt, docs: &[doc::ImplDoc]) {
+    if docs.is_empty() {
+        return;
+    }
+
+    ctxt.w.write_line(~"<h4>Implementors</h4>");
+    ctxt.w.write_line(~"<ul>");
+    for docs.each |doc| {
+        let self_ty = escape((&doc.self_ty).get());
+        match ctxt.hrefs.find(&doc.id()) {
+          Some(href) => {
+            ctxt.w.write_line(fmt!("<li><a href=\"%s\"><code>%s</code></a>\
+                                    </li>", escape(*href), self_ty));
+          }
+          None => {
+            ctxt.w.write_line(fmt!("<li><code>%s</code></li>", self_ty));
+          }
+        }
+    }
+    ctxt.w.write_line(~"</ul>");
+}
+
+fn write_mod_contents(ctxt: &Ctxt, doc: doc::ModDoc) {
+    pure fn is_mod(doc: &doc::ItemTag) -> bool {
+        match *doc {
+          doc::ModTag(_) | doc::NmodTag(_) => true,
+          _ => false
+        }
+    }
+
+    write_common(ctxt, doc.desc(), doc.sections());
+    // Modules are only still here when the crate is a single page
+    for doc.items.each |item| {
+        if !is_mod(item) { write_item(ctxt, copy *item); }
+    }
+    for doc.items.each |item| {
+        if is_mod(item) { write_item(ctxt, copy *item); }
+    }
+}
+
+fn write_nmod(ctxt: &Ctxt, doc: doc::NmodDoc) {
+    write_common(ctxt, doc.desc(), doc.sections());
+    for doc.fns.each |doc| {
+        write_item(ctxt, doc::FnTag(copy *doc));
+    }
+}
+
+fn write_common(
+    ctxt: &Ctxt,
+    desc: Option<~str>,
+    sections: &[doc::Section]
+) {
+    match desc {
+      Some(desc) => ctxt.w.write_str(render_markdown(desc)),
+      None => ()
+    }
+    for sections.each |section| {
+        ctxt.w.write_line(fmt!("<h4>%s</h4>",
+                               render_inline(section.header)));
+        ctxt.w.write_str(render_markdown(section.body));
+    }
+}
+
+fn write_variants(
+    ctxt: &Ctxt,
+    links: &LinearMap<~str, ~str>,
+    docs: &[doc::VariantDoc]
+) {
+    if docs.is_empty() {
+        return;
+    }
+
+    ctxt.w.write_line(~"<h4>Variants</h4>");
+    ctxt.w.write_line(~"<ul>");
+    for docs.each |doc| {
+        fail_unless!(doc.sig.is_some());
+        let sig = link_paths((&doc.sig).get(), links);
+        match copy doc.desc {
+          Some(desc) => {
+            ctxt.w.write_line(fmt!("<li><code>%s</code> - %s</li>",
+                                   sig, render_inline(desc)));
+          }
+          None => {
+            ctxt.w.write_line(fmt!("<li><code>%s</code></li>", sig));
+          }
+        }
+    }
+    ctxt.w.write_line(~"</ul>");
+}
+
+fn write_methods(
+    ctxt: &Ctxt,
+    links: &LinearMap<~str, ~str>,
+    level: uint,
+    docs: &[doc::MethodDoc]
+) {
+    for docs.each |doc| {
+        ctxt.w.write_line(fmt!("<h%u>Method <code>%s</code></h%u>",
+                               level, escape(doc.name), level));
+        write_sig(ctxt, links, copy doc.sig);
+        write_common(ctxt, copy doc.desc, doc.sections);
+    }
+}
+
+fn write_sig(
+    ctxt: &Ctxt,
+    links: &LinearMap<~str, ~str>,
+    sig: Option<~str>
+) {
+    match sig {
+      Some(sig) => {
+        ctxt.w.write_line(fmt!("<pre class=\"sig\">%s</pre>",
+                               link_paths(sig, links)));
+      }
+      None => fail!(~"unimplemented")
+    }
+}
+
+/// Where each path the item `id` uses can be linked to
+fn path_hrefs(ctxt: &Ctxt, id: doc::AstId) -> LinearMap<~str, ~str> {
+    let links = do astsrv::exec(ctxt.srv.clone()) |ctxt| {
+        match ctxt.links.find(&id) {
+          Some(links) => copy *links,
+          None => ~[]
+        }
+    };
+
+    let mut hrefs = LinearMap::new();
+    for vec::consume(links) |_, (path, link)| {
+        let href = match link {
+          astsrv::LocalLink(item_id) => {
+            ctxt.hrefs.find(&item_id).map(|href| copy **href)
+          }
+          astsrv::ExternLink(item_path) => {
+            Some(extern_href(item_path, "type"))
+          }
+          // Signatures only link to types
+          astsrv::ExternValueLink(_) => None
+        };
+        match href {
+          Some(href) => { hrefs.insert(path, href); }
+          // Not documented, e.g. private
+          None => ()
+        }
+    }
+    hrefs
+}
+
+/// Escapes `sig`, linking each path in it that `hrefs` has a link for
+fn link_paths(sig: &str, hrefs: &LinearMap<~str, ~str>) -> ~str {
+    fn link_path(path: &str, hrefs: &LinearMap<~str, ~str>) -> ~str {
+        match hrefs.find(&str::from_slice(path)) {
+          Some(href) => fmt!("<a href=\"%s\">%s</a>", escape(*href), path),
+          None => str::from_slice(path)
+        }
+    }
+
+    let mut html = ~"";
+    let mut path = ~"";
+    for str::each_char(sig) |c| {
+        if char::is_alphanumeric(c) || c == '_' || c == ':' {
+            str::push_char(&mut path, c);
+        } else {
+            html += link_path(path, hrefs);
+            html += escape(str::from_char(c));
+            path = ~"";
+        }
+    }
+    html + link_path(path, hrefs)
+}
+
+#[test]
+fn should_link_whole_paths() {
+    let mut hrefs = LinearMap::new();
+    hrefs.insert(~"option::Option", ~"o.html");
+    hrefs.insert(~"T", ~"t.html");
+    fail_unless!(link_paths("fn a(x: option::Option<T>) -> ~[U]", &hrefs)
+                 == ~"fn a(x: <a href=\"o.html\">option::Option</a>&lt;\
+                      <a href=\"t.html\">T</a>&gt;) -&gt; ~[U]");
+}
+
+fn escape(s: &str) -> ~str {
+    let s = str::replace(s, ~"&", ~"&amp;");
+    let s = str::replace(s, ~"<", ~"&lt;");
+    let s = str::replace(s, ~">", ~"&gt;");
+    str::replace(s, ~"\"", ~"&quot;")
+}
+
+/**
+ * Renders the markdown doc comments are written in: paragraphs, headers,
+ * lists and indented code blocks, with code spans and links in the text
+ */
+pub fn render_markdown(text: &str) -> ~str {
+    fn flush_para(html: &mut ~str, para: &mut ~[~str]) {
+        if !para.is_empty() {
+            *html += fmt!("<p>%s</p>\n",
+                          render_inline(str::connect(*para, ~"\n")));
+            *para = ~[];
+        }
+    }
+    fn flush_list(html: &mut ~str, items: &mut ~[~str]) {
+        if !items.is_empty() {
+            *html += ~"<ul>\n";
+            for items.each |item| {
+                *html += fmt!("<li>%s</li>\n", render_inline(*item));
+            }
+            *html += ~"</ul>\n";
+            *items = ~[];
+        }
+    }
+    fn flush_code(html: &mut ~str, code: &mut ~[~str]) {
+        while !code.is_empty() && code.last().is_empty() {
+            code.pop();
+        }
+        if !code.is_empty() {
+            *html += fmt!("<pre><code>%s</code></pre>\n",
+                          escape(str::connect(*code, ~"\n")));
+            *code = ~[];
+        }
+    }
+    fn list_item(line: &str) -> Option<~str> {
+        let line = str::trim_left(line);
+        if str::starts_with(line, "* ") || str::starts_with(line, "- ")
+            || str::starts_with(line, "+ ") {
+            Some(str::trim(str::slice(line, 2, line.len())))
+        } else {
+            None
+        }
+    }
+
+    let mut html = ~"";
+    let mut para = ~[];
+    let mut items = ~[];
+    let mut code = ~[];
+    // The marker of the fenced block we are in, and whether it is an
+    // example with lines hidden from readers
+    let mut fence = None;
+    let mut hide = false;
+    for str::lines_any(text).each |line| {
+        if fence.is_some() {
+            let closed = match fence {
+                Some(ref marker) => str::starts_with(*line, *marker),
+                None => false
+            };
+            if closed {
+                flush_code(&mut html, &mut code);
+                fence = None;
+            } else if !(hide && doctest::is_hidden(*line)) {
+                code.push(copy *line);
+            }
+        } else if str::is_whitespace(*line) {
+            if code.is_empty() {
+                flush_para(&mut html, &mut para);
+                flush_list(&mut html, &mut items);
+            } else {
+                code.push(~"");
+            }
+        } else if str::starts_with(*line, "    ")
+            && para.is_empty() && items.is_empty() {
+            code.push(str::slice(*line, 4, line.len()));
+        } else {
+            flush_code(&mut html, &mut code);
+            let item = list_item(*line);
+            let opening = doctest::fence_start(*line);
+            if opening.is_some() {
+                flush_para(&mut html, &mut para);
+                flush_list(&mut html, &mut items);
+                let (marker, info) = opening.get();
+                hide = doctest::is_example(info);
+                fence = Some(marker);
+            } else if str::starts_with(*line, "#") {
+                flush_para(&mut html, &mut para);
+                flush_list(&mut html, &mut items);
+                let level = str::find(*line, |c| c != '#')
+                    .get_or_default(line.len());
+                let text = str::trim(str::slice(*line, level, line.len()));
+                let level = uint::min(level, 6);
+                html += fmt!("<h%u>%s</h%u>\n",
+                             level, render_inline(text), level);
+            } else if item.is_some() {
+                flush_para(&mut html, &mut para);
+                items.push(item.get());
+            } else if !items.is_empty() {
+                // A list item continued on the next line
+                let last = items.pop();
+                items.push(last + ~" " + str::trim(*line));
+            } else {
+                para.push(str::trim(*line));
+            }
+        }
+    }
+    flush_para(&mut html, &mut para);
+    flush_list(&mut html, &mut items);
+    flush_code(&mut html, &mut code);
+    html
+}
+
+#[test]
+fn should_render_paragraphs() {
+    fail_unless!(render_markdown("a\nb\n\nc")
+                 == ~"<p>a\nb</p>\n<p>c</p>\n");
+}
+
+#[test]
+fn should_render_code_blocks() {
+    fail_unless!(render_markdown("a:\n\n    x < y\n\n    z\n\nb")
+                 == ~"<p>a:</p>\n<pre><code>x &lt; y\n\nz</code></pre>\n\
+                      <p>b</p>\n");
+}
+
+#[test]
+fn should_render_fenced_code_blocks() {
+    fail_unless!(render_markdown("a:\n```\n# fn f() { }\nf();\n```\nb")
+                 == ~"<p>a:</p>\n<pre><code>f();</code></pre>\n<p>b</p>\n");
+    fail_unless!(render_markdown("~~~ sh\n# make\n~~~")
+                 == ~"<pre><code># make</code></pre>\n");
+}
+
+#[test]
+fn should_render_lists() {
+    fail_unless!(render_markdown("* a\n  b\n* c")
+                 == ~"<ul>\n<li>a b</li>\n<li>c</li>\n</ul>\n");
+}
+
+#[test]
+fn should_render_headers() {
+    fail_unless!(render_markdown("## Safety\nb")
+                 == ~"<h2>Safety</h2>\n<p>b</p>\n");
+}
+
+/// Renders code spans, links and backslash escapes, escaping the rest
+fn render_inline(text: &str) -> ~str {
+    let mut html = ~"";
+    let mut i = 0;
+    while i < text.len() {
+        let str::CharRange {ch, next} = str::char_range_at(text, i);
+        if ch == '\\' && next < text.len() {
+            let escaped = str::char_range_at(text, next);
+            html += escape(str::from_char(escaped.ch));
+            i = escaped.next;
+        } else if ch == '`' {
+            match str::find_char_from(text, '`', next) {
+              Some(end) => {
+                html += fmt!("<code>%s</code>",
+                             escape(str::slice(text, next, end)));
+                i = end + 1;
+              }
+              None => {
+                html += ~"`";
+                i = next;
+              }
+            }
+        } else if ch == '[' && inline_link(text, i).is_some() {
+            let (link_text, url, end) = inline_link(text, i).get();
+            html += fmt!("<a href=\"%s\">%s</a>",
+                         escape(url), render_inline(link_text));
+            i = end;
+        } else {
+            html += escape(str::from_char(ch));
+            i = next;
+        }
+    }
+    html
+}
+
+/// The text and url of a `[text](url)` link at `start`, and its end
+fn inline_link(text: &str, start: uint) -> Option<(~str, ~str, uint)> {
+    let mid = match str::find_char_from(text, ']', start) {
+      Some(mid) if str::starts_with(str::view(text, mid, text.len()),
+                                    "](") => mid,
+      _ => return None
+    };
+    do str::find_char_from(text, ')', mid + 2).map |end| {
+        (str::slice(text, start + 1, mid), str::slice(text, mid + 2, *end),
+         *end + 1)
+    }
+}
+
+#[test]
+fn should_render_inline_markdown() {
+    fail_unless!(render_inline("use `a<b>` & [c](d.html), \\\\ \\*")
+                 == ~"use <code>a&lt;b&gt;</code> &amp; \
+                      <a href=\"d.html\">c</a>, \\ *");
+}
+
+#[test]
+fn should_write_page_per_module() {
+    let pages = test::render_pages(config::DocPerMod, ~"mod a { }");
+    fail_unless!(pages.len() == 2);
+    for pages.each |page| {
+        let (page, html) = copy *page;
+        fail_unless!(str::starts_with(html, ~"<!DOCTYPE html>"));
+        match page {
+          doc::CratePage(_) => {
+            fail_unless!(str::contains(html, ~"<title>Crate test</title>"));
+          }
+          doc::ItemPage(_) => {
+            fail_unless!(str::contains(html, ~"<title>Module a</title>"));
+          }
+        }
+    }
+}
+
+#[test]
+fn should_write_sidebar_of_module_items() {
+    let html = test::render(~"mod a { } fn b() { }");
+    fail_unless!(str::contains(
+        html,
+        ~"<div class=\"sidebar\">\n<h3>test</h3>\n<ul>\n\
+         <li><a href=\"#type.a\" title=\"Module\">a</a></li>\n\
+         <li><a href=\"#value.b\" title=\"Function\">b</a></li>\n\
+         </ul>\n</div>"));
+}
+
+#[test]
+fn should_write_item_headers_under_anchors() {
+    let html = test::render(~"mod a { fn b() { } }");
+    fail_unless!(str::contains(
+        html, ~"<h2 id=\"type.a\">Module <code>a</code></h2>"));
+    fail_unless!(str::contains(
+        html, ~"<h2 id=\"value.a::b\">Function <code>b</code></h2>"));
+}
+
+#[test]
+fn should_write_descriptions_as_html() {
+    let html = test::render(~"#[doc = \"The `a` fn\"] fn a() { }");
+    fail_unless!(str::contains(html, ~"<p>The <code>a</code> fn</p>"));
+}
+
+#[test]
+fn should_escape_signatures() {
+    let html = test::render(~"fn a<T>(x: ~[T]) { }");
+    fail_unless!(str::contains(
+        html, ~"<pre class=\"sig\">fn a&lt;T&gt;(x: ~[T])</pre>"));
+}
+
+#[test]
+fn should_link_types_to_their_definitions() {
+    let html = test::render_with_links(
+        ~"struct S { x: int } fn a(s: S) { }");
+    fail_unless!(str::contains(
+        html, ~"fn a(s: <a href=\"test.html#type.S\">S</a>)"));
+}
+
+#[test]
+fn should_link_types_in_other_crates() {
+    let html = test::render_with_links(
+        ~"fn a(x: core::option::Option<int>) { }");
+    fail_unless!(str::contains(
+        html, ~"<a href=\"../core/option.html#type.Option\">\
+                core::option::Option</a>&lt;int&gt;"));
+}
+
+#[test]
+fn should_write_impls_under_their_type() {
+    let html = test::render(
+        ~"struct S { x: int } impl S { fn a(&self) { } }");
+    fail_unless!(str::contains(
+        html, ~"\">Implementation for <code>S</code></h3>"));
+    fail_unless!(str::contains(
+        html, ~"<h4>Method <code>a</code></h4>"));
+}
+
+#[test]
+fn should_link_implementors_to_their_impls() {
+    let html = test::render(
+        ~"trait T { } struct S { x: int } impl T for S { }");
+    fail_unless!(str::contains(html, ~"<h4>Implementors</h4>"));
+    fail_unless!(str::contains(
+        html, ~"<li><a href=\"test.html#impl."));
+    fail_unless!(str::contains(html, ~"\"><code>S</code></a></li>"));
+}
+
+#[test]
+fn should_index_every_item_path() {
+    let index = test::render_index(~"mod a { struct B { x: int } }");
+    let entries = match json::from_str(index) {
+      Ok(json::List(entries)) => entries,
+      _ => fail!()
+    };
+    let entry = do entries.find |entry| {
+        match *entry {
+          json::Object(ref entry) => {
+            *entry.get(&~"path") == json::String(~"a::B")
+          }
+          _ => false
+        }
+    };
+    match entry {
+      Some(json::Object(entry)) => {
+        fail_unless!(*entry.get(&~"kind") == json::String(~"Struct"));
+        fail_unless!(*entry.get(&~"href")
+                     == json::String(~"a.html#type.B"));
+      }
+      _ => fail!()
+    }
+    fail_unless!(entries.len() == 2);
+}
+
+#[cfg(test)]
+mod test {
+    use astsrv;
+    use attr_pass;
+    use config;
+    use desc_to_brief_pass;
+    use doc;
+    use extract;
+    use html_writer::{item_hrefs, search_index, write_html};
+    use impl_pass;
+    use markdown_index_pass;
+    use markdown_writer;
+    use page_pass;
+    use path_pass;
+    use sectionalize_pass;
+    use trim_pass;
+    use tystr_pass;
+    use unindent_pass;
+
+    use core::path::Path;
+    use core::vec;
+
+    pub fn render(source: ~str) -> ~str {
+        do astsrv::from_str(source) |srv| {
+            let pages = write_pages(srv, config::DocPerCrate);
+            pages[0].second()
+        }
+    }
+
+    pub fn render_with_links(source: ~str) -> ~str {
+        do astsrv::from_str_with_links(source) |srv| {
+            let pages = write_pages(srv, config::DocPerCrate);
+            pages[0].second()
+        }
+    }
+
+    pub fn render_pages(
+        output_style: config::OutputStyle,
+        source: ~str
+    ) -> ~[(doc::Page, ~str)] {
+        do astsrv::from_str(source) |srv| {
+            write_pages(srv, output_style)
+        }
+    }
+
+    pub fn render_index(source: ~str) -> ~str {
+        do astsrv::from_str(source) |srv| {
+            let config = mk_config(config::DocPerMod);
+            let doc = mk_doc(srv.clone(), copy config);
+            search_index(&doc, &item_hrefs(&config, &doc))
+        }
+    }
+
+    fn mk_config(output_style: config::OutputStyle) -> config::Config {
+        config::Config {
+            output_format: config::NativeHtml,
+            output_style: output_style,
+            .. config::default_config(&Path("test"))
+        }
+    }
+
+    fn write_pages(
+        srv: astsrv::Srv,
+        output_style: config::OutputStyle
+    ) -> ~[(doc::Page, ~str)] {
+        let config = mk_config(output_style);
+        let doc = mk_doc(srv.clone(), copy config);
+        let (writer_factory, po) = markdown_writer::future_writer_factory();
+        write_html(srv, &doc, @item_hrefs(&config, &doc), copy config,
+                   writer_factory);
+        vec::from_fn(doc.pages.len(), |_| po.recv())
+    }
+
+    fn mk_doc(srv: astsrv::Srv, config: config::Config) -> doc::Doc {
+        let doc = extract::from_srv(srv.clone(), ~"test");
+        let doc = (tystr_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (path_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (attr_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (desc_to_brief_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (unindent_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (sectionalize_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (trim_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (impl_pass::mk_pass().f)(srv.clone(), doc);
+        let doc = (markdown_index_pass::mk_pass(copy config).f)(
+            srv.clone(), doc);
+        (page_pass::mk_pass(config.output_style).f)(srv.clone(), doc)
+    }
+}
@@ -0,0 +1,345 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Moves each impl to the docs of the struct, enum or type it is for,
+wherever in the crate the impl is, and lists on each trait the impls of
+it. Impls for types the crate doesn't document, like `int` or a type of
+another crate, are left in their modules.
+
+The paths an impl names are looked up in the crate's resolve map when
+the crate could be resolved, and otherwise matched by name against the
+types and traits of the crate.
+*/
+
+use core::prelude::*;
+
+use astsrv;
+use doc::ItemUtils;
+use doc;
+use extract::to_str;
+use fold::Fold;
+use fold;
+use pass::Pass;
+
+use core::str;
+use core::vec;
+use syntax::ast;
+use syntax::ast_map;
+
+pub fn mk_pass() -> Pass {
+    Pass {
+        name: ~"impl",
+        f: run
+    }
+}
+
+/// An impl, with the ids of the type and the trait it is for
+struct ImplTarget {
+    doc: doc::ImplDoc,
+    self_ty: Option<doc::AstId>,
+    trait_: Option<doc::AstId>
+}
+
+/// A path an impl names, and what it resolved to
+struct PathTarget {
+    idents: ~[~str],
+    resolved: bool,
+    link: Option<astsrv::Link>
+}
+
+struct Ctxt {
+    impls: @~[ImplTarget]
+}
+
+impl Clone for Ctxt {
+    fn clone(&self) -> Ctxt {
+        Ctxt {
+            impls: self.impls
+        }
+    }
+}
+
+pub fn run(srv: astsrv::Srv, doc: doc::Doc) -> doc::Doc {
+    let mut types = ~[];
+    let mut traits = ~[];
+    let mut impls = ~[];
+    collect(&doc.cratemod(), &mut types, &mut traits, &mut impls);
+
+    let impls = do vec::map_consume(impls) |impl_doc| {
+        let (self_path, trait_path) = impl_paths(srv.clone(), impl_doc.id());
+        ImplTarget {
+            self_ty: self_path.chain(|path| {
+                find_item(types, &impl_doc.item, &path)
+            }),
+            trait_: trait_path.chain(|path| {
+                find_item(traits, &impl_doc.item, &path)
+            }),
+            doc: impl_doc
+        }
+    };
+
+    let ctxt = Ctxt {
+        impls: @impls
+    };
+    let fold = Fold {
+        ctxt: ctxt.clone(),
+        fold_mod: fold_mod,
+        fold_enum: fold_enum,
+        fold_trait: fold_trait,
+        fold_type: fold_type,
+        fold_struct: fold_struct,
+        .. fold::default_any_fold(ctxt)
+    };
+    (fold.fold_doc)(&fold, doc)
+}
+
+fn collect(
+    doc: &doc::ModDoc,
+    types: &mut ~[doc::ItemDoc],
+    traits: &mut ~[doc::ItemDoc],
+    impls: &mut ~[doc::ImplDoc]
+) {
+    for doc.items.each |item| {
+        match *item {
+          doc::ModTag(ref doc) => collect(doc, types, traits, impls),
+          doc::EnumTag(_) | doc::TyTag(_) | doc::StructTag(_) => {
+            types.push(item.item());
+          }
+          doc::TraitTag(ref doc) => traits.push(copy doc.item),
+          doc::ImplTag(ref doc) => impls.push(copy *doc),
+          _ => ()
+        }
+    }
+}
+
+/// The paths of the type an impl is for and of the trait it implements
+fn impl_paths(
+    srv: astsrv::Srv,
+    id: doc::AstId
+) -> (Option<PathTarget>, Option<PathTarget>) {
+    do astsrv::exec(srv) |ctxt| {
+        let links = match ctxt.links.find(&id) {
+          Some(links) => copy *links,
+          None => ~[]
+        };
+        let target = |path: @ast::path| {
+            let idents = path.idents.map(|ident| to_str(*ident));
+            let path_str = str::connect(idents, ~"::");
+            let path_str = if path.global { ~"::" + path_str }
+                           else { path_str };
+            PathTarget {
+                idents: idents,
+                resolved: !ctxt.links.is_empty(),
+                link: vec::find(links, |link| link.first() == path_str)
+                    .map(|link| link.second())
+            }
+        };
+
+        match ctxt.ast_map.get(&id) {
+          ast_map::node_item(@ast::item {
+            node: ast::item_impl(_, ref trait_ref, self_ty, _), _
+          }, _) => {
+            (ty_path(self_ty).map(|path| target(*path)),
+             trait_ref.map(|trait_ref| target(trait_ref.path)))
+          }
+          _ => fail!(~"impl_paths: id not bound to an impl")
+        }
+    }
+}
+
+/// The path naming a type, looking through pointers to it
+fn ty_path(ty: @ast::Ty) -> Option<@ast::path> {
+    match ty.node {
+      ast::ty_path(path, _) => Some(path),
+      ast::ty_box(ref mt) | ast::ty_uniq(ref mt)
+      | ast::ty_rptr(_, ref mt) => ty_path(mt.ty),
+      _ => None
+    }
+}
+
+/// Which of `items` a path named in the impl `impl_item` refers to
+fn find_item(
+    items: &[doc::ItemDoc],
+    impl_item: &doc::ItemDoc,
+    path: &PathTarget
+) -> Option<doc::AstId> {
+    if path.resolved {
+        return match path.link {
+          Some(astsrv::LocalLink(id)) => {
+            vec::find(items, |item| item.id == id).map(|item| item.id)
+          }
+          _ => None
+        };
+    }
+
+    // Prefer the item the path names relative to the impl's module, then
+    // the only item of the crate with that name
+    let name = path.idents.last();
+    let candidates = do items.filtered |item| { item.name == *name };
+    let qualifier = vec::slice(path.idents, 0, path.idents.len() - 1);
+    let relative_path = vec::append(copy impl_item.path, qualifier);
+    match vec::find(candidates, |item| item.path == relative_path) {
+      Some(item) => Some(item.id),
+      None if candidates.len() == 1 => Some(candidates[0].id),
+      None => None
+    }
+}
+
+fn impls_for(fold: &fold::Fold<Ctxt>, id: doc::AstId) -> ~[doc::ImplDoc] {
+    do fold.ctxt.impls.filter_mapped |target| {
+        if target.self_ty == Some(id) { Some(copy target.doc) } else { None }
+    }
+}
+
+fn fold_mod(fold: &fold::Fold<Ctxt>, doc: doc::ModDoc) -> doc::ModDoc {
+    let doc = fold::default_any_fold_mod(fold, doc);
+
+    doc::ModDoc {
+        items: do doc.items.filtered |item| {
+            match *item {
+              doc::ImplTag(ref impl_doc) => {
+                !vec::any(*fold.ctxt.impls, |target| {
+                    target.doc.id() == impl_doc.id()
+                        && target.self_ty.is_some()
+                })
+              }
+              _ => true
+            }
+        },
+        .. doc
+    }
+}
+
+fn fold_enum(fold: &fold::Fold<Ctxt>, doc: doc::EnumDoc) -> doc::EnumDoc {
+    let doc = fold::default_seq_fold_enum(fold, doc);
+
+    doc::EnumDoc {
+        impls: impls_for(fold, doc.id()),
+        .. doc
+    }
+}
+
+fn fold_type(fold: &fold::Fold<Ctxt>, doc: doc::TyDoc) -> doc::TyDoc {
+    let doc = fold::default_seq_fold_type(fold, doc);
+
+    doc::TyDoc {
+        impls: impls_for(fold, doc.id()),
+        .. doc
+    }
+}
+
+fn fold_struct(
+    fold: &fold::Fold<Ctxt>,
+    doc: doc::StructDoc
+) -> doc::StructDoc {
+    let doc = fold::default_seq_fold_struct(fold, doc);
+
+    doc::StructDoc {
+        impls: impls_for(fold, doc.id()),
+        .. doc
+    }
+}
+
+fn fold_trait(fold: &fold::Fold<Ctxt>, doc: doc::TraitDoc) -> doc::TraitDoc {
+    let doc = fold::default_seq_fold_trait(fold, doc);
+    let id = doc.id();
+
+    doc::TraitDoc {
+        implementors: do fold.ctxt.impls.filter_mapped |target| {
+            if target.trait_ == Some(id) {
+                Some(copy target.doc)
+            } else {
+                None
+            }
+        },
+        .. doc
+    }
+}
+
+#[test]
+fn should_move_impls_to_their_struct() {
+    let doc = test::mk_doc(
+        ~"struct S { x: int } impl S { fn a(&self) { } }");
+    fail_unless!(doc.cratemod().impls().is_empty());
+    fail_unless!(doc.cratemod().structs()[0].impls[0].methods[0].name
+                 == ~"a");
+}
+
+#[test]
+fn should_move_impls_from_other_modules() {
+    let doc = test::mk_doc(
+        ~"mod a { pub enum E { V } } mod b { impl ::a::E { } }");
+    fail_unless!(doc.cratemod().mods()[1].impls().is_empty());
+    fail_unless!(doc.cratemod().mods()[0].enums()[0].impls.len() == 1);
+}
+
+#[test]
+fn should_move_impls_to_types() {
+    let doc = test::mk_doc(~"type T = int; impl T { fn a(&self) { } }");
+    fail_unless!(doc.cratemod().types()[0].impls.len() == 1);
+}
+
+#[test]
+fn should_prefer_types_in_the_impls_module() {
+    let doc = test::mk_doc(
+        ~"mod a { pub struct S { x: int } impl S { } } \
+         mod b { pub struct S { x: int } }");
+    fail_unless!(doc.cratemod().mods()[0].structs()[0].impls.len() == 1);
+    fail_unless!(doc.cratemod().mods()[1].structs()[0].impls.is_empty());
+}
+
+#[test]
+fn should_leave_impls_for_other_types() {
+    let doc = test::mk_doc(~"impl int { fn a(&self) { } }");
+    fail_unless!(doc.cratemod().impls().len() == 1);
+}
+
+#[test]
+fn should_list_implementors() {
+    let doc = test::mk_doc(
+        ~"trait T { } struct S { x: int } \
+         impl T for S { } impl T for int { }");
+    let implementors = copy doc.cratemod().traits()[0].implementors;
+    fail_unless!(implementors.map(|i| (&i.self_ty).get())
+                 == ~[~"S", ~"int"]);
+}
+
+#[test]
+fn should_use_resolved_paths() {
+    let source = ~"mod a { pub struct S { x: int } } \
+                   mod b { use T = a::S; impl T { } }";
+    let doc = do astsrv::from_str_with_links(source) |srv| {
+        run(srv.clone(), test::extract(srv))
+    };
+    fail_unless!(doc.cratemod().mods()[0].structs()[0].impls.len() == 1);
+}
+
+#[cfg(test)]
+mod test {
+    use astsrv;
+    use doc;
+    use extract;
+    use impl_pass::run;
+    use path_pass;
+    use tystr_pass;
+
+    pub fn extract(srv: astsrv::Srv) -> doc::Doc {
+        let doc = extract::from_srv(srv.clone(), ~"");
+        let doc = (path_pass::mk_pass().f)(srv.clone(), doc);
+        (tystr_pass::mk_pass().f)(srv.clone(), doc)
+    }
+
+    pub fn mk_doc(source: ~str) -> doc::Doc {
+        do astsrv::from_str(copy source) |srv| {
+            run(srv.clone(), extract(srv.clone()))
+        }
+    }
+}
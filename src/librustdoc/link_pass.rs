@@ -0,0 +1,467 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Links the item paths written in doc comments to the docs of the items
+they name.
+
+A path in backticks is linked when it names an item, and is otherwise
+left as code. A path in square brackets, or a path with more than one
+name in backticks, is clearly meant as a link, so the AST service warns
+about it when it names nothing. Paths are resolved as if they were
+written where the item they document is, and a path to a method links to
+the type or trait the method is on. Code blocks are left alone.
+*/
+
+use core::prelude::*;
+
+use astsrv;
+use config;
+use doc::ItemUtils;
+use doc;
+use doctest;
+use fold::Fold;
+use fold;
+use html_writer;
+use pass::Pass;
+
+use core::char;
+use core::str;
+use core::vec;
+
+pub fn mk_pass(config: config::Config) -> Pass {
+    Pass {
+        name: ~"link",
+        f: |srv, doc| run(srv, doc, copy config)
+    }
+}
+
+struct Ctxt {
+    html: bool,
+    hrefs: @html_writer::Hrefs,
+    links: @astsrv::LinkMap
+}
+
+impl Clone for Ctxt {
+    fn clone(&self) -> Ctxt {
+        Ctxt {
+            html: self.html,
+            hrefs: self.hrefs,
+            links: self.links
+        }
+    }
+}
+
+fn run(
+    srv: astsrv::Srv,
+    doc: doc::Doc,
+    config: config::Config
+) -> doc::Doc {
+    let links = do astsrv::exec(srv) |ctxt| { copy *ctxt.doc_links };
+    let ctxt = Ctxt {
+        html: config.output_format == config::NativeHtml,
+        hrefs: @html_writer::item_hrefs(&config, &doc),
+        links: @links
+    };
+    let fold = Fold {
+        ctxt: ctxt.clone(),
+        fold_item: fold_item,
+        fold_enum: fold_enum,
+        fold_trait: fold_trait,
+        fold_impl: fold_impl,
+        fold_type: fold_type,
+        fold_struct: fold_struct,
+        .. fold::default_any_fold(ctxt)
+    };
+    (fold.fold_doc)(&fold, doc)
+}
+
+/**
+ * The paths in `text` that may be links, each with whether it is clearly
+ * meant as one
+ */
+pub fn find_paths(text: &str) -> ~[(~str, bool)] {
+    let mut paths = ~[];
+    scan(text, |path, explicit| {
+        paths.push((str::from_slice(path), explicit));
+        None
+    });
+    paths
+}
+
+/**
+ * Copies `text`, making links of the paths in it that `link` returns an
+ * href for. `link` is called with each path that may be a link, and
+ * whether it is clearly meant as one.
+ */
+fn scan(text: &str, link: &fn(&str, bool) -> Option<~str>) -> ~str {
+    let mut lines = ~[];
+    // The marker of the fenced block we are in
+    let mut fence = None;
+    let mut indented = false;
+    let mut prev_blank = true;
+
+    for str::lines_any(text).each |line| {
+        let line: &str = *line;
+        let blank = str::is_whitespace(line);
+        if fence.is_some() {
+            if str::starts_with(line, *fence.get_ref()) {
+                fence = None;
+                prev_blank = false;
+            }
+            lines.push(str::from_slice(line));
+        } else if doctest::is_indented(line) && (indented || prev_blank)
+            || indented && blank {
+            indented = true;
+            lines.push(str::from_slice(line));
+        } else {
+            indented = false;
+            prev_blank = blank;
+            match doctest::fence_start(line) {
+              Some((marker, _)) => {
+                fence = Some(marker);
+                lines.push(str::from_slice(line));
+              }
+              None => lines.push(link_line(line, link))
+            }
+        }
+    }
+    str::connect(lines, ~"\n")
+}
+
+fn link_line(line: &str, link: &fn(&str, bool) -> Option<~str>) -> ~str {
+    let mut linked = ~"";
+    let mut i = 0;
+    while i < line.len() {
+        let str::CharRange {ch, next} = str::char_range_at(line, i);
+        let end = if ch == '\\' && next < line.len() {
+            str::char_range_at(line, next).next
+        } else if ch == '`' {
+            match str::find_char_from(line, '`', next) {
+              Some(end) => {
+                let code = str::slice(line, next, end);
+                let href = do path_of(code).chain |path| {
+                    link(path, str::contains(path, "::"))
+                };
+                match href {
+                  Some(href) => {
+                    linked += fmt!("[`%s`](%s)", code, href);
+                    i = end + 1;
+                    loop;
+                  }
+                  None => end + 1
+                }
+              }
+              None => next
+            }
+        } else if ch == '[' {
+            match str::find_char_from(line, ']', next) {
+              Some(mid) if !follows_link_text(line, mid + 1) => {
+                let text = str::slice(line, next, mid);
+                let path = if text.len() > 1 && str::starts_with(text, "`")
+                    && str::ends_with(text, "`") {
+                    path_of(str::slice(text, 1, text.len() - 1))
+                } else {
+                    path_of(text)
+                };
+                match path {
+                  Some(path) => match link(path, true) {
+                    Some(href) => {
+                      linked += fmt!("[%s](%s)", text, href);
+                      i = mid + 1;
+                      loop;
+                    }
+                    None => mid + 1
+                  },
+                  None => next
+                }
+              }
+              // Already a link; copy it whole
+              Some(mid) if str::starts_with(str::view(line, mid + 1,
+                                                      line.len()), "(") => {
+                match str::find_char_from(line, ')', mid) {
+                  Some(end) => end + 1,
+                  None => line.len()
+                }
+              }
+              _ => next
+            }
+        } else {
+            next
+        };
+        linked += str::slice(line, i, end);
+        i = end;
+    }
+    linked
+}
+
+/// Whether `[text]` ending before `pos` is the text of a markdown link or
+/// of a link reference
+fn follows_link_text(line: &str, pos: uint) -> bool {
+    let rest = str::view(line, pos, line.len());
+    str::starts_with(rest, "(") || str::starts_with(rest, "[")
+        || str::starts_with(rest, ":")
+}
+
+/// The path `text` is, without the parens that may follow a function
+fn path_of(text: &str) -> Option<~str> {
+    let path = if str::ends_with(text, "()") {
+        str::slice(text, 0, text.len() - 2)
+    } else {
+        str::from_slice(text)
+    };
+    let names = if str::starts_with(path, "::") {
+        str::split_str(str::view(path, 2, path.len()), "::")
+    } else {
+        str::split_str(path, "::")
+    };
+    if vec::all(names, |name| is_ident(*name)) { Some(path) } else { None }
+}
+
+fn is_ident(name: &str) -> bool {
+    !str::is_empty(name)
+        && (char::is_alphabetic(name[0] as char) || name[0] == '_' as u8)
+        && str::all(name, |c| char::is_alphanumeric(c) || c == '_')
+}
+
+/// Where `path`, written in the docs of the item `id`, is linked to
+fn href(ctxt: &Ctxt, id: doc::AstId, path: &str) -> Option<~str> {
+    let link = match ctxt.links.find(&id) {
+      Some(links) => {
+        vec::find(*links, |link| str::eq_slice(link.first(), path))
+            .map(|link| link.second())
+      }
+      None => None
+    };
+    match link {
+      Some(astsrv::LocalLink(item_id)) => {
+        ctxt.hrefs.find(&item_id).map(|href| copy **href)
+      }
+      Some(astsrv::ExternLink(ref item_path)) if ctxt.html => {
+        Some(html_writer::extern_href(*item_path, "type"))
+      }
+      Some(astsrv::ExternValueLink(ref item_path)) if ctxt.html => {
+        Some(html_writer::extern_href(*item_path, "value"))
+      }
+      // Not documented, or not anywhere this output format can link to
+      _ => None
+    }
+}
+
+fn link_text(ctxt: &Ctxt, id: doc::AstId, text: &str) -> ~str {
+    scan(text, |path, _| href(ctxt, id, path))
+}
+
+fn link_desc(ctxt: &Ctxt, id: doc::AstId, desc: &Option<~str>)
+    -> Option<~str> {
+    desc.map(|desc| link_text(ctxt, id, *desc))
+}
+
+fn link_sections(
+    ctxt: &Ctxt,
+    id: doc::AstId,
+    sections: &[doc::Section]
+) -> ~[doc::Section] {
+    do sections.map |section| {
+        doc::Section {
+            body: link_text(ctxt, id, section.body),
+            .. copy *section
+        }
+    }
+}
+
+fn link_methods(
+    ctxt: &Ctxt,
+    id: doc::AstId,
+    methods: &[doc::MethodDoc]
+) -> ~[doc::MethodDoc] {
+    do methods.map |method| {
+        doc::MethodDoc {
+            desc: link_desc(ctxt, id, &method.desc),
+            sections: link_sections(ctxt, id, method.sections),
+            .. copy *method
+        }
+    }
+}
+
+/// Links the text of the impls that were moved to a type
+fn link_impls(ctxt: &Ctxt, impls: &[doc::ImplDoc]) -> ~[doc::ImplDoc] {
+    do impls.map |impl_doc| {
+        link_impl(ctxt, copy *impl_doc)
+    }
+}
+
+fn link_impl(ctxt: &Ctxt, doc: doc::ImplDoc) -> doc::ImplDoc {
+    let id = doc.id();
+    doc::ImplDoc {
+        item: doc::ItemDoc {
+            desc: link_desc(ctxt, id, &doc.item.desc),
+            sections: link_sections(ctxt, id, doc.item.sections),
+            .. copy doc.item
+        },
+        methods: link_methods(ctxt, id, doc.methods),
+        .. doc
+    }
+}
+
+fn fold_item(fold: &fold::Fold<Ctxt>, doc: doc::ItemDoc) -> doc::ItemDoc {
+    let doc = fold::default_seq_fold_item(fold, doc);
+
+    doc::ItemDoc {
+        desc: link_desc(&fold.ctxt, doc.id, &doc.desc),
+        sections: link_sections(&fold.ctxt, doc.id, doc.sections),
+        .. doc
+    }
+}
+
+fn fold_enum(fold: &fold::Fold<Ctxt>, doc: doc::EnumDoc) -> doc::EnumDoc {
+    let doc = fold::default_seq_fold_enum(fold, doc);
+    let id = doc.id();
+
+    doc::EnumDoc {
+        variants: do doc.variants.map |variant| {
+            doc::VariantDoc {
+                desc: link_desc(&fold.ctxt, id, &variant.desc),
+                .. copy *variant
+            }
+        },
+        impls: link_impls(&fold.ctxt, doc.impls),
+        .. doc
+    }
+}
+
+fn fold_trait(fold: &fold::Fold<Ctxt>, doc: doc::TraitDoc) -> doc::TraitDoc {
+    let doc = fold::default_seq_fold_trait(fold, doc);
+
+    doc::TraitDoc {
+        methods: link_methods(&fold.ctxt, doc.id(), doc.methods),
+        .. doc
+    }
+}
+
+fn fold_impl(fold: &fold::Fold<Ctxt>, doc: doc::ImplDoc) -> doc::ImplDoc {
+    // Not folded by default, so that its item isn't linked twice
+    link_impl(&fold.ctxt, doc)
+}
+
+fn fold_type(fold: &fold::Fold<Ctxt>, doc: doc::TyDoc) -> doc::TyDoc {
+    let doc = fold::default_seq_fold_type(fold, doc);
+
+    doc::TyDoc {
+        impls: link_impls(&fold.ctxt, doc.impls),
+        .. doc
+    }
+}
+
+fn fold_struct(
+    fold: &fold::Fold<Ctxt>,
+    doc: doc::StructDoc
+) -> doc::StructDoc {
+    let doc = fold::default_seq_fold_struct(fold, doc);
+
+    doc::StructDoc {
+        impls: link_impls(&fold.ctxt, doc.impls),
+        .. doc
+    }
+}
+
+#[test]
+fn should_find_paths_in_backticks_and_brackets() {
+    fail_unless!(find_paths("see `a`, `a::b()`, [c] and [`d`]")
+                 == ~[(~"a", false), (~"a::b", true), (~"c", true),
+                      (~"d", true)]);
+}
+
+#[test]
+fn should_not_find_code_or_links() {
+    fail_unless!(find_paths("`a + b`, [c](d.html), [e][1] and \\`f`")
+                 .is_empty());
+}
+
+#[test]
+fn should_not_find_paths_in_code_blocks() {
+    let text = ~"a\n\n    `b`\n\n```\n`c`\n```\n`d`";
+    fail_unless!(find_paths(text) == ~[(~"d", false)]);
+}
+
+#[test]
+fn should_link_paths() {
+    let text = scan("`a`, [b] and `c`", |path, _| {
+        if path == "c" { None } else { Some(fmt!("%s.html", path)) }
+    });
+    fail_unless!(text == ~"[`a`](a.html), [b](b.html) and `c`");
+}
+
+#[test]
+fn should_link_paths_in_docs() {
+    let doc = test::mk_doc(
+        ~"#[doc = \"Makes an `S`\"] fn a() { } pub struct S { x: int }");
+    fail_unless!(doc.cratemod().fns()[0].desc()
+                 == Some(~"Makes an [`S`](test.html#type.S)"));
+}
+
+#[test]
+fn should_link_paths_in_method_docs() {
+    let doc = test::mk_doc(
+        ~"pub struct S { x: int } \
+         impl S { #[doc = \"Like [a]\"] fn b(&self) { } } fn a() { }");
+    fail_unless!(doc.cratemod().structs()[0].impls[0].methods[0].desc
+                 == Some(~"Like [a](test.html#value.a)"));
+}
+
+#[test]
+fn should_link_methods_to_their_type() {
+    let doc = test::mk_doc(
+        ~"#[doc = \"See `S::b`\"] fn a() { } \
+         pub struct S { x: int } impl S { fn b(&self) { } }");
+    fail_unless!(doc.cratemod().fns()[0].desc()
+                 == Some(~"See [`S::b`](test.html#type.S)"));
+}
+
+#[test]
+fn should_resolve_paths_where_the_item_is() {
+    let doc = test::mk_doc(
+        ~"pub mod a { #[doc = \"See `S`\"] pub fn b() { } \
+         pub struct S { x: int } } pub struct S { x: int }");
+    fail_unless!(doc.cratemod().mods()[0].fns()[0].desc()
+                 == Some(~"See [`S`](test.html#type.a::S)"));
+}
+
+#[cfg(test)]
+mod test {
+    use astsrv;
+    use attr_pass;
+    use config;
+    use doc;
+    use extract;
+    use impl_pass;
+    use link_pass::run;
+    use page_pass;
+    use path_pass;
+    use trim_pass;
+
+    pub fn mk_doc(source: ~str) -> doc::Doc {
+        do astsrv::from_str_with_links(copy source) |srv| {
+            let config = config::Config {
+                output_format: config::NativeHtml,
+                output_style: config::DocPerCrate,
+                .. config::default_config(&Path("test"))
+            };
+            let doc = extract::from_srv(srv.clone(), ~"test");
+            let doc = (path_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (attr_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (trim_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (impl_pass::mk_pass().f)(srv.clone(), doc);
+            let doc = (page_pass::mk_pass(config.output_style).f)(
+                srv.clone(), doc);
+            run(srv.clone(), doc, config)
+        }
+    }
+}
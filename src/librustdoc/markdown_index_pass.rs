@@ -18,6 +18,7 @@ use doc::ItemUtils;
 use doc;
 use fold::Fold;
 use fold;
+use html_writer;
 use markdown_pass;
 use markdown_writer;
 use pass::Pass;
@@ -102,9 +103,7 @@ fn item_to_entry(
         markdown_writer::make_filename(config,
                                        doc::ItemPage(copy doc)).to_str()
       }
-      _ => {
-        ~"#" + pandoc_header_id(markdown_pass::header_text(copy doc))
-      }
+      _ => ~"#" + item_anchor(&config, &doc)
     };
 
     doc::IndexEntry {
@@ -115,6 +114,15 @@ fn item_to_entry(
     }
 }
 
+/// The id of the header `doc` is written under, for the output format
+pub fn item_anchor(config: &config::Config, doc: &doc::ItemTag) -> ~str {
+    if config.output_format == config::NativeHtml {
+        html_writer::anchor(config, doc)
+    } else {
+        pandoc_header_id(markdown_pass::header_text(copy *doc))
+    }
+}
+
 fn pandoc_header_id(header: &str) -> ~str {
 
     // http://johnmacfarlane.net/pandoc/README.html#headers
@@ -212,6 +220,19 @@ fn should_index_mod_contents_multi_page() {
     });
 }
 
+#[test]
+fn should_index_mod_contents_with_html_anchors() {
+    let doc = test::mk_doc_(
+        config::NativeHtml,
+        config::DocPerMod,
+        ~"mod a { } fn b() { } struct C { x: int }"
+    );
+    let entries = copy (&doc.cratemod().index).get().entries;
+    fail_unless!(entries[0].link == ~"a.html");
+    fail_unless!(entries[1].link == ~"#value.b");
+    fail_unless!(entries[2].link == ~"#type.C");
+}
+
 #[test]
 fn should_index_foreign_mod_pages() {
     let doc = test::mk_doc(
@@ -266,8 +287,15 @@ mod test {
 
     pub fn mk_doc(output_style: config::OutputStyle, source: ~str)
                -> doc::Doc {
+        mk_doc_(config::PandocHtml, output_style, source)
+    }
+
+    pub fn mk_doc_(output_format: config::OutputFormat,
+                   output_style: config::OutputStyle,
+                   source: ~str) -> doc::Doc {
         do astsrv::from_str(source) |srv| {
             let config = config::Config {
+                output_format: output_format,
                 output_style: output_style,
                 .. config::default_config(&Path("whatever"))
             };
@@ -150,7 +150,7 @@ fn write_title(ctxt: &Ctxt, page: doc::Page) {
     ctxt.w.write_line(~"");
 }
 
-fn make_title(page: doc::Page) -> ~str {
+pub fn make_title(page: doc::Page) -> ~str {
     let item = match page {
       doc::CratePage(CrateDoc) => {
         doc::ModTag(copy CrateDoc.topmod)
@@ -612,6 +612,7 @@ fn write_enum(
 ) {
     write_common(ctxt, doc.desc(), doc.sections());
     write_variants(ctxt, doc.variants);
+    write_impls(ctxt, doc.impls);
 }
 
 #[test]
@@ -692,17 +693,18 @@ fn should_write_variant_list_with_signatures() {
 
 fn write_trait(ctxt: &Ctxt, doc: doc::TraitDoc) {
     write_common(ctxt, doc.desc(), doc.sections());
-    write_methods(ctxt, doc.methods);
+    write_methods(ctxt, H3, doc.methods);
+    write_implementors(ctxt, doc.implementors);
 }
 
-fn write_methods(ctxt: &Ctxt, docs: &[doc::MethodDoc]) {
+fn write_methods(ctxt: &Ctxt, lvl: Hlvl, docs: &[doc::MethodDoc]) {
     for vec::each(docs) |doc| {
-        write_method(ctxt, copy *doc);
+        write_method(ctxt, lvl, copy *doc);
     }
 }
 
-fn write_method(ctxt: &Ctxt, doc: doc::MethodDoc) {
-    write_header_(ctxt, H3, header_text_(~"Method", doc.name));
+fn write_method(ctxt: &Ctxt, lvl: Hlvl, doc: doc::MethodDoc) {
+    write_header_(ctxt, lvl, header_text_(~"Method", doc.name));
     write_fnlike(
         ctxt,
         copy doc.sig,
@@ -738,9 +740,42 @@ fn should_write_trait_method_signature() {
     fail_unless!(str::contains(markdown, ~"\n    fn a(&self)"));
 }
 
+fn write_implementors(ctxt: &Ctxt, docs: &[doc::ImplDoc]) {
+    if vec::is_empty(docs) {
+        return;
+    }
+
+    write_header_(ctxt, H4, ~"Implementors");
+
+    for vec::each(docs) |doc| {
+        ctxt.w.write_line(fmt!("* `%s`", (&doc.self_ty).get()));
+    }
+
+    ctxt.w.write_line(~"");
+}
+
+#[test]
+fn should_write_implementors() {
+    let markdown = test::render(
+        ~"trait i { } struct S { x: int } \
+         impl i for S { } impl i for int { }");
+    fail_unless!(str::contains(
+        markdown,
+        ~"\n\n#### Implementors\n\n* `S`\n* `int`\n\n"));
+}
+
 fn write_impl(ctxt: &Ctxt, doc: doc::ImplDoc) {
     write_common(ctxt, doc.desc(), doc.sections());
-    write_methods(ctxt, doc.methods);
+    write_methods(ctxt, H3, doc.methods);
+}
+
+/// Writes the impls for a type under its header
+fn write_impls(ctxt: &Ctxt, docs: &[doc::ImplDoc]) {
+    for vec::each(docs) |doc| {
+        write_header(ctxt, H3, doc::ImplTag(copy *doc));
+        write_common(ctxt, doc.desc(), doc.sections());
+        write_methods(ctxt, H4, doc.methods);
+    }
 }
 
 #[test]
@@ -783,6 +818,7 @@ fn write_type(
 ) {
     write_sig(ctxt, copy doc.sig);
     write_common(ctxt, doc.desc(), doc.sections());
+    write_impls(ctxt, doc.impls);
 }
 
 #[test]
@@ -810,6 +846,7 @@ fn write_struct(
 ) {
     write_sig(ctxt, copy doc.sig);
     write_common(ctxt, doc.desc(), doc.sections());
+    write_impls(ctxt, doc.impls);
 }
 
 #[test]
@@ -818,6 +855,18 @@ fn should_write_struct_header() {
     fail_unless!(str::contains(markdown, ~"## Struct `S`\n\n"));
 }
 
+#[test]
+fn should_write_impls_under_their_type() {
+    let markdown = test::render(
+        ~"struct S { field: () } \
+         impl Clone for S { fn clone(&self) -> S { fail!() } }");
+    fail_unless!(str::contains(
+        markdown,
+        ~"### Implementation of `Clone` for `S`\n\n\
+         #### Method `clone`\n\n"));
+    fail_unless!(!str::contains(markdown, ~"\n## Implementation"));
+}
+
 #[cfg(test)]
 mod test {
     use astsrv;
@@ -826,6 +875,7 @@ mod test {
     use desc_to_brief_pass;
     use doc;
     use extract;
+    use impl_pass;
     use markdown_index_pass;
     use markdown_pass::{mk_pass, write_markdown};
     use markdown_writer;
@@ -849,6 +899,7 @@ mod test {
         do astsrv::from_str(source) |srv| {
 
             let config = config::Config {
+                output_format: config::PandocHtml,
                 output_style: config::DocPerCrate,
                 .. config::default_config(&Path("whatever"))
             };
@@ -869,6 +920,8 @@ mod test {
             debug!("doc (trim): %?", doc);
             let doc = (trim_pass::mk_pass().f)(srv.clone(), doc);
             debug!("doc (sectionalize): %?", doc);
+            let doc = (impl_pass::mk_pass().f)(srv.clone(), doc);
+            debug!("doc (impl): %?", doc);
             let doc = (markdown_index_pass::mk_pass(config).f)(
                 srv.clone(), doc);
             debug!("doc (index): %?", doc);
@@ -55,7 +55,8 @@ impl WriterUtils for Writer {
 
 pub fn make_writer_factory(config: config::Config) -> WriterFactory {
     match config.output_format {
-      config::Markdown => {
+      config::Markdown | config::NativeHtml => {
+        // Written as is
         markdown_writer_factory(config)
       }
       config::PandocHtml => {
@@ -197,7 +198,7 @@ pub fn make_filename(
     let filename = {
         match page {
           doc::CratePage(doc) => {
-            if config.output_format == config::PandocHtml &&
+            if config.output_format != config::Markdown &&
                 config.output_style == config::DocPerMod {
                 ~"index"
             } else {
@@ -212,7 +213,7 @@ pub fn make_filename(
     };
     let ext = match config.output_format {
       config::Markdown => ~"md",
-      config::PandocHtml => ~"html"
+      config::PandocHtml | config::NativeHtml => ~"html"
     };
 
     Path(filename).with_filetype(ext)
@@ -246,6 +247,20 @@ fn should_name_html_crate_file_name_index_html_when_doc_per_mod() {
     fail_unless!(filename.to_str() == ~"output/dir/index.html");
 }
 
+#[test]
+fn should_name_native_html_crate_file_name_index_html() {
+    let config = config::Config {
+        output_dir: Path("output/dir"),
+        output_format: config::NativeHtml,
+        output_style: config::DocPerMod,
+        .. config::default_config(&Path("input/test.rc"))
+    };
+    let doc = test::mk_doc(~"test", ~"");
+    let page = doc::CratePage(doc.CrateDoc());
+    let filename = make_local_filename(config, page);
+    fail_unless!(filename.to_str() == ~"output/dir/index.html");
+}
+
 #[test]
 fn should_name_mod_file_names_by_path() {
     let config = config::Config {
@@ -277,7 +292,7 @@ mod test {
     }
 }
 
-fn write_file(path: &Path, s: ~str) {
+pub fn write_file(path: &Path, s: ~str) {
     use core::io::WriterUtil;
 
     match io::file_writer(path, ~[io::Create, io::Truncate]) {
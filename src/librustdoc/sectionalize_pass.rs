@@ -110,9 +110,16 @@ fn sectionalize(desc: Option<~str>) -> (Option<~str>, ~[doc::Section]) {
     let mut new_desc = None::<~str>;
     let mut current_section = None;
     let mut sections = ~[];
+    // Lines in fenced code blocks are never headers, even the hidden
+    // lines of an example that begin with '# '
+    let mut in_fence = false;
 
     for lines.each |line| {
-        match parse_header(copy *line) {
+        if str::starts_with(*line, "```") || str::starts_with(*line, "~~~") {
+            in_fence = !in_fence;
+        }
+        let header = if in_fence { None } else { parse_header(copy *line) };
+        match header {
           Some(header) => {
             if current_section.is_some() {
                 sections += ~[(&current_section).get()];
@@ -186,6 +193,16 @@ fn should_create_section_bodies() {
         ~"Body"));
 }
 
+#[test]
+fn should_not_create_sections_from_fenced_code() {
+    let doc = test::mk_doc(
+        ~"#[doc = \"\
+         Text\n```\n# use core::vec;\n```\"]\
+         mod a {
+         }");
+    fail_unless!(vec::is_empty(doc.cratemod().mods()[0].item.sections));
+}
+
 #[test]
 fn should_not_create_sections_from_indented_headers() {
     let doc = test::mk_doc(
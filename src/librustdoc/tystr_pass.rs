@@ -330,7 +330,7 @@ fn fold_type(
 
     let srv = fold.ctxt.clone();
 
-    doc::SimpleItemDoc {
+    doc::TyDoc {
         sig: {
             let doc = copy doc;
             do astsrv::exec(srv) |ctxt| {
@@ -32,7 +32,7 @@ pub fn mk_pass() -> Pass {
     text_pass::mk_pass(~"unindent", unindent)
 }
 
-fn unindent(s: &str) -> ~str {
+pub fn unindent(s: &str) -> ~str {
     let lines = str::lines_any(s);
     let mut saw_first_line = false;
     let mut saw_second_line = false;
@@ -23,6 +23,29 @@
 
 extern mod std;
 
-fn print<T>(result: T) {
-    io::println(fmt!("%?", result));
+use core::hashmap::linear::LinearMap;
+use std::ebml::{reader, writer};
+use std::serialize::{Decodable, Encodable};
+
+// Must match the layout of `Env` in rusti.rc, which hands us a pointer
+// to its copy.
+type Env = LinearMap<~str, ~[u8]>;
+
+fn print<T>(name: &str, ty: &str, value: &T) {
+    io::print(fmt!("%s: %s = ", name, ty));
+    core::repr::write_repr(io::stdout(), value);
+    io::println("");
+}
+
+fn load<T:Decodable<reader::Decoder>>(env: &mut Env, name: &str) -> T {
+    let bytes = copy *env.get(&name.to_owned());
+    Decodable::decode(&reader::Decoder(reader::Doc(@bytes)))
+}
+
+fn save<T:Encodable<writer::Encoder>>(env: &mut Env, name: &str,
+                                      value: &T) {
+    let bytes = do io::with_bytes_writer |wr| {
+        value.encode(&writer::Encoder(wr))
+    };
+    env.insert(name.to_owned(), bytes);
 }
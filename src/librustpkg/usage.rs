@@ -14,19 +14,38 @@ pub fn general() {
     io::println(~"Usage: rustpkg [options] <cmd> [args..]
 
 Where <cmd> is one of:
-    build, clean, do, info, install, prefer, test, uninstall, unprefer
+    bench, build, clean, do, info, install, prefer, test, uninstall,
+    unprefer, update, vendor
 
 Options:
 
     -h, --help                  Display this message
-    <cmd> -h, <cmd> --help      Display help for <cmd>");
+    <cmd> -h, <cmd> --help      Display help for <cmd>
+    --vendor <dir>              Look for dependency sources in <dir>
+                                (defaults to ./vendor if it exists)");
+}
+
+pub fn bench() {
+    io::println(~"rustpkg [options..] bench [filter]
+
+Build the package in the current directory with the test flag like `rustpkg
+test`, then run the benchmarks of every test crate, or only those whose
+names contain the filter, and list their results together.
+
+Options:
+    -c, --cfg      Pass a cfg flag to the package script");
 }
 
 pub fn build() {
     io::println(~"rustpkg [options..] build
 
 Build all targets described in the package script in the current
-directory.
+directory. A dependency may restrict its version with a semver
+requirement, as in #[pkg_dep(url = \"...\", vers = \">=0.4, <0.6\")]; the
+highest installed or fetched version satisfying the requirements of every
+package needing it is used. The version and content hash each dependency
+resolved to are recorded in pkg.lock, and later builds use exactly those
+until `rustpkg update` is run.
 
 Options:
     -c, --cfg      Pass a cfg flag to the package script");
@@ -47,9 +66,11 @@ by tagging a function with the attribute `#[pkg_do(cmd)]`.");
 }
 
 pub fn info() {
-    io::println(~"rustpkg [options..] info
+    io::println(~"rustpkg [options..] info [id|name[@version]]
 
-Probe the package script in the current directory for information.
+Probe the package script in the current directory for information. If a
+package is named, show the installed package instead, including the URL
+or directory it was installed from.
 
 Options:
     -j, --json      Output the result as JSON");
@@ -64,9 +85,13 @@ continuing. If the URL is a TAR file (with or without compression),
 extract it before installing. If a URL isn't provided, the package will
 be built and installed from the current directory (which is
 functionally the same as `rustpkg build` and installing the result).
+The URL may also be the path of a local directory, which is copied
+as-is. A copy of the package in the vendor directory (see `rustpkg
+vendor`) is always used before any other source.
 
 Examples:
     rustpkg install
+    rustpkg install ../servo
     rustpkg install git://github.com/mozilla/servo.git
     rustpkg install git://github.com/mozilla/servo.git v0.1.2
     rustpkg install http://rust-lang.org/servo-0.1.2.tar.gz
@@ -88,7 +113,8 @@ pub fn prefer() {
 By default all binaries are given a unique name so that multiple versions can
 coexist. The prefer command will symlink the uniquely named binary to
 the binary directory under its bare name. If version is not supplied, the
-latest version of the package will be preferred.
+latest version of the package will be preferred. The version may also be a
+requirement such as ~1.2, which prefers the latest version matching it.
 
 Example:
     export PATH=$PATH:/home/user/.rustpkg/bin
@@ -110,12 +136,35 @@ information.");
 }
 
 pub fn test() {
-    io::println(~"rustpkg [options..] test
+    io::println(~"rustpkg [options..] test [filter] [--ignored]
 
 Build all targets described in the package script in the current directory
 with the test flag. The test bootstraps will be run afterwards and the output
-and exit code will be redirected.
+and exit code will be redirected. Only tests whose names contain the filter
+are run. The results of every crate and of the custom test listener are
+summed up at the end, along with the slowest tests.
+
+Options:
+    -c, --cfg      Pass a cfg flag to the package script
+    --ignored      Run only the tests marked #[ignore]");
+}
+
+pub fn update() {
+    io::println(~"rustpkg [options..] update
+
+Fetch the dependencies of the package in the current directory again,
+ignoring the versions recorded in pkg.lock, then build it and record
+the newly resolved versions in pkg.lock.
 
 Options:
     -c, --cfg      Pass a cfg flag to the package script");
 }
+
+pub fn vendor() {
+    io::println(~"rustpkg [options..] vendor
+
+Copy the full source of every dependency of the package in the current
+directory, and of their dependencies, into vendor/. Later builds and
+installs from this directory take dependencies from vendor/ instead of
+fetching them, so no network access is needed.");
+}
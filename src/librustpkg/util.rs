@@ -27,6 +27,8 @@ pub struct Package {
     vers: semver::Version,
     bins: ~[~str],
     libs: ~[~str],
+    // The url or local directory the package was installed from
+    source: ~str,
 }
 
 pub fn root() -> Path {
@@ -37,8 +39,9 @@ pub fn root() -> Path {
 }
 
 pub fn is_cmd(cmd: ~str) -> bool {
-    let cmds = &[~"build", ~"clean", ~"do", ~"info", ~"install", ~"prefer",
-                 ~"test", ~"uninstall", ~"unprefer"];
+    let cmds = &[~"bench", ~"build", ~"clean", ~"do", ~"info", ~"install",
+                 ~"prefer", ~"test", ~"uninstall", ~"unprefer", ~"update",
+                 ~"vendor"];
 
     vec::contains(cmds, &cmd)
 }
@@ -261,6 +264,206 @@ pub fn hash(data: ~str) -> ~str {
     hasher.result_str()
 }
 
+/**
+ * Hash the contents of every file under a directory, along with the
+ * file names, so that two copies of a package's source hash the same.
+ * Version control metadata and the lockfile itself are left out.
+ */
+pub fn hash_dir(dir: &Path) -> ~str {
+    let mut files = ~[];
+
+    for os::walk_dir(dir) |file| {
+        if !os::path_is_dir(file) &&
+           !file.components.contains(&~".git") &&
+           file.filename() != Some(~"pkg.lock") {
+            files.push(copy *file);
+        }
+    }
+
+    let files = sort::merge_sort(files, |f1, f2| {
+        f1.to_str() <= f2.to_str()
+    });
+    let hasher = &hash::default_state();
+    let prefix = dir.to_str().len();
+
+    for files.each |file| {
+        match io::read_whole_file(file) {
+            result::Ok(data) => {
+                let name = file.to_str();
+
+                hasher.write_str(name.slice(prefix, name.len()));
+                hasher.input(data);
+            }
+            result::Err(err) => warn(err)
+        }
+    }
+
+    hasher.result_str()
+}
+
+/// A dependency as it was resolved by a build and recorded in pkg.lock
+pub struct LockedDep {
+    url: ~str,
+    target: Option<~str>,
+    id: ~str,
+    vers: semver::Version,
+    hash: ~str
+}
+
+fn lock_to_json(deps: &[LockedDep]) -> json::Json {
+    json::List(do deps.map |dep| {
+        let mut map = ~LinearMap::new();
+
+        map.insert(~"url", json::String(copy dep.url));
+
+        if !dep.target.is_none() {
+            map.insert(~"target", json::String(dep.target.get()));
+        }
+
+        map.insert(~"id", json::String(copy dep.id));
+        map.insert(~"vers", json::String(dep.vers.to_str()));
+        map.insert(~"hash", json::String(copy dep.hash));
+
+        json::Object(map)
+    })
+}
+
+fn lock_from_json(json: &json::Json)
+    -> result::Result<~[LockedDep], ~str> {
+    let list = match *json {
+        json::List(ref list) => list,
+        _ => return result::Err(~"pkg.lock's json is not a list")
+    };
+    let mut deps = ~[];
+
+    for list.each |dep| {
+        let map = match *dep {
+            json::Object(ref map) => map,
+            _ => return result::Err(~"pkg.lock entry is not an object")
+        };
+        let get = |key: ~str| {
+            match map.find(&key) {
+                Some(&json::String(ref str)) => Some(copy *str),
+                _ => None
+            }
+        };
+        let (url, id, vers, hash) = match (get(~"url"), get(~"id"),
+                                           get(~"vers"), get(~"hash")) {
+            (Some(url), Some(id), Some(vers), Some(hash)) => {
+                (url, id, vers, hash)
+            }
+            _ => return result::Err(
+                ~"pkg.lock entry without (url, id, vers, hash) values")
+        };
+        let vers = match parse_vers(vers) {
+            result::Ok(vers) => vers,
+            result::Err(err) => return result::Err(err)
+        };
+
+        deps.push(LockedDep {
+            url: url,
+            target: get(~"target"),
+            id: id,
+            vers: vers,
+            hash: hash
+        });
+    }
+
+    result::Ok(deps)
+}
+
+/// Load the dependencies recorded in a package's pkg.lock, if it has one
+pub fn load_lock(dir: &Path) -> result::Result<~[LockedDep], ~str> {
+    let lock = dir.push(~"pkg.lock");
+
+    if !os::path_exists(&lock) {
+        return result::Ok(~[]);
+    }
+
+    match io::read_whole_file_str(&lock) {
+        result::Ok(str) => {
+            match json::from_str(str) {
+                result::Ok(json) => lock_from_json(&json),
+                result::Err(err) => {
+                    result::Err(fmt!("failed to parse pkg.lock: %s",
+                                     err.to_str()))
+                }
+            }
+        }
+        result::Err(err) => {
+            result::Err(fmt!("failed to read pkg.lock: %s", err))
+        }
+    }
+}
+
+/**
+ * Record the resolved dependencies of a package in its pkg.lock. A package
+ * without dependencies has no pkg.lock, so an old one is removed.
+ */
+pub fn save_lock(dir: &Path, deps: &[LockedDep]) -> bool {
+    let lock = dir.push(~"pkg.lock");
+
+    if deps.is_empty() {
+        if os::path_exists(&lock) && !os::remove_file(&lock) {
+            error(~"failed to remove pkg.lock");
+
+            return false;
+        }
+
+        return true;
+    }
+
+    match io::mk_file_writer(&lock, ~[io::Create, io::Truncate]) {
+        result::Ok(writer) => {
+            writer.write_line(json::to_pretty_str(&lock_to_json(deps)));
+
+            true
+        }
+        result::Err(err) => {
+            error(fmt!("failed to write pkg.lock: %s", err));
+
+            false
+        }
+    }
+}
+
+/// A test run by a test binary, as read back from its log file
+pub struct TestOutcome {
+    name: ~str,
+    // "ok", "failed", "ignored" or the timing of a benchmark
+    result: ~str,
+    ns: u64
+}
+
+/**
+ * Read the log a test binary writes when given `--logfile`. Each line
+ * holds the result, the test name and how long the test ran in
+ * nanoseconds; benchmark results span several words.
+ */
+pub fn parse_test_log(log: &str) -> ~[TestOutcome] {
+    let mut outcomes = ~[];
+
+    for str::lines_each(log) |line| {
+        let words = str::words(line);
+        let len = words.len();
+
+        if len < 3 { loop; }
+
+        let ns = match u64::from_str(words[len - 1]) {
+            Some(ns) => ns,
+            None => loop
+        };
+
+        outcomes.push(TestOutcome {
+            name: copy words[len - 2],
+            result: str::connect(vec::slice(words, 0, len - 2), " "),
+            ns: ns
+        });
+    }
+
+    outcomes
+}
+
 pub fn temp_change_dir<T>(dir: &Path, cb: &fn() -> T) {
     let cwd = os::getcwd();
 
@@ -276,6 +479,32 @@ pub fn touch(path: &Path) {
     }
 }
 
+/// Copy a directory tree into `to`, leaving out any `.git` metadata
+pub fn copy_dir_r(from: &Path, to: &Path) -> bool {
+    let prefix = from.components.len();
+
+    need_dir(to);
+
+    for os::walk_dir(from) |file| {
+        let rel = vec::slice(file.components, prefix, file.components.len());
+
+        if rel.contains(&~".git") { loop; }
+
+        let dest = to.push_many(rel);
+
+        if os::path_is_dir(file) {
+            need_dir(&dest);
+        } else if !os::copy_file(file, &dest) {
+            error(fmt!("failed to copy %s to %s", file.to_str(),
+                       dest.to_str()));
+
+            return false;
+        }
+    }
+
+    true
+}
+
 pub fn remove_dir_r(path: &Path) {
     for os::walk_dir(path) |&file| {
         let mut cdir = file;
@@ -345,6 +574,7 @@ fn _add_pkg(packages: ~[json::Json], pkg: &Package) -> ~[json::Json] {
     map.insert(~"libs", json::List(do pkg.libs.map |&lib| {
         json::String(lib)
     }));
+    map.insert(~"source", json::String(pkg.source));
 
     vec::append(packages, ~[json::Object(map)])
 }
@@ -426,17 +656,20 @@ pub fn load_pkgs() -> result::Result<~[json::Json], ~str> {
     result::Ok(packages)
 }
 
-pub fn get_pkg(id: ~str,
-               vers: Option<~str>) -> result::Result<Package, ~str> {
+/// Every installed version of a package, lowest version first
+pub fn get_pkgs(id: ~str) -> result::Result<~[Package], ~str> {
+    match load_pkgs() {
+        result::Ok(packages) => _get_pkgs(packages, id),
+        result::Err(err) => result::Err(err)
+    }
+}
+
+fn _get_pkgs(packages: ~[json::Json],
+             id: ~str) -> result::Result<~[Package], ~str> {
     let name = match parse_name(id) {
         result::Ok(name) => name,
         result::Err(err) => return result::Err(err)
     };
-    let packages = match load_pkgs() {
-        result::Ok(packages) => packages,
-        result::Err(err) => return result::Err(err)
-    };
-    let mut sel = None;
     let mut possibs = ~[];
     let mut err = None;
 
@@ -482,6 +715,11 @@ pub fn get_pkg(id: ~str,
                         }
                         _ => ~[]
                     };
+                    // Packages installed before sources were recorded
+                    let source = match map.find(&~"source") {
+                        Some(&json::String(ref str)) => copy *str,
+                        _ => ~""
+                    };
                     let package = Package {
                         id: pid,
                         vers: match parse_vers(pvers) {
@@ -493,15 +731,11 @@ pub fn get_pkg(id: ~str,
                             }
                         },
                         bins: bins,
-                        libs: libs
+                        libs: libs,
+                        source: source
                     };
 
-                    if !vers.is_none() && vers.get() == pvers {
-                        sel = Some(package);
-                    }
-                    else {
-                        possibs.push(package);
-                    }
+                    possibs.push(package);
                 }
             }
             _ => {}
@@ -511,18 +745,108 @@ pub fn get_pkg(id: ~str,
     if !err.is_none() {
         return result::Err(err.get());
     }
-    if !sel.is_none() {
-        return result::Ok(sel.get());
+
+    result::Ok(sort::merge_sort(possibs, |v1, v2| {
+        v1.vers <= v2.vers
+    }))
+}
+
+/**
+ * The highest installed version of a package, limited to the versions
+ * matching `vers` if given. `vers` is a semver requirement, so a plain
+ * version selects exactly that version.
+ */
+pub fn get_pkg(id: ~str,
+               vers: Option<~str>) -> result::Result<Package, ~str> {
+    let req = match vers {
+        Some(ref vers) => match semver::parse_req(*vers) {
+            Some(req) => Some(req),
+            None => {
+                return result::Err(
+                    fmt!("invalid version requirement: %s", *vers));
+            }
+        },
+        None => None
+    };
+    let possibs = match get_pkgs(id) {
+        result::Ok(possibs) => possibs,
+        result::Err(err) => return result::Err(err)
+    };
+    let mut sel = None;
+
+    for possibs.each |package| {
+        if req.is_none() || req.get_ref().matches(&package.vers) {
+            sel = Some(copy *package);
+        }
     }
-    if !vers.is_none() || possibs.len() < 1 {
-        return result::Err(~"package not found");
+
+    match sel {
+        Some(package) => result::Ok(package),
+        None => result::Err(~"package not found")
     }
+}
 
-    let possibs = sort::merge_sort(possibs, |v1, v2| {
-        v1.vers <= v2.vers
-    });
+/**
+ * Pick the highest of `candidates` satisfying every requirement in
+ * `reqs`, each paired with the name of the package that made it. When
+ * none does, the error names a requirement nothing satisfies or a pair
+ * of requirements that conflict.
+ */
+pub fn select_vers(name: &str, candidates: &[semver::Version],
+                   reqs: &[(~str, semver::VersionReq)])
+    -> result::Result<semver::Version, ~str> {
+    let mut best: Option<semver::Version> = None;
+
+    for candidates.each |vers| {
+        let ok = do reqs.all |&req| {
+            let (_, req) = req;
+
+            req.matches(vers)
+        };
+
+        if ok && (best.is_none() || *vers > *best.get_ref()) {
+            best = Some(copy *vers);
+        }
+    }
+
+    if !best.is_none() {
+        return result::Ok(best.get());
+    }
+    if reqs.is_empty() {
+        return result::Err(fmt!("no version of %s is available", name));
+    }
+
+    for reqs.each |&req| {
+        let (from, req) = req;
+
+        if !candidates.any(|vers| req.matches(vers)) {
+            return result::Err(
+                fmt!("no version of %s matches %s (required by %s)",
+                     name, req.to_str(), from));
+        }
+    }
+
+    for uint::range(0, reqs.len()) |i| {
+        for uint::range(i + 1, reqs.len()) |j| {
+            let (from_a, req_a) = copy reqs[i];
+            let (from_b, req_b) = copy reqs[j];
+
+            if !candidates.any(|vers| req_a.matches(vers) &&
+                                      req_b.matches(vers)) {
+                return result::Err(
+                    fmt!("conflicting requirements on %s: %s requires %s \
+                          but %s requires %s", name, from_a, req_a.to_str(),
+                         from_b, req_b.to_str()));
+            }
+        }
+    }
+
+    result::Err(fmt!("no version of %s satisfies all of %s", name,
+                     str::connect(do reqs.map |&req| {
+                         let (from, req) = req;
 
-    result::Ok(copy *possibs.last())
+                         fmt!("%s (required by %s)", req.to_str(), from)
+                     }, ", ")))
 }
 
 pub fn add_pkg(pkg: &Package) -> bool {
@@ -623,8 +947,9 @@ pub fn compile_input(sysroot: Option<Path>, input: driver::input, dir: &Path,
     let cfg = driver::build_configuration(sess, binary, input);
     let mut outputs = driver::build_output_filenames(input, &None, &None,
                                                      sess);
-    let (crate, _) = driver::compile_upto(sess, cfg, input, driver::cu_parse,
-                                          Some(outputs));
+    let (crate, _, _) = driver::compile_upto(sess, cfg, input,
+                                             driver::cu_parse,
+                                             Some(outputs));
 
     let mut name = None;
     let mut vers = None;
@@ -788,6 +1113,7 @@ pub fn link_exe(src: &Path, dest: &Path) -> bool {
 
 #[test]
 fn test_is_cmd() {
+    fail_unless!(is_cmd(~"bench"));
     fail_unless!(is_cmd(~"build"));
     fail_unless!(is_cmd(~"clean"));
     fail_unless!(is_cmd(~"do"));
@@ -797,6 +1123,108 @@ fn test_is_cmd() {
     fail_unless!(is_cmd(~"test"));
     fail_unless!(is_cmd(~"uninstall"));
     fail_unless!(is_cmd(~"unprefer"));
+    fail_unless!(is_cmd(~"update"));
+    fail_unless!(is_cmd(~"vendor"));
+}
+
+#[test]
+fn test_lock_json_roundtrip() {
+    let deps = ~[
+        LockedDep {
+            url: ~"git://github.com/mozilla/servo.git",
+            target: Some(~"v0.1.2"),
+            id: ~"org.mozilla.servo",
+            vers: parse_vers(~"0.1.2").get(),
+            hash: hash(~"servo")
+        },
+        LockedDep {
+            url: ~"http://rust-lang.org/machine-1.2.4.tar.gz",
+            target: None,
+            id: ~"org.rust-lang.machine",
+            vers: parse_vers(~"1.2.4").get(),
+            hash: hash(~"machine")
+        }
+    ];
+    let json = json::from_str(json::to_pretty_str(&lock_to_json(deps)));
+    let loaded = lock_from_json(&json.get()).get();
+
+    fail_unless!(loaded.len() == 2);
+    fail_unless!(loaded[0].url == deps[0].url);
+    fail_unless!(loaded[0].target == deps[0].target);
+    fail_unless!(loaded[0].vers == deps[0].vers);
+    fail_unless!(loaded[1].target.is_none());
+    fail_unless!(loaded[1].hash == deps[1].hash);
+}
+
+#[test]
+fn test_save_empty_lock() {
+    let dir = std::tempfile::mkdtemp(&os::tmpdir(), "rustpkg").get();
+    let lock = dir.push(~"pkg.lock");
+    let dep = LockedDep {
+        url: ~"http://rust-lang.org/machine-1.2.4.tar.gz",
+        target: None,
+        id: ~"org.rust-lang.machine",
+        vers: parse_vers(~"1.2.4").get(),
+        hash: hash(~"machine")
+    };
+
+    fail_unless!(save_lock(&dir, ~[dep]));
+    fail_unless!(os::path_exists(&lock));
+    fail_unless!(save_lock(&dir, ~[]));
+    fail_unless!(!os::path_exists(&lock));
+    fail_unless!(load_lock(&dir).get().is_empty());
+
+    os::remove_dir(&dir);
+}
+
+#[test]
+fn test_copy_dir_r() {
+    let dir = std::tempfile::mkdtemp(&os::tmpdir(), "rustpkg").get();
+    let from = dir.push(~"from");
+    let to = dir.push(~"to");
+
+    need_dir(&from);
+    need_dir(&from.push(~"src"));
+    need_dir(&from.push(~".git"));
+    touch(&from.push(~"pkg.rs"));
+    touch(&from.push(~"src").push(~"lib.rs"));
+    touch(&from.push(~".git").push(~"HEAD"));
+
+    fail_unless!(copy_dir_r(&from, &to));
+    fail_unless!(os::path_exists(&to.push(~"pkg.rs")));
+    fail_unless!(os::path_exists(&to.push(~"src").push(~"lib.rs")));
+    fail_unless!(!os::path_exists(&to.push(~".git")));
+    fail_unless!(hash_dir(&from) == hash_dir(&to));
+
+    remove_dir_r(&dir);
+}
+
+#[test]
+fn test_pkg_source() {
+    let pkg = Package {
+        id: ~"org.rust-lang.machine",
+        vers: parse_vers(~"1.2.4").get(),
+        bins: ~[],
+        libs: ~[],
+        source: ~"http://rust-lang.org/machine-1.2.4.tar.gz"
+    };
+    let pkgs = _get_pkgs(_add_pkg(~[], &pkg), copy pkg.id).get();
+
+    fail_unless!(pkgs.len() == 1);
+    fail_unless!(pkgs[0].source == pkg.source);
+
+    // Packages installed before sources were recorded have none
+    let old = json::from_str(~"[{\"id\": \"org.rust-lang.machine\", \
+                                 \"vers\": \"1.2.4\", \"bins\": [], \
+                                 \"libs\": []}]").get();
+    let old = match old {
+        json::List(list) => list,
+        _ => fail!()
+    };
+    let pkgs = _get_pkgs(old, copy pkg.id).get();
+
+    fail_unless!(pkgs.len() == 1);
+    fail_unless!(pkgs[0].source.is_empty());
 }
 
 #[test]
@@ -804,3 +1232,46 @@ fn test_parse_name() {
     fail_unless!(parse_name(~"org.mozilla.servo").get() == ~"servo");
     fail_unless!(parse_name(~"org. mozilla.servo 2131").is_err());
 }
+
+#[test]
+fn test_select_vers() {
+    let candidates = do [~"0.3.0", ~"0.4.1", ~"0.5.2", ~"1.0.0"].map |&v| {
+        semver::parse(v).get()
+    };
+    let req = |from: ~str, s: ~str| (from, semver::parse_req(s).get());
+
+    fail_unless!(select_vers("foo", candidates, ~[]) ==
+                 result::Ok(semver::parse("1.0.0").get()));
+    fail_unless!(select_vers("foo", candidates,
+                             ~[req(~"a", ~">=0.4, <0.6")]) ==
+                 result::Ok(semver::parse("0.5.2").get()));
+    fail_unless!(select_vers("foo", candidates,
+                             ~[req(~"a", ~">=0.4"), req(~"b", ~"~0.4")]) ==
+                 result::Ok(semver::parse("0.4.1").get()));
+    fail_unless!(select_vers("foo", candidates, ~[req(~"a", ~"^2")]) ==
+                 result::Err(~"no version of foo matches ^2 \
+                               (required by a)"));
+    fail_unless!(select_vers("foo", candidates,
+                             ~[req(~"a", ~"~0.4"), req(~"b", ~"^1")]) ==
+                 result::Err(~"conflicting requirements on foo: a requires \
+                               ~0.4 but b requires ^1"));
+}
+
+#[test]
+fn test_parse_test_log() {
+    let outcomes = parse_test_log("ok foo::bar 1500\n\
+                                   failed foo::baz 20\n\
+                                   ignored quux 0\n\
+                                   250 ns/iter (+/- 7) bench::fib 9000\n\
+                                   garbage\n");
+
+    fail_unless!(outcomes.len() == 4);
+    fail_unless!(outcomes[0].name == ~"foo::bar");
+    fail_unless!(outcomes[0].result == ~"ok");
+    fail_unless!(outcomes[0].ns == 1500);
+    fail_unless!(outcomes[1].result == ~"failed");
+    fail_unless!(outcomes[2].result == ~"ignored");
+    fail_unless!(outcomes[3].name == ~"bench::fib");
+    fail_unless!(outcomes[3].result == ~"250 ns/iter (+/- 7)");
+    fail_unless!(outcomes[3].ns == 9000);
+}
@@ -18,6 +18,7 @@ use core::option::{Option, Some, None};
 use core::str;
 use core::to_str::ToStr;
 use core::uint;
+use core::vec;
 
 #[deriving_eq]
 pub enum Identifier {
@@ -237,6 +238,193 @@ pub fn parse(s: &str) -> Option<Version> {
     }
 }
 
+/// A comparison operator in a version requirement
+#[deriving_eq]
+pub enum Op {
+    Ex,         // =
+    Gt,         // >
+    GtEq,       // >=
+    Lt,         // <
+    LtEq,       // <=
+    Tilde,      // ~
+    Compatible  // ^
+}
+
+impl ToStr for Op {
+    #[inline(always)]
+    pure fn to_str(&self) -> ~str {
+        match *self {
+            Ex => ~"=",
+            Gt => ~">",
+            GtEq => ~">=",
+            Lt => ~"<",
+            LtEq => ~"<=",
+            Tilde => ~"~",
+            Compatible => ~"^"
+        }
+    }
+}
+
+/**
+ * A single comparison against a version that may leave out its minor
+ * and patch numbers, such as `>=0.4` or `~1.2.3`. Missing numbers match
+ * anything.
+ */
+#[deriving_eq]
+pub struct Predicate {
+    op: Op,
+    major: uint,
+    minor: Option<uint>,
+    patch: Option<uint>
+}
+
+pure fn cmp_uint(a: uint, b: uint) -> int {
+    if a < b { -1 } else if a > b { 1 } else { 0 }
+}
+
+pub impl Predicate {
+    /**
+     * Compare `v` against the numbers this predicate gives, returning
+     * -1, 0 or 1. A pre-release sorts before the release it precedes.
+     */
+    pure fn cmp(&self, v: &Version) -> int {
+        if v.major != self.major {
+            return cmp_uint(v.major, self.major);
+        }
+        let minor = match self.minor {
+            Some(minor) => minor,
+            None => return 0
+        };
+        if v.minor != minor {
+            return cmp_uint(v.minor, minor);
+        }
+        let patch = match self.patch {
+            Some(patch) => patch,
+            None => return 0
+        };
+        if v.patch != patch {
+            return cmp_uint(v.patch, patch);
+        }
+        if v.pre.is_empty() { 0 } else { -1 }
+    }
+
+    /**
+     * `~1.2.3` allows patch updates and `~1.2` or `~1` allow updates
+     * below the last number given. `^1.2.3` allows any update that keeps
+     * the leftmost non-zero number, so `^0.2.3` means `>=0.2.3, <0.3.0`.
+     */
+    pure fn matches(&self, v: &Version) -> bool {
+        match self.op {
+            Ex => self.cmp(v) == 0,
+            Gt => self.cmp(v) > 0,
+            GtEq => self.cmp(v) >= 0,
+            Lt => self.cmp(v) < 0,
+            LtEq => self.cmp(v) <= 0,
+            Tilde => {
+                self.cmp(v) >= 0 && v.major == self.major &&
+                    (self.minor.is_none() || self.minor == Some(v.minor))
+            }
+            Compatible => {
+                self.cmp(v) >= 0 && v.major == self.major &&
+                    (self.major > 0 || match self.minor {
+                        None => true,
+                        Some(minor) => {
+                            v.minor == minor &&
+                                (minor > 0 || self.patch.is_none() ||
+                                 self.patch == Some(v.patch))
+                        }
+                    })
+            }
+        }
+    }
+}
+
+impl ToStr for Predicate {
+    #[inline(always)]
+    pure fn to_str(&self) -> ~str {
+        let s = self.op.to_str() + self.major.to_str();
+        let s = match self.minor {
+            Some(minor) => s + "." + minor.to_str(),
+            None => s
+        };
+        match self.patch {
+            Some(patch) => s + "." + patch.to_str(),
+            None => s
+        }
+    }
+}
+
+/// A list of predicates, all of which a version must satisfy
+#[deriving_eq]
+pub struct VersionReq {
+    predicates: ~[Predicate]
+}
+
+pub impl VersionReq {
+    pure fn matches(&self, v: &Version) -> bool {
+        vec::all(self.predicates, |p| p.matches(v))
+    }
+}
+
+impl ToStr for VersionReq {
+    #[inline(always)]
+    pure fn to_str(&self) -> ~str {
+        str::connect(self.predicates.map(|p| p.to_str()), ", ")
+    }
+}
+
+fn parse_predicate(s: &str) -> Option<Predicate> {
+    let s = s.trim();
+    let (op, len) = if str::starts_with(s, ">=") { (GtEq, 2) }
+        else if str::starts_with(s, "<=") { (LtEq, 2) }
+        else if str::starts_with(s, ">") { (Gt, 1) }
+        else if str::starts_with(s, "<") { (Lt, 1) }
+        else if str::starts_with(s, "=") { (Ex, 1) }
+        else if str::starts_with(s, "~") { (Tilde, 1) }
+        else if str::starts_with(s, "^") { (Compatible, 1) }
+        else { (Ex, 0) };
+    let parts = str::split_char(str::slice(s, len, s.len()).trim(), '.');
+    let mut nums = ~[];
+
+    if parts.len() > 3 {
+        return None;
+    }
+    for parts.each |part| {
+        if part.is_empty() || !part.all(char::is_digit) {
+            return None;
+        }
+        match uint::from_str(*part) {
+            Some(n) => nums.push(n),
+            None => return None
+        }
+    }
+
+    Some(Predicate {
+        op: op,
+        major: nums[0],
+        minor: if nums.len() > 1 { Some(nums[1]) } else { None },
+        patch: if nums.len() > 2 { Some(nums[2]) } else { None }
+    })
+}
+
+/**
+ * Parse a comma separated list of requirements such as `>=0.4, <0.6`,
+ * `~0.5` or `^1.2`. A version with no operator must match exactly.
+ */
+pub fn parse_req(s: &str) -> Option<VersionReq> {
+    if ! str::is_ascii(s) {
+        return None;
+    }
+    let mut predicates = ~[];
+    for str::split_char(s, ',').each |part| {
+        match parse_predicate(*part) {
+            Some(p) => predicates.push(p),
+            None => return None
+        }
+    }
+    Some(VersionReq { predicates: predicates })
+}
+
 #[test]
 fn test_parse() {
     fail_unless!(parse("") == None);
@@ -392,3 +580,66 @@ fn test_spec_order() {
         i += 1;
     }
 }
+
+#[test]
+fn test_parse_req() {
+    fail_unless!(parse_req("") == None);
+    fail_unless!(parse_req(">=") == None);
+    fail_unless!(parse_req("1.2.3.4") == None);
+    fail_unless!(parse_req("~a.b") == None);
+    fail_unless!(parse_req(">=0.4,") == None);
+    fail_unless!(parse_req("1.2.3-alpha") == None);
+
+    fail_unless!(parse_req("1.2.3") == Some(VersionReq {
+        predicates: ~[Predicate {
+            op: Ex, major: 1u, minor: Some(2u), patch: Some(3u)
+        }]
+    }));
+    fail_unless!(parse_req(" >=0.4, <0.6 ") == Some(VersionReq {
+        predicates: ~[Predicate {
+                          op: GtEq, major: 0u, minor: Some(4u), patch: None
+                      },
+                      Predicate {
+                          op: Lt, major: 0u, minor: Some(6u), patch: None
+                      }]
+    }));
+    fail_unless!(parse_req("^1").get().to_str() == ~"^1");
+    fail_unless!(parse_req("~ 0.5").get().to_str() == ~"~0.5");
+    fail_unless!(parse_req(">=0.4,<0.6").get().to_str() == ~">=0.4, <0.6");
+}
+
+#[test]
+fn test_req_matches() {
+    fn matches(req: &str, v: &str) -> bool {
+        parse_req(req).get().matches(&parse(v).get())
+    }
+
+    fail_unless!(matches("1.2.3", "1.2.3"));
+    fail_unless!(!matches("1.2.3", "1.2.4"));
+    fail_unless!(matches("=1.2", "1.2.7"));
+
+    fail_unless!(matches(">=0.4, <0.6", "0.4.0"));
+    fail_unless!(matches(">=0.4, <0.6", "0.5.9"));
+    fail_unless!(!matches(">=0.4, <0.6", "0.6.0"));
+    fail_unless!(!matches(">=0.4, <0.6", "0.3.9"));
+    fail_unless!(!matches(">=0.4.0", "0.4.0-pre"));
+    fail_unless!(matches(">0.4", "0.5.0"));
+    fail_unless!(!matches(">0.4", "0.4.9"));
+    fail_unless!(matches("<=0.4", "0.4.9"));
+
+    fail_unless!(matches("~0.5", "0.5.3"));
+    fail_unless!(!matches("~0.5", "0.6.0"));
+    fail_unless!(matches("~1.2.3", "1.2.9"));
+    fail_unless!(!matches("~1.2.3", "1.2.2"));
+    fail_unless!(!matches("~1.2.3", "1.3.0"));
+    fail_unless!(matches("~1", "1.9.0"));
+
+    fail_unless!(matches("^1.2", "1.9.0"));
+    fail_unless!(!matches("^1.2", "1.1.0"));
+    fail_unless!(!matches("^1.2", "2.0.0"));
+    fail_unless!(matches("^0.2.3", "0.2.9"));
+    fail_unless!(!matches("^0.2.3", "0.3.0"));
+    fail_unless!(matches("^0.0.3", "0.0.3"));
+    fail_unless!(!matches("^0.0.3", "0.0.4"));
+    fail_unless!(matches("^0", "0.9.0"));
+}
@@ -179,7 +179,6 @@ impl<T:Copy + Ord + Eq> Sort for &'self mut [T] {
 
 const MIN_MERGE: uint = 64;
 const MIN_GALLOP: uint = 7;
-const INITIAL_TMP_STORAGE: uint = 128;
 
 pub fn tim_sort<T:Copy + Ord>(array: &mut [T]) {
     let size = array.len();
@@ -18,6 +18,7 @@
 use getopts;
 use sort;
 use term;
+use time::precise_time_ns;
 
 use core::cmp::Eq;
 
@@ -27,6 +28,7 @@ use core::either;
 use core::io::WriterUtil;
 use core::io;
 use core::comm::{stream, Chan, Port, SharedChan};
+use core::hashmap::linear::LinearMap;
 use core::option;
 use core::prelude::*;
 use core::result;
@@ -223,9 +225,9 @@ pub fn run_tests_console(opts: &TestOpts,
           }
           TeWait(ref test) => st.out.write_str(
               fmt!("test %s ... ", test.name.to_str())),
-          TeResult(copy test, result) => {
+          TeResult(copy test, result, ns) => {
             match st.log_out {
-                Some(f) => write_log(f, result, &test),
+                Some(f) => write_log(f, result, &test, ns),
                 None => ()
             }
             match result {
@@ -316,14 +318,17 @@ pub fn run_tests_console(opts: &TestOpts,
         }
     }
 
-    fn write_log(out: @io::Writer, result: TestResult, test: &TestDesc) {
-        out.write_line(fmt!("%s %s",
+    // The result, the test name and how long the test took to run, in
+    // nanoseconds
+    fn write_log(out: @io::Writer, result: TestResult, test: &TestDesc,
+                 ns: u64) {
+        out.write_line(fmt!("%s %s %s",
                     match result {
                         TrOk => ~"ok",
                         TrFailed => ~"failed",
                         TrIgnored => ~"ignored",
                         TrBench(ref bs) => fmt_bench_samples(bs)
-                    }, test.name.to_str()));
+                    }, test.name.to_str(), ns.to_str()));
     }
 
     fn write_ok(out: @io::Writer, use_color: bool) {
@@ -411,7 +416,8 @@ fn use_color() -> bool { return get_concurrency() == 1; }
 enum TestEvent {
     TeFiltered(~[TestDesc]),
     TeWait(TestDesc),
-    TeResult(TestDesc, TestResult),
+    // The result of a test and its wall clock time in nanoseconds
+    TeResult(TestDesc, TestResult, u64),
 }
 
 type MonitorMsg = (TestDesc, TestResult);
@@ -445,6 +451,9 @@ fn run_tests(opts: &TestOpts,
     let (p, ch) = stream();
     let ch = SharedChan(ch);
 
+    // When each pending test was started, by name
+    let mut started = LinearMap::new();
+
     while pending > 0 || !remaining.is_empty() {
         while pending < concurrency && !remaining.is_empty() {
             let test = remaining.pop();
@@ -454,24 +463,27 @@ fn run_tests(opts: &TestOpts,
                 // that hang forever.
                 callback(TeWait(test.desc));
             }
+            started.insert(test.desc.name.to_str(), precise_time_ns());
             run_test(!opts.run_tests, test, ch.clone());
             pending += 1;
         }
 
         let (desc, result) = p.recv();
+        let start = started.pop(&desc.name.to_str()).get();
         if concurrency != 1 {
             callback(TeWait(desc));
         }
-        callback(TeResult(desc, result));
+        callback(TeResult(desc, result, precise_time_ns() - start));
         pending -= 1;
     }
 
     // All benchmarks run at the end, in serial.
     do vec::consume(filtered_benchs) |_, b| {
         callback(TeWait(copy b.desc));
+        let start = precise_time_ns();
         run_test(!opts.run_benchmarks, b, ch.clone());
         let (test, result) = p.recv();
-        callback(TeResult(test, result));
+        callback(TeResult(test, result, precise_time_ns() - start));
     }
 }
 
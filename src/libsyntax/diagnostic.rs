@@ -13,17 +13,24 @@ use core::prelude::*;
 use codemap::{Pos, span};
 use codemap;
 
+use core::hashmap::linear::LinearMap;
 use core::io::WriterUtil;
 use core::io;
 use core::option;
 use core::str;
+use core::util;
 use core::vec;
 
+use std::json;
 use std::term;
 
+// `code` is the error code of the diagnostic, such as `E0004`, if it has
+// one, and `lint` names the lint that caused it, if any
 pub type Emitter = @fn(cmsp: Option<(@codemap::CodeMap, span)>,
                        msg: &str,
-                       lvl: level);
+                       lvl: level,
+                       code: Option<~str>,
+                       lint: Option<~str>);
 
 // a handler deals with errors; certain errors
 // (fatal, bug, unimpl) may cause immediate exit,
@@ -31,6 +38,7 @@ pub type Emitter = @fn(cmsp: Option<(@codemap::CodeMap, span)>,
 pub trait handler {
     fn fatal(@mut self, msg: &str) -> !;
     fn err(@mut self, msg: &str);
+    fn err_with_code(@mut self, msg: &str, code: &str);
     fn bump_err_count(@mut self);
     fn has_errors(@mut self) -> bool;
     fn abort_if_errors(@mut self);
@@ -43,6 +51,16 @@ pub trait handler {
             cmsp: Option<(@codemap::CodeMap, span)>,
             msg: &str,
             lvl: level);
+    fn emit_with_code(@mut self,
+                      cmsp: Option<(@codemap::CodeMap, span)>,
+                      msg: &str,
+                      lvl: level,
+                      code: &str);
+    fn emit_lint(@mut self,
+                 cmsp: Option<(@codemap::CodeMap, span)>,
+                 msg: &str,
+                 lvl: level,
+                 lint: &str);
 }
 
 // a span-handler is like a handler but also
@@ -50,11 +68,15 @@ pub trait handler {
 // reporting.
 pub trait span_handler {
     fn span_fatal(@mut self, sp: span, msg: &str) -> !;
+    fn span_fatal_with_code(@mut self, sp: span, msg: &str, code: &str) -> !;
     fn span_err(@mut self, sp: span, msg: &str);
+    fn span_err_with_code(@mut self, sp: span, msg: &str, code: &str);
     fn span_warn(@mut self, sp: span, msg: &str);
     fn span_note(@mut self, sp: span, msg: &str);
     fn span_bug(@mut self, sp: span, msg: &str) -> !;
     fn span_unimpl(@mut self, sp: span, msg: &str) -> !;
+    // a warning or error caused by the lint `lint`
+    fn span_lint(@mut self, sp: span, msg: &str, lvl: level, lint: &str);
     fn handler(@mut self) -> @handler;
 }
 
@@ -73,10 +95,18 @@ impl span_handler for CodemapT {
         self.handler.emit(Some((self.cm, sp)), msg, fatal);
         fail!();
     }
+    fn span_fatal_with_code(@mut self, sp: span, msg: &str, code: &str) -> ! {
+        self.handler.emit_with_code(Some((self.cm, sp)), msg, fatal, code);
+        fail!();
+    }
     fn span_err(@mut self, sp: span, msg: &str) {
         self.handler.emit(Some((self.cm, sp)), msg, error);
         self.handler.bump_err_count();
     }
+    fn span_err_with_code(@mut self, sp: span, msg: &str, code: &str) {
+        self.handler.emit_with_code(Some((self.cm, sp)), msg, error, code);
+        self.handler.bump_err_count();
+    }
     fn span_warn(@mut self, sp: span, msg: &str) {
         self.handler.emit(Some((self.cm, sp)), msg, warning);
     }
@@ -89,6 +119,12 @@ impl span_handler for CodemapT {
     fn span_unimpl(@mut self, sp: span, msg: &str) -> ! {
         self.span_bug(sp, ~"unimplemented " + msg);
     }
+    fn span_lint(@mut self, sp: span, msg: &str, lvl: level, lint: &str) {
+        self.handler.emit_lint(Some((self.cm, sp)), msg, lvl, lint);
+        if lvl == error {
+            self.handler.bump_err_count();
+        }
+    }
     fn handler(@mut self) -> @handler {
         self.handler
     }
@@ -96,11 +132,15 @@ impl span_handler for CodemapT {
 
 impl handler for HandlerT {
     fn fatal(@mut self, msg: &str) -> ! {
-        (self.emit)(None, msg, fatal);
+        (self.emit)(None, msg, fatal, None, None);
         fail!();
     }
     fn err(@mut self, msg: &str) {
-        (self.emit)(None, msg, error);
+        (self.emit)(None, msg, error, None, None);
+        self.bump_err_count();
+    }
+    fn err_with_code(@mut self, msg: &str, code: &str) {
+        (self.emit)(None, msg, error, Some(code.to_str()), None);
         self.bump_err_count();
     }
     fn bump_err_count(@mut self) {
@@ -120,10 +160,10 @@ impl handler for HandlerT {
         self.fatal(s);
     }
     fn warn(@mut self, msg: &str) {
-        (self.emit)(None, msg, warning);
+        (self.emit)(None, msg, warning, None, None);
     }
     fn note(@mut self, msg: &str) {
-        (self.emit)(None, msg, note);
+        (self.emit)(None, msg, note, None, None);
     }
     fn bug(@mut self, msg: &str) -> ! {
         self.fatal(ice_msg(msg));
@@ -135,7 +175,21 @@ impl handler for HandlerT {
             cmsp: Option<(@codemap::CodeMap, span)>,
             msg: &str,
             lvl: level) {
-        (self.emit)(cmsp, msg, lvl);
+        (self.emit)(cmsp, msg, lvl, None, None);
+    }
+    fn emit_with_code(@mut self,
+                      cmsp: Option<(@codemap::CodeMap, span)>,
+                      msg: &str,
+                      lvl: level,
+                      code: &str) {
+        (self.emit)(cmsp, msg, lvl, Some(code.to_str()), None);
+    }
+    fn emit_lint(@mut self,
+                 cmsp: Option<(@codemap::CodeMap, span)>,
+                 msg: &str,
+                 lvl: level,
+                 lint: &str) {
+        (self.emit)(cmsp, msg, lvl, None, Some(lint.to_str()));
     }
 }
 
@@ -152,7 +206,9 @@ pub fn mk_handler(emitter: Option<Emitter>) -> @handler {
     let emit: Emitter = match emitter {
         Some(e) => e,
         None => {
-            let emit: Emitter = |cmsp, msg, t| emit(cmsp, msg, t);
+            let emit: Emitter = |cmsp, msg, t, code, lint| {
+                emit(cmsp, msg, t, code, lint)
+            };
             emit
         }
     };
@@ -186,7 +242,7 @@ fn diagnosticcolor(lvl: level) -> u8 {
     }
 }
 
-fn print_diagnostic(topic: ~str, lvl: level, msg: &str) {
+fn print_diagnostic(topic: ~str, lvl: level, msg: &str, code: Option<~str>) {
     let use_color = term::color_supported() &&
         io::stderr().get_type() == io::Screen;
     if !topic.is_empty() {
@@ -195,32 +251,37 @@ fn print_diagnostic(topic: ~str, lvl: level, msg: &str) {
     if use_color {
         term::fg(io::stderr(), diagnosticcolor(lvl));
     }
-    io::stderr().write_str(fmt!("%s:", diagnosticstr(lvl)));
+    match code {
+        Some(code) => {
+            io::stderr().write_str(fmt!("%s[%s]:", diagnosticstr(lvl), code))
+        }
+        None => io::stderr().write_str(fmt!("%s:", diagnosticstr(lvl)))
+    }
     if use_color {
         term::reset(io::stderr());
     }
     io::stderr().write_str(fmt!(" %s\n", msg));
 }
 
-pub fn collect(messages: @mut ~[~str])
-            -> @fn(Option<(@codemap::CodeMap, span)>, &str, level) {
-    let f: @fn(Option<(@codemap::CodeMap, span)>, &str, level) =
-        |_o, msg: &str, _l| { messages.push(msg.to_str()); };
+pub fn collect(messages: @mut ~[~str]) -> Emitter {
+    let f: Emitter =
+        |_o, msg: &str, _l, _code, _lint| { messages.push(msg.to_str()); };
     f
 }
 
-pub fn emit(cmsp: Option<(@codemap::CodeMap, span)>, msg: &str, lvl: level) {
+pub fn emit(cmsp: Option<(@codemap::CodeMap, span)>, msg: &str, lvl: level,
+            code: Option<~str>, _lint: Option<~str>) {
     match cmsp {
       Some((cm, sp)) => {
         let sp = cm.adjust_span(sp);
         let ss = cm.span_to_str(sp);
         let lines = cm.span_to_lines(sp);
-        print_diagnostic(ss, lvl, msg);
+        print_diagnostic(ss, lvl, msg, code);
         highlight_lines(cm, sp, lines);
         print_macro_backtrace(cm, sp);
       }
       None => {
-        print_diagnostic(~"", lvl, msg);
+        print_diagnostic(~"", lvl, msg, code);
       }
     }
 }
@@ -297,13 +358,151 @@ fn print_macro_backtrace(cm: @codemap::CodeMap, sp: span) {
         let ss = option::map_default(&ei.callee.span, @~"",
                                      |span| @cm.span_to_str(*span));
         print_diagnostic(*ss, note,
-                         fmt!("in expansion of %s!", ei.callee.name));
+                         fmt!("in expansion of %s!", ei.callee.name), None);
         let ss = cm.span_to_str(ei.call_site);
-        print_diagnostic(ss, note, ~"expansion site");
+        print_diagnostic(ss, note, ~"expansion site", None);
         print_macro_backtrace(cm, ei.call_site);
     }
 }
 
+/**
+ * Writes diagnostics as JSON objects, one per line, for tools to read.
+ * Each object has the level, the message, the primary span (or null), the
+ * error code (or null), the name of the lint that caused it (or null) and
+ * a list of notes, each with a message and span. Notes emitted right after a
+ * diagnostic are attached to it, so every diagnostic is held back until
+ * the next one arrives or the emitter is flushed. The `JsonFlusher`
+ * returned with the emitter flushes it when dropped, which includes when
+ * the task fails.
+ */
+pub struct JsonEmitter {
+    out: @io::Writer,
+    pending: Option<~json::Object>,
+    notes: ~[json::Json]
+}
+
+pub impl JsonEmitter {
+    static fn new(out: @io::Writer) -> JsonEmitter {
+        JsonEmitter { out: out, pending: None, notes: ~[] }
+    }
+
+    fn emit(&mut self, cmsp: Option<(@codemap::CodeMap, span)>, msg: &str,
+            lvl: level, code: Option<~str>, lint: Option<~str>) {
+        if lvl == note && self.pending.is_some() {
+            self.notes.push(json_note(cmsp, msg));
+            return;
+        }
+
+        self.flush();
+
+        let mut obj = ~LinearMap::new();
+        obj.insert(~"level", json::String(diagnosticstr(lvl)));
+        obj.insert(~"message", json::String(msg.to_str()));
+        obj.insert(~"span", json_span(cmsp));
+        obj.insert(~"code", match code {
+            Some(code) => json::String(code),
+            None => json::Null
+        });
+        obj.insert(~"lint", match lint {
+            Some(lint) => json::String(lint),
+            None => json::Null
+        });
+        self.pending = Some(obj);
+
+        // The backtrace of the macros the span was expanded from
+        match cmsp {
+            Some((cm, sp)) => {
+                let mut sp = sp;
+                loop {
+                    match sp.expn_info {
+                        Some(ei) => {
+                            let msg = fmt!("in expansion of %s!",
+                                           ei.callee.name);
+                            let site = Some((cm, ei.call_site));
+                            self.notes.push(json_note(site, msg));
+                            sp = ei.call_site;
+                        }
+                        None => break
+                    }
+                }
+            }
+            None => {}
+        }
+
+        // Nothing can follow a fatal error
+        if lvl == fatal {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        match util::replace(&mut self.pending, None) {
+            Some(obj) => {
+                let mut obj = obj;
+                let notes = util::replace(&mut self.notes, ~[]);
+                obj.insert(~"notes", json::List(notes));
+                json::to_writer(self.out, &json::Object(obj));
+                self.out.write_str("\n");
+            }
+            None => {}
+        }
+    }
+}
+
+fn json_span(cmsp: Option<(@codemap::CodeMap, span)>) -> json::Json {
+    match cmsp {
+        Some((cm, sp)) => {
+            let sp = cm.adjust_span(sp);
+            let lo = cm.lookup_char_pos(sp.lo);
+            let hi = cm.lookup_char_pos(sp.hi);
+            let start = lo.file.start_pos.to_uint();
+            let mut obj = ~LinearMap::new();
+
+            // Lines count from 1 and columns from 0, as in the text output;
+            // byte offsets are from the start of the file
+            obj.insert(~"file", json::String(copy lo.file.name));
+            obj.insert(~"line_start", json::Number(lo.line as float));
+            obj.insert(~"col_start",
+                       json::Number(lo.col.to_uint() as float));
+            obj.insert(~"line_end", json::Number(hi.line as float));
+            obj.insert(~"col_end", json::Number(hi.col.to_uint() as float));
+            obj.insert(~"byte_start",
+                       json::Number((sp.lo.to_uint() - start) as float));
+            obj.insert(~"byte_end",
+                       json::Number((sp.hi.to_uint() - start) as float));
+            json::Object(obj)
+        }
+        None => json::Null
+    }
+}
+
+fn json_note(cmsp: Option<(@codemap::CodeMap, span)>, msg: &str)
+          -> json::Json {
+    let mut obj = ~LinearMap::new();
+    obj.insert(~"message", json::String(msg.to_str()));
+    obj.insert(~"span", json_span(cmsp));
+    json::Object(obj)
+}
+
+/// Writes out the diagnostic a `JsonEmitter` is holding back when dropped
+pub struct JsonFlusher {
+    priv json: @mut JsonEmitter
+}
+
+impl Drop for JsonFlusher {
+    fn finalize(&self) {
+        self.json.flush();
+    }
+}
+
+pub fn mk_json_emitter(out: @io::Writer) -> (Emitter, JsonFlusher) {
+    let json = @mut JsonEmitter::new(out);
+    let emit: Emitter = |cmsp, msg, lvl, code, lint| {
+        json.emit(cmsp, msg, lvl, code, lint)
+    };
+    (emit, JsonFlusher { json: json })
+}
+
 pub fn expect<T:Copy>(diag: @span_handler,
                        opt: Option<T>,
                        msg: &fn() -> ~str) -> T {
@@ -92,9 +92,13 @@ pub fn expand_include(cx: @ext_ctxt, sp: span, tts: &[ast::token_tree])
 pub fn expand_include_str(cx: @ext_ctxt, sp: span, tts: &[ast::token_tree])
     -> base::MacResult {
     let file = get_single_str_from_tts(cx, sp, tts, "include_str!");
-    let res = io::read_whole_file_str(&res_rel_file(cx, sp, &Path(file)));
+    let path = res_rel_file(cx, sp, &Path(file));
+    let res = io::read_whole_file_str(&path);
     match res {
-      result::Ok(_) => { /* Continue. */ }
+      result::Ok(ref src) => {
+        // Record the file so that it's listed as an input of the crate
+        cx.codemap().new_filemap(path.to_str(), @copy *src);
+      }
       result::Err(ref e) => {
         cx.parse_sess().span_diagnostic.handler().fatal((*e));
       }
@@ -106,8 +110,12 @@ pub fn expand_include_str(cx: @ext_ctxt, sp: span, tts: &[ast::token_tree])
 pub fn expand_include_bin(cx: @ext_ctxt, sp: span, tts: &[ast::token_tree])
     -> base::MacResult {
     let file = get_single_str_from_tts(cx, sp, tts, "include_bin!");
-    match io::read_whole_file(&res_rel_file(cx, sp, &Path(file))) {
+    let path = res_rel_file(cx, sp, &Path(file));
+    match io::read_whole_file(&path) {
       result::Ok(src) => {
+        // Record the file as an input of the crate; its bytes need not be
+        // UTF-8, so none of its source is kept
+        cx.codemap().new_filemap(path.to_str(), @~"");
         let u8_exprs = vec::map(src, |char| {
             mk_u8(cx, sp, *char)
         });
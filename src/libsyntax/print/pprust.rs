@@ -39,6 +39,8 @@ pub enum ann_node<'self> {
     node_item(@ps, @ast::item),
     node_expr(@ps, @ast::expr),
     node_pat(@ps, @ast::pat),
+    // Only passed to `post`, after the identifier is printed
+    node_ident(@ps, ast::ident),
 }
 pub struct pp_ann {
     pre: @fn(ann_node),
@@ -1488,6 +1490,7 @@ pub fn print_decl(s: @ps, decl: @ast::decl) {
 
 pub fn print_ident(s: @ps, ident: ast::ident) {
     word(s.s, *s.intr.get(ident));
+    (s.ann.post)(node_ident(s, ident));
 }
 
 pub fn print_for_decl(s: @ps, loc: @ast::local, coll: @ast::expr) {
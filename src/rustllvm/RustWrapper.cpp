@@ -40,6 +40,7 @@
 #include "llvm/Support/Debug.h"
 #include "llvm/Support/DynamicLibrary.h"
 #include "llvm/Support/Memory.h"
+#include "llvm/Support/Threading.h"
 #include "llvm/ExecutionEngine/ExecutionEngine.h"
 #include "llvm/ExecutionEngine/JIT.h"
 #include "llvm/ExecutionEngine/JITMemoryManager.h"
@@ -418,6 +419,30 @@ LLVMRustExecuteJIT(void* mem,
   return entry;
 }
 
+static bool CodegenInitialized = false;
+
+static void LLVMRustInitializeCodegen() {
+  if (CodegenInitialized)
+    return;
+
+  LLVMRustInitializeTargets();
+
+  int argc = 3;
+  const char* argv[] = {"rustc", "-arm-enable-ehabi",
+      "-arm-enable-ehabi-descriptors"};
+  cl::ParseCommandLineOptions(argc, argv);
+  CodegenInitialized = true;
+}
+
+// Must be called before any module is emitted from more than one thread:
+// target and option setup is not thread-safe, so it is done here, once.
+extern "C" bool LLVMRustStartMultithreading() {
+  LLVMRustInitializeCodegen();
+  if (!llvm_is_multithreaded())
+    llvm_start_multithreaded();
+  return llvm_is_multithreaded();
+}
+
 extern "C" bool
 LLVMRustWriteOutputFile(LLVMPassManagerRef PMR,
                         LLVMModuleRef M,
@@ -427,12 +452,7 @@ LLVMRustWriteOutputFile(LLVMPassManagerRef PMR,
                         CodeGenOpt::Level OptLevel,
 			bool EnableSegmentedStacks) {
 
-  LLVMRustInitializeTargets();
-
-  int argc = 3;
-  const char* argv[] = {"rustc", "-arm-enable-ehabi",
-      "-arm-enable-ehabi-descriptors"};
-  cl::ParseCommandLineOptions(argc, argv);
+  LLVMRustInitializeCodegen();
 
   TargetOptions Options;
   Options.NoFramePointerElim = true;
@@ -484,6 +504,36 @@ extern "C" LLVMModuleRef LLVMRustParseBitcode(LLVMMemoryBufferRef MemBuf) {
          ? NULL : M;
 }
 
+extern "C" LLVMModuleRef
+LLVMRustParseBitcodeInContext(LLVMContextRef C, LLVMMemoryBufferRef MemBuf) {
+  LLVMModuleRef M;
+  return LLVMParseBitcodeInContext(C, MemBuf, &M,
+                                   const_cast<char **>(&LLVMRustError))
+         ? NULL : M;
+}
+
+extern "C" LLVMModuleRef
+LLVMRustParseBitcodeBytes(const char *Data, size_t Len) {
+  MemoryBuffer *MB = MemoryBuffer::getMemBuffer(StringRef(Data, Len),
+                                                "", false);
+  LLVMModuleRef M;
+  LLVMBool Failed = LLVMParseBitcode(wrap(MB), &M,
+                                     const_cast<char **>(&LLVMRustError));
+  delete MB;
+  return Failed ? NULL : M;
+}
+
+// Moves the contents of Src into Dst; Src is left empty.
+extern "C" bool LLVMRustLinkModules(LLVMModuleRef Dst, LLVMModuleRef Src) {
+  static std::string Err;
+  if (Linker::LinkModules(unwrap(Dst), unwrap(Src), Linker::DestroySource,
+                          &Err)) {
+    LLVMRustError = Err.c_str();
+    return false;
+  }
+  return true;
+}
+
 extern "C" LLVMValueRef LLVMRustConstSmallInt(LLVMTypeRef IntTy, unsigned N,
                                               LLVMBool SignExtend) {
   return LLVMConstInt(IntTy, (unsigned long long)N, SignExtend);
@@ -0,0 +1,24 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub const scale: int = 3;
+
+pub struct Counter {
+    n: int
+}
+
+pub impl Counter {
+    fn bump(&mut self, by: int) { self.n += by * scale; }
+}
+
+// Not #[inline], so only LTO can inline it into the executable
+pub fn add(x: int, y: int) -> int { x + y }
+
+pub fn repeat<T: Copy>(x: T, n: uint) -> ~[T] { vec::from_elem(n, x) }
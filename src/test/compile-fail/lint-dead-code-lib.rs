@@ -0,0 +1,30 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -D dead-code
+
+#[crate_type="lib"];
+
+pub struct Exported { x: int }
+
+// Other crates can call these methods
+impl Exported {
+    fn get(&self) -> int { self.x }
+}
+
+struct Private { x: int }
+
+impl Private {
+    fn get(&self) -> int { self.x } //~ ERROR method is never used: `get`
+}
+
+pub fn private_x() -> int { Private { x: 1 }.x }
+
+fn unused() { } //~ ERROR function is never used: `unused`
@@ -0,0 +1,72 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -D dead-code
+
+pub fn exported() -> int { used_by_exported() }
+
+fn used_by_exported() -> int { USED as int }
+
+fn unused() { } //~ ERROR function is never used: `unused`
+
+// Only used by a dead function, so dead too
+fn only_used_by_unused() { } //~ ERROR function is never used
+
+fn uses_dead() { only_used_by_unused() } //~ ERROR function is never used
+
+const USED: uint = 1;
+const UNUSED: uint = 2; //~ ERROR constant is never used: `UNUSED`
+
+struct Used { x: int }
+struct Unused { x: int } //~ ERROR struct is never used: `Unused`
+
+enum Shape {
+    Circle(Used),
+    Square, //~ ERROR variant is never used: `Square`
+}
+
+impl Used {
+    fn area(&self) -> int { self.x }
+    fn perimeter(&self) -> int { self.x } //~ ERROR method is never used
+}
+
+// Used through the trait implemented for it
+struct Dropped { x: int }
+
+impl Drop for Dropped {
+    fn finalize(&self) { }
+}
+
+// Called from outside Rust
+#[no_mangle]
+fn called_by_name() { }
+
+extern fn called_through_a_pointer() { }
+
+fn _unused_on_purpose() { }
+
+#[allow(dead_code)]
+fn allowed() { }
+
+#[allow(dead_code)]
+mod allowed_mod {
+    fn inner() { }
+}
+
+#[test]
+fn test_something() { }
+
+fn main() {
+    let c = Circle(Used { x: 1 });
+    match c {
+        Circle(ref u) => { u.area(); }
+        _ => ()
+    }
+}
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 #[forbid(heap_memory)];
+#[allow(dead_code)];
 
 struct Foo {
     x: @int //~ ERROR type uses managed
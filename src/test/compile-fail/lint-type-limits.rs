@@ -9,6 +9,8 @@
 // except according to those terms.
 
 // compile-flags: -D type-limits
+#[allow(dead_code)];
+
 fn main() { }
 
 fn foo() {
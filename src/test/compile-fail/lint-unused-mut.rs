@@ -0,0 +1,46 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -D unused-mut
+#[allow(dead_code)];
+
+struct Point { x: int, y: int }
+
+fn takes_mut(v: &mut ~[int]) { v.push(1); }
+
+fn main() {
+    let mut a = 3; //~ ERROR variable does not need to be mutable
+    let mut (b, c) = (1, 2); //~ ERROR variables do not need to be mutable
+    io::println(fmt!("%d %d %d", a, b, c));
+
+    // Assigned, mutably borrowed, or mutated through a field
+    let mut d = 1;
+    d += 1;
+    let mut e = ~[];
+    takes_mut(&mut e);
+    let mut f = Point { x: 1, y: 2 };
+    f.x = 3;
+    let mut g = ~[1];
+    g[0] = 2;
+
+    // Mutated from inside a closure
+    let mut h = 0;
+    for e.each |_| { h += 1; }
+
+    // Only one of the bindings needs to be mutable
+    let mut (i, j) = (1, 2);
+    i = 3;
+    io::println(fmt!("%d %d %d %d", d, h, i, j));
+}
+
+fn arg(mut x: int) -> int { x } //~ ERROR variable does not need to be mutable
+
+fn used_arg(mut x: int) -> int { x += 1; x }
@@ -0,0 +1,37 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: -D unused-unsafe
+#[allow(dead_code)];
+
+extern mod foo {
+    pub fn bar();
+}
+
+unsafe fn baz() { }
+
+fn bad1() { unsafe { } } //~ ERROR unnecessary `unsafe` block
+fn bad2() { unsafe { let x = 1; x; } } //~ ERROR unnecessary `unsafe` block
+unsafe fn bad3() {
+    unsafe { baz() } //~ ERROR unnecessary `unsafe` block
+}
+fn bad4() {
+    unsafe {
+        unsafe { foo::bar() } //~ ERROR unnecessary `unsafe` block
+    }
+}
+
+fn good1() { unsafe { foo::bar() } }
+fn good2() { unsafe { baz() } }
+fn good3() { unsafe { do 3.times { baz(); } } }
+unsafe fn good4() { baz() }
+
+fn main() { }
@@ -26,7 +26,7 @@ fn main() {
       (a, b) => {}
       (b, a) => {}
     }
-    match a { //~ ERROR b not covered
+    match a { //~ ERROR `b` not covered
       a => {}
     }
     // This is exhaustive, though the algorithm got it wrong at one point
@@ -35,7 +35,7 @@ fn main() {
       (_, a) => {}
       (b, b) => {}
     }
-    match ~[Some(42), None, Some(21)] { //~ ERROR non-exhaustive patterns: vectors of length 0 not covered
+    match ~[Some(42), None, Some(21)] { //~ ERROR non-exhaustive patterns: `[]` not covered
         [Some(*), None, ..tail] => {}
         [Some(*), Some(*), ..tail] => {}
         [None] => {}
@@ -44,7 +44,7 @@ fn main() {
         [_, ..tail] => (),
         [] => ()
     }
-    match ~[0.5] { //~ ERROR non-exhaustive patterns: vectors of length 4 not covered
+    match ~[0.5] { //~ ERROR non-exhaustive patterns: `[_, _, _, _]` not covered
         [0.1, 0.2, 0.3] => (),
         [0.1, 0.2] => (),
         [0.1] => (),
@@ -0,0 +1,45 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+struct Foo { x: Option<int>, y: int }
+
+struct Pair(bool, bool);
+
+enum Shape { Circle(float), Square(Option<bool>) }
+
+fn main() {
+    match Some(Foo { x: Some(1), y: 2 }) {
+        //~^ ERROR `Some(Foo{x: None, _})` not covered
+        None => {}
+        Some(Foo { x: Some(_), _ }) => {}
+    }
+    match (true, Circle(1.0)) {
+        //~^ ERROR non-exhaustive patterns: `(false, Square(_))` not covered
+        (true, _) => {}
+        (false, Circle(_)) => {}
+    }
+    match Square(None) {
+        //~^ ERROR non-exhaustive patterns: `Square(Some(false))` not covered
+        Circle(_) | Square(None) => {}
+        Square(Some(true)) => {}
+    }
+    match Pair(true, false) {
+        //~^ ERROR non-exhaustive patterns: `Pair(false, true)` not covered
+        Pair(true, _) => {}
+        Pair(false, false) => {}
+    }
+    match ~[Some(1)] {
+        //~^ ERROR non-exhaustive patterns: `[None, _]` not covered
+        [] | [_] => {}
+        [Some(_), ..rest] => {}
+        [None, _, _, ..rest] => {}
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+fn main() {
+    match Some(3) {
+        Some(_) => {} //~ NOTE already matched by this pattern
+        None => {}
+        Some(3) => {} //~ ERROR unreachable pattern
+    }
+    match (true, false) {
+        (true, _) => {}
+        (false, true) => {}
+        (false, false) => {}
+        //~^ NOTE already matched by this pattern together with the ones
+        (_, _) => {} //~ ERROR unreachable pattern
+    }
+}
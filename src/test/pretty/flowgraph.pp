@@ -0,0 +1,6 @@
+digraph liveness_3 {
+    label="f (node 3)";
+    N0 [label="ln(0): Exit node"];
+    N1 [label="ln(1): Entry node"];
+    N1 -> N0;
+}
@@ -0,0 +1,51 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Private and generic functions called from other codegen units keep a
+// single definition, so a private function used as a task-local data key
+// is the same key everywhere.
+
+// compile-flags: --codegen-units 3 -O
+// xfail-fast
+
+use core::task::local_data;
+
+fn key(_x: @int) { }
+
+fn private_square(x: int) -> int { x * x }
+
+fn pair<T: Copy>(x: T) -> (T, T) { (x, x) }
+
+pub mod a {
+    pub fn set(x: int) {
+        unsafe { local_data::local_data_set(::key, @x); }
+    }
+    pub fn square_pair(x: int) -> (int, int) {
+        ::pair(::private_square(x))
+    }
+}
+
+pub mod b {
+    pub fn get() -> Option<int> {
+        unsafe { local_data::local_data_get(::key).map(|&x| *x) }
+    }
+    pub fn squares(x: int) -> int {
+        let (y, z) = ::pair(::private_square(x));
+        let (s, _) = ::pair(~"s");
+        y + z + s.len() as int
+    }
+}
+
+pub fn main() {
+    a::set(7);
+    fail_unless!(b::get() == Some(7));
+    fail_unless!(a::square_pair(3) == (9, 9));
+    fail_unless!(b::squares(3) == 19);
+}
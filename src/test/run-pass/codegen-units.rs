@@ -0,0 +1,38 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Calls and statics that cross codegen units still link.
+
+// compile-flags: --codegen-units 3 -O
+// xfail-fast
+
+pub static base: int = 10;
+pub static offsets: [int, ..3] = [1, 2, 3];
+
+pub mod a {
+    pub fn double(x: int) -> int { ::b::add(x, x) }
+}
+
+pub mod b {
+    pub fn add(x: int, y: int) -> int { x + y }
+    pub fn sum(v: &[int]) -> int {
+        let mut total = ::base;
+        for v.each |&x| { total = add(total, ::a::double(x)); }
+        total
+    }
+}
+
+pub fn generic<T: Copy>(x: T) -> ~[T] { ~[x, x] }
+
+pub fn main() {
+    fail_unless!(a::double(4) == 8);
+    fail_unless!(b::sum(offsets) == 22);
+    fail_unless!(generic(~"x") == ~[~"x", ~"x"]);
+}
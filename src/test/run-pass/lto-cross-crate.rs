@@ -0,0 +1,26 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An executable built with --lto links in the bitcode of a crate that was
+// also built with --lto, and of core.
+
+// compile-flags: --lto -O
+// xfail-fast - check-fast doesn't understand aux-build
+// aux-build:lto_cross_crate_lib.rs
+
+extern mod lto_cross_crate_lib;
+use lto_cross_crate_lib::{Counter, add, repeat};
+
+pub fn main() {
+    let mut c = Counter { n: 0 };
+    c.bump(add(1, 2));
+    fail_unless!(c.n == 9);
+    fail_unless!(repeat(~"x", 2) == ~[~"x", ~"x"]);
+}
@@ -0,0 +1,37 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Links the static library built from rustlib.rs into a C program
+
+#include <stdio.h>
+
+int rust_staticlib_sum(int n);
+int rust_staticlib_greeting_len(int n);
+int rust_staticlib_main(int argc, char **argv);
+
+// Called from the Rust main, while the runtime is running
+int c_callback(void) {
+    return rust_staticlib_greeting_len(3);
+}
+
+int main(int argc, char **argv) {
+    int sum = rust_staticlib_sum(10);
+    int status;
+    if (sum != 45) {
+        printf("rust_staticlib_sum(10) returned %d, not 45\n", sum);
+        return 1;
+    }
+    status = rust_staticlib_main(argc, argv);
+    if (status != 0) {
+        printf("rust_staticlib_main failed with %d\n", status);
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,53 @@
+// Copyright 2013 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Built with --staticlib, and called from main.c
+
+#[link(name = "rustlib", vers = "0.1")];
+
+use core::libc::c_int;
+
+extern {
+    // In main.c, calls rust_staticlib_greeting_len
+    fn c_callback() -> c_int;
+}
+
+// Called from C with no task: only the stack and `~` allocations
+#[no_mangle]
+pub extern fn rust_staticlib_sum(n: c_int) -> c_int {
+    let mut v = ~[];
+    for int::range(0, n as int) |i| { v.push(i); }
+    let mut sum = 0;
+    for v.each |&i| { sum += i; }
+    sum as c_int
+}
+
+// Called from C inside the task that runs main
+#[no_mangle]
+pub extern fn rust_staticlib_greeting_len(n: c_int) -> c_int {
+    let name = @str::repeat("ab", n as uint);
+    let greeting = fmt!("hello %s", *name);
+    greeting.len() as c_int
+}
+
+// Run by rust_staticlib_main
+fn main() {
+    let boxed = @mut ~[1, 2];
+    boxed.push(3);
+    fail_unless!(*boxed == ~[1, 2, 3]);
+
+    let (port, chan) = comm::stream();
+    do task::spawn {
+        chan.send(~"from a task");
+    }
+    fail_unless!(port.recv() == ~"from a task");
+
+    fail_unless!(unsafe { c_callback() } == 12);
+}